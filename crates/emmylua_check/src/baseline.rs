@@ -0,0 +1,406 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::Diagnostic;
+use serde::{Deserialize, Serialize};
+
+use crate::output::{DiagnosticReceiver, diagnostic_code};
+
+const BASELINE_VERSION: u32 = 1;
+
+/// Diagnostics accepted by `--write-baseline`, later runs with `--baseline` only report the
+/// diagnostics which are not in it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    version: u32,
+    diagnostics: Vec<BaselineEntry>,
+}
+
+/// A diagnostic is identified by its code, its file and the hash of its source line, so moving
+/// code up or down does not invalidate the baseline.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct BaselineKey {
+    file: String,
+    code: String,
+    line_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineEntry {
+    file: String,
+    code: String,
+    line_hash: String,
+    /// Only for humans reading the baseline, not part of the fingerprint
+    message: String,
+    count: usize,
+}
+
+impl BaselineEntry {
+    fn key(&self) -> BaselineKey {
+        BaselineKey {
+            file: self.file.clone(),
+            code: self.code.clone(),
+            line_hash: self.line_hash.clone(),
+        }
+    }
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read baseline {:?}: {}", path, e))?;
+        let baseline: Baseline = serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse baseline {:?}: {}", path, e))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(format!(
+                "Unsupported baseline version {} in {:?}, expected {}",
+                baseline.version, path, BASELINE_VERSION
+            ));
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+            && !parent.exists()
+        {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+        let mut text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        text.push('\n');
+        std::fs::write(path, text).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+
+    pub fn diagnostic_count(&self) -> usize {
+        self.diagnostics.iter().map(|entry| entry.count).sum()
+    }
+
+    /// Receive the diagnostics of `total_count` files and record all of them.
+    pub async fn collect(
+        total_count: usize,
+        db: &DbIndex,
+        workspace: &Path,
+        mut receiver: DiagnosticReceiver,
+    ) -> Self {
+        let mut entries: HashMap<BaselineKey, BaselineEntry> = HashMap::new();
+        let mut count = 0;
+        while count < total_count
+            && let Some((file_id, diagnostics)) = receiver.recv().await
+        {
+            count += 1;
+            let Some(diagnostics) = diagnostics else {
+                continue;
+            };
+            let fingerprinter = Fingerprinter::new(db, workspace, file_id);
+            for diagnostic in &diagnostics {
+                let key = fingerprinter.key(diagnostic);
+                entries
+                    .entry(key.clone())
+                    .or_insert_with(|| BaselineEntry {
+                        file: key.file,
+                        code: key.code,
+                        line_hash: key.line_hash,
+                        message: diagnostic.message.clone(),
+                        count: 0,
+                    })
+                    .count += 1;
+            }
+        }
+
+        let mut diagnostics: Vec<BaselineEntry> = entries.into_values().collect();
+        diagnostics.sort_by_key(|entry| entry.key());
+        Baseline {
+            version: BASELINE_VERSION,
+            diagnostics,
+        }
+    }
+}
+
+/// Removes the diagnostics of a baseline from the checked files and keeps track of the baseline
+/// entries which did not occur anymore.
+#[derive(Debug)]
+pub struct BaselineFilter {
+    workspace: PathBuf,
+    remaining: HashMap<BaselineKey, BaselineEntry>,
+    checked_files: HashSet<String>,
+    /// Only some files are checked, like with `--changed-files`, so entries of the other files
+    /// are not known to be stale.
+    partial: bool,
+}
+
+impl BaselineFilter {
+    pub fn new(baseline: Baseline, workspace: PathBuf, partial: bool) -> Self {
+        Self {
+            workspace,
+            remaining: baseline
+                .diagnostics
+                .into_iter()
+                .map(|entry| (entry.key(), entry))
+                .collect(),
+            checked_files: HashSet::new(),
+            partial,
+        }
+    }
+
+    /// Return the diagnostics of the file which are not in the baseline.
+    pub fn filter(
+        &mut self,
+        db: &DbIndex,
+        file_id: FileId,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        let fingerprinter = Fingerprinter::new(db, &self.workspace, file_id);
+        self.checked_files.insert(fingerprinter.file.clone());
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let key = fingerprinter.key(diagnostic);
+                match self.remaining.get_mut(&key) {
+                    Some(entry) if entry.count > 0 => {
+                        entry.count -= 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+
+    /// Baseline entries which no longer occur, their `count` is the number of occurrences which
+    /// are gone. A full check also reports the entries of deleted or renamed files.
+    pub fn finish(self) -> Vec<BaselineEntry> {
+        let mut stale: Vec<BaselineEntry> = self
+            .remaining
+            .into_values()
+            .filter(|entry| {
+                entry.count > 0 && (!self.partial || self.checked_files.contains(&entry.file))
+            })
+            .collect();
+        stale.sort_by_key(|entry| entry.key());
+        stale
+    }
+}
+
+pub fn print_stale_entries(baseline_path: &Path, stale: &[BaselineEntry]) {
+    if stale.is_empty() {
+        return;
+    }
+
+    let count: usize = stale.iter().map(|entry| entry.count).sum();
+    eprintln!(
+        "{} baseline entries no longer occur and can be pruned from {}:",
+        count,
+        baseline_path.display()
+    );
+    for entry in stale {
+        let times = if entry.count > 1 {
+            format!(" (x{})", entry.count)
+        } else {
+            String::new()
+        };
+        eprintln!(
+            "  {}: [{}] {}{}",
+            entry.file, entry.code, entry.message, times
+        );
+    }
+}
+
+/// Fingerprints the diagnostics of one file, shared with the writers which need a stable issue id.
+pub struct Fingerprinter<'a> {
+    file: String,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Fingerprinter<'a> {
+    pub fn new(db: &'a DbIndex, workspace: &Path, file_id: FileId) -> Self {
+        let vfs = db.get_vfs();
+        let file = vfs
+            .get_file_path(&file_id)
+            .map(|path| {
+                path.strip_prefix(workspace)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .unwrap_or_default();
+        let lines = vfs
+            .get_file_content(&file_id)
+            .map(|content| content.lines().collect())
+            .unwrap_or_default();
+        Self { file, lines }
+    }
+
+    fn key(&self, diagnostic: &Diagnostic) -> BaselineKey {
+        let code = diagnostic_code(diagnostic);
+        let line = self
+            .lines
+            .get(diagnostic.range.start.line as usize)
+            .copied()
+            .unwrap_or_default();
+        let normalized_line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        BaselineKey {
+            file: self.file.clone(),
+            code,
+            line_hash: format!("{:016x}", fnv1a(normalized_line.as_bytes())),
+        }
+    }
+
+    /// Stable id of the `occurrence`-th diagnostic with the same fingerprint in the file.
+    pub fn issue_id(&self, diagnostic: &Diagnostic, occurrence: usize) -> String {
+        let key = self.key(diagnostic);
+        let text = format!("{}:{}:{}:{}", key.file, key.code, key.line_hash, occurrence);
+        format!("{:016x}", fnv1a(text.as_bytes()))
+    }
+}
+
+/// The baseline is committed and shared between tool versions, so it can not use `DefaultHasher`
+/// whose output may change between Rust releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use emmylua_code_analysis::{FileId, VirtualWorkspace};
+    use lsp_types::{Diagnostic, NumberOrString, Position, Range};
+
+    use super::{Baseline, BaselineEntry, BaselineFilter};
+
+    fn diagnostic(code: &str, line: u32) -> Diagnostic {
+        Diagnostic {
+            range: Range::new(Position::new(line, 0), Position::new(line, 1)),
+            code: Some(NumberOrString::String(code.to_string())),
+            message: format!("{} at {}", code, line),
+            ..Default::default()
+        }
+    }
+
+    async fn collect(ws: &VirtualWorkspace, files: Vec<(FileId, Vec<Diagnostic>)>) -> Baseline {
+        let (sender, receiver) = tokio::sync::mpsc::channel(files.len().max(1));
+        let total_count = files.len();
+        for (file_id, diagnostics) in files {
+            sender.send((file_id, Some(diagnostics))).await.unwrap();
+        }
+        Baseline::collect(
+            total_count,
+            ws.analysis.compilation.get_db(),
+            &ws.virtual_url_generator.base,
+            receiver,
+        )
+        .await
+    }
+
+    fn new_filter(ws: &VirtualWorkspace, baseline: Baseline, partial: bool) -> BaselineFilter {
+        BaselineFilter::new(baseline, ws.virtual_url_generator.base.clone(), partial)
+    }
+
+    fn summary(stale: &[BaselineEntry]) -> Vec<(String, String, usize)> {
+        stale
+            .iter()
+            .map(|entry| (entry.file.clone(), entry.code.clone(), entry.count))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_match_by_code_file_and_line_content() {
+        let mut ws = VirtualWorkspace::new();
+        let a = ws.def_file("a.lua", "local x = 1\nlocal y = 2\n");
+        let b = ws.def_file("b.lua", "local x = 1\n");
+        let baseline = collect(&ws, vec![(a, vec![diagnostic("unused", 0)])]).await;
+
+        // the line moved down, the fingerprint only looks at its content
+        ws.def_file("a.lua", "\n  local x = 1\nlocal y = 2\n");
+        let mut filter = new_filter(&ws, baseline, false);
+        let db = ws.analysis.compilation.get_db();
+        assert!(
+            filter
+                .filter(db, a, vec![diagnostic("unused", 1)])
+                .is_empty()
+        );
+        // another code, another line or another file is a new diagnostic
+        assert_eq!(
+            filter
+                .filter(db, a, vec![diagnostic("undefined-global", 1)])
+                .len(),
+            1
+        );
+        assert_eq!(filter.filter(db, a, vec![diagnostic("unused", 2)]).len(), 1);
+        assert_eq!(filter.filter(db, b, vec![diagnostic("unused", 0)]).len(), 1);
+        assert!(filter.finish().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_counts() {
+        let mut ws = VirtualWorkspace::new();
+        let a = ws.def_file("a.lua", "local x = 1\nlocal x = 1\nlocal x = 1\n");
+        let baseline = collect(
+            &ws,
+            vec![(a, vec![diagnostic("unused", 0), diagnostic("unused", 1)])],
+        )
+        .await;
+        assert_eq!(baseline.diagnostic_count(), 2);
+
+        let db = ws.analysis.compilation.get_db();
+        let mut filter = new_filter(&ws, baseline, false);
+        let reported = filter.filter(
+            db,
+            a,
+            vec![
+                diagnostic("unused", 0),
+                diagnostic("unused", 1),
+                diagnostic("unused", 2),
+            ],
+        );
+        assert_eq!(reported.len(), 1);
+        assert!(filter.finish().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stale_entries() {
+        let mut ws = VirtualWorkspace::new();
+        let a = ws.def_file("a.lua", "local x = 1\nlocal y = 2\n");
+        let b = ws.def_file("b.lua", "local z = 1\n");
+        let baseline = collect(
+            &ws,
+            vec![
+                (a, vec![diagnostic("unused", 0), diagnostic("unused", 1)]),
+                (b, vec![diagnostic("unused", 0)]),
+            ],
+        )
+        .await;
+        let text = serde_json::to_string(&baseline).unwrap();
+
+        // b.lua was deleted, a full run reports its entries too
+        let uri = ws.virtual_url_generator.new_uri("b.lua");
+        ws.analysis.remove_file_by_uri(&uri);
+        let db = ws.analysis.compilation.get_db();
+        let mut filter = new_filter(&ws, serde_json::from_str(&text).unwrap(), false);
+        filter.filter(db, a, vec![diagnostic("unused", 0)]);
+        assert_eq!(
+            summary(&filter.finish()),
+            vec![
+                ("a.lua".to_string(), "unused".to_string(), 1),
+                ("b.lua".to_string(), "unused".to_string(), 1),
+            ]
+        );
+
+        // with `--changed-files` only a.lua is checked, entries of other files are kept
+        let mut filter = new_filter(&ws, serde_json::from_str(&text).unwrap(), true);
+        filter.filter(db, a, vec![diagnostic("unused", 0)]);
+        assert_eq!(
+            summary(&filter.finish()),
+            vec![("a.lua".to_string(), "unused".to_string(), 1)]
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    io::BufRead,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use emmylua_code_analysis::{DbIndex, FileId, collect_affected_files};
+
+/// Result of the changed files helpers, errors are reported by `run_check`
+type CheckResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+/// Collect the paths given by `--changed-files` or `--changed-since`, `None` if neither is set.
+///
+/// A single `-` in `--changed-files` reads the paths from stdin, one per line. Relative paths are
+/// resolved against `cwd`, paths listed by git against `main_path`.
+pub fn collect_changed_paths(
+    changed_files: Option<Vec<PathBuf>>,
+    changed_since: Option<String>,
+    cwd: &Path,
+    main_path: &Path,
+) -> CheckResult<Option<Vec<PathBuf>>> {
+    if let Some(git_ref) = changed_since {
+        let paths = git_changed_paths(&git_ref, main_path)?;
+        return Ok(Some(
+            paths.into_iter().map(|path| main_path.join(path)).collect(),
+        ));
+    }
+
+    let Some(changed_files) = changed_files else {
+        return Ok(None);
+    };
+
+    let mut paths = Vec::new();
+    for path in changed_files {
+        if path.as_os_str() == "-" {
+            for line in std::io::stdin().lock().lines() {
+                let line = line?;
+                let line = line.trim();
+                if !line.is_empty() {
+                    paths.push(PathBuf::from(line));
+                }
+            }
+        } else {
+            paths.push(path);
+        }
+    }
+
+    Ok(Some(
+        paths
+            .into_iter()
+            .map(|path| {
+                if path.is_absolute() {
+                    path
+                } else {
+                    cwd.join(path)
+                }
+            })
+            .collect(),
+    ))
+}
+
+/// Files changed since `git_ref` and untracked files, relative to `main_path`.
+fn git_changed_paths(git_ref: &str, main_path: &Path) -> CheckResult<Vec<PathBuf>> {
+    let mut paths = run_git(
+        &["diff", "--name-only", "--relative", git_ref, "--"],
+        main_path,
+    )?;
+    // new files are not in the diff until they are added
+    for path in run_git(&["ls-files", "--others", "--exclude-standard"], main_path)? {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+fn run_git(args: &[&str], main_path: &Path) -> CheckResult<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(main_path)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Keep the files of `check_files` which are changed or whose analysis depends on a changed file.
+///
+/// A changed path which no longer exists is a removed module, the files that required it are
+/// affected too.
+pub fn filter_affected_files(
+    db: &DbIndex,
+    check_files: Vec<FileId>,
+    changed_paths: &[PathBuf],
+) -> Vec<FileId> {
+    let changed_paths: HashSet<PathBuf> = changed_paths
+        .iter()
+        .map(|path| normalize_path(path))
+        .collect();
+
+    let vfs = db.get_vfs();
+    let mut changed_file_ids: Vec<FileId> = vfs
+        .get_all_file_ids()
+        .into_iter()
+        .filter(|file_id| {
+            vfs.get_file_path(file_id)
+                .is_some_and(|path| changed_paths.contains(&normalize_path(path)))
+        })
+        .collect();
+
+    let dependency_index = db.get_file_dependencies_index();
+    let module_index = db.get_module_index();
+    for path in &changed_paths {
+        if path.exists() {
+            continue;
+        }
+        if let Some((module_path, _)) = path
+            .to_str()
+            .and_then(|path| module_index.get_module_path(path))
+        {
+            changed_file_ids
+                .extend(dependency_index.get_unresolved_require_files_by_path(&module_path));
+        }
+    }
+
+    let affected: HashSet<FileId> = collect_affected_files(db, &changed_file_ids)
+        .into_iter()
+        .collect();
+    check_files
+        .into_iter()
+        .filter(|file_id| affected.contains(file_id))
+        .collect()
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        process::Command,
+    };
+
+    use emmylua_code_analysis::{FileId, VirtualWorkspace};
+
+    use super::{collect_changed_paths, filter_affected_files};
+
+    #[test]
+    fn test_changed_files_paths() {
+        let cwd = std::env::current_dir().unwrap().join("work");
+        let main_path = std::env::current_dir().unwrap().join("main");
+        let absolute = std::env::current_dir().unwrap().join("other/b.lua");
+        let paths = collect_changed_paths(
+            Some(vec![PathBuf::from("src/a.lua"), absolute.clone()]),
+            None,
+            &cwd,
+            &main_path,
+        )
+        .unwrap();
+        assert_eq!(paths, Some(vec![cwd.join("src/a.lua"), absolute]));
+        assert_eq!(
+            collect_changed_paths(None, None, &cwd, &main_path).unwrap(),
+            None
+        );
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_changed_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let main_path = dir.path().join("scripts");
+        fs::create_dir_all(&main_path).unwrap();
+        fs::write(main_path.join("a.lua"), "return 1\n").unwrap();
+        fs::write(main_path.join("b.lua"), "return 2\n").unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-q", "-m", "init"]);
+
+        fs::write(main_path.join("a.lua"), "return 3\n").unwrap();
+        fs::write(main_path.join("c.lua"), "return 4\n").unwrap();
+        let mut paths = collect_changed_paths(
+            None,
+            Some("HEAD".to_string()),
+            Path::new("/unused"),
+            &main_path,
+        )
+        .unwrap()
+        .unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![main_path.join("a.lua"), main_path.join("c.lua")]
+        );
+    }
+
+    /// The check files affected by changes of `changed`, as file names.
+    fn affected_names(ws: &VirtualWorkspace, file_ids: &[FileId], changed: &[&str]) -> Vec<String> {
+        let db = ws.analysis.compilation.get_db();
+        let changed_paths: Vec<PathBuf> = changed
+            .iter()
+            .map(|name| ws.virtual_url_generator.new_path(name))
+            .collect();
+        let mut names: Vec<String> = filter_affected_files(db, file_ids.to_vec(), &changed_paths)
+            .into_iter()
+            .filter_map(|file_id| {
+                let path = db.get_vfs().get_file_path(&file_id)?;
+                let path = path.strip_prefix(&ws.virtual_url_generator.base).ok()?;
+                Some(path.to_string_lossy().replace('\\', "/"))
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_affected_dependents() {
+        let mut ws = VirtualWorkspace::new();
+        let file_ids = ws.def_files(vec![
+            ("changed/lib.lua", "return {}"),
+            (
+                "changed/mid.lua",
+                r#"local lib = require("changed.lib") return {}"#,
+            ),
+            ("changed/top.lua", r#"local mid = require("changed.mid")"#),
+            ("changed/other.lua", "return {}"),
+        ]);
+        assert_eq!(
+            affected_names(&ws, &file_ids, &["changed/lib.lua"]),
+            vec!["changed/lib.lua", "changed/mid.lua", "changed/top.lua"]
+        );
+    }
+
+    #[test]
+    fn test_affected_deleted_module() {
+        let mut ws = VirtualWorkspace::new();
+        let file_ids = ws.def_files(vec![
+            ("deleted/a.lua", r#"local foo = require("deleted.foo")"#),
+            ("deleted/b.lua", r#"local util = require("deleted.a.util")"#),
+            ("deleted/c.lua", r#"local util = require("deleted.x.util")"#),
+        ]);
+        assert_eq!(
+            affected_names(&ws, &file_ids, &["deleted/foo/init.lua"]),
+            vec!["deleted/a.lua"]
+        );
+        assert_eq!(
+            affected_names(&ws, &file_ids, &["deleted/a/util.lua"]),
+            vec!["deleted/b.lua"]
+        );
+    }
+}
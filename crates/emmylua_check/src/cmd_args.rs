@@ -36,7 +36,7 @@ pub struct CmdArgs {
     )]
     pub output_format: OutputFormat,
 
-    /// Specify output destination (stdout or a file path, only used when output_format is json)
+    /// Specify output destination (stdout or a file path, not used when output_format is text)
     #[cfg_attr(feature = "cli", arg(long, default_value = "stdout"))]
     pub output: OutputDestination,
 
@@ -44,6 +44,43 @@ pub struct CmdArgs {
     #[cfg_attr(feature = "cli", arg(long))]
     pub warnings_as_errors: bool,
 
+    /// Apply the safe fixes of the diagnostics to the files before reporting the remaining
+    /// diagnostics
+    #[cfg_attr(feature = "cli", arg(long, conflicts_with = "fix_dry_run"))]
+    pub fix: bool,
+
+    /// Print the safe fixes of the diagnostics as unified diffs instead of applying them
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub fix_dry_run: bool,
+
+    /// Only report diagnostics which are not in this baseline file
+    #[cfg_attr(feature = "cli", arg(long, conflicts_with = "write_baseline"))]
+    pub baseline: Option<PathBuf>,
+
+    /// Record the current diagnostics in this baseline file instead of reporting them
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Only report diagnostics for these files and the files depending on them.
+    /// Use "-" to read the paths from stdin, one per line
+    #[cfg_attr(feature = "cli", arg(long, value_delimiter = ','))]
+    pub changed_files: Option<Vec<PathBuf>>,
+
+    /// Only report diagnostics for files changed since this git revision and the files depending
+    /// on them
+    #[cfg_attr(feature = "cli", arg(long, conflicts_with = "changed_files"))]
+    pub changed_since: Option<String>,
+
+    /// Also report the exported members, global functions and class fields nothing in the
+    /// workspace uses
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub unused_exports: bool,
+
+    /// Also report the files which the `workspace.entryPoints` of the configuration never
+    /// require
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub unreachable_files: bool,
+
     /// Verbose output
     #[cfg_attr(feature = "cli", arg(long))]
     pub verbose: bool,
@@ -55,6 +92,14 @@ pub enum OutputFormat {
     Json,
     Text,
     Sarif,
+    /// JUnit XML, one test suite per file
+    Junit,
+    /// Checkstyle XML
+    Checkstyle,
+    /// GitLab Code Quality JSON
+    Gitlab,
+    /// GitHub Actions workflow commands
+    Github,
 }
 
 #[allow(unused)]
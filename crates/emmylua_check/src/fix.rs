@@ -0,0 +1,300 @@
+use std::{error::Error, path::Path, sync::Arc};
+
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, FileId, FixEdit, apply_fix_edits, write_file_with_encoding,
+};
+use rowan::{TextRange, TextSize};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+/// Number of unchanged lines around a change in the printed diffs
+const DIFF_CONTEXT: usize = 3;
+
+/// Apply the machine applicable fixes of `file_ids` and update the analysis with the fixed text,
+/// or only print the fixes as unified diffs when `dry_run` is set.
+pub async fn fix_files(
+    analysis: EmmyLuaAnalysis,
+    file_ids: &[FileId],
+    workspace: &Path,
+    dry_run: bool,
+) -> Result<EmmyLuaAnalysis, Box<dyn Error + Sync + Send>> {
+    let analysis = Arc::new(analysis);
+    let mut tasks = JoinSet::new();
+    for file_id in file_ids.iter().copied() {
+        let analysis = analysis.clone();
+        tasks.spawn(async move {
+            let edits = analysis.get_file_fix_edits(file_id, CancellationToken::new());
+            (file_id, edits.unwrap_or_default())
+        });
+    }
+
+    let mut fixed_files = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        let (file_id, edits) = result?;
+        if !edits.is_empty() {
+            fixed_files.push((file_id, edits));
+        }
+    }
+    let mut analysis = Arc::try_unwrap(analysis)
+        .map_err(|_| "analysis is still shared after all fix tasks finished")?;
+
+    let vfs = analysis.compilation.get_db().get_vfs();
+    let mut fixed_files: Vec<_> = fixed_files
+        .into_iter()
+        .filter_map(|(file_id, edits)| {
+            let path = vfs.get_file_path(&file_id)?.clone();
+            let text = vfs.get_file_content(&file_id)?.clone();
+            Some((path, text, edits))
+        })
+        .collect();
+    fixed_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut edit_count = 0;
+    for (path, text, edits) in &fixed_files {
+        edit_count += edits.len();
+        if dry_run {
+            let relative_path = path.strip_prefix(workspace).unwrap_or(path);
+            let label = relative_path.to_string_lossy().replace('\\', "/");
+            print!("{}", unified_diff(&label, text, edits));
+            continue;
+        }
+
+        let fixed_text = apply_fix_edits(text, edits);
+        write_file_with_encoding(path, &fixed_text, &analysis.emmyrc.workspace.encoding)
+            .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+        analysis.update_file_by_path(path, Some(fixed_text));
+    }
+
+    if dry_run {
+        eprintln!(
+            "{} fixes available in {} files",
+            edit_count,
+            fixed_files.len()
+        );
+    } else {
+        eprintln!(
+            "Applied {} fixes in {} files",
+            edit_count,
+            fixed_files.len()
+        );
+    }
+
+    Ok(analysis)
+}
+
+/// Lines of the original text touched by some edits and the same lines after the edits.
+struct Change<'a> {
+    first_line: usize,
+    last_line: usize,
+    edits: Vec<&'a FixEdit>,
+}
+
+/// Unified diff of `text` before and after `edits`, computed from the edited lines only.
+fn unified_diff(label: &str, text: &str, edits: &[FixEdit]) -> String {
+    let mut lines: Vec<&str> = text.split_inclusive('\n').collect();
+    if lines.is_empty() {
+        lines.push("");
+    }
+    let mut line_starts = Vec::with_capacity(lines.len() + 1);
+    let mut offset = 0;
+    for line in &lines {
+        line_starts.push(offset);
+        offset += line.len();
+    }
+    line_starts.push(offset);
+    let line_of = |offset: TextSize| -> usize {
+        let offset = usize::from(offset);
+        line_starts
+            .partition_point(|start| *start <= offset)
+            .saturating_sub(1)
+            .min(lines.len().saturating_sub(1))
+    };
+
+    let mut changes: Vec<Change> = Vec::new();
+    for edit in edits {
+        let first_line = line_of(edit.range.start());
+        let last_line = if edit.range.is_empty() {
+            first_line
+        } else {
+            line_of(edit.range.end() - TextSize::from(1))
+        };
+        match changes.last_mut() {
+            Some(change) if first_line <= change.last_line => {
+                change.last_line = change.last_line.max(last_line);
+                change.edits.push(edit);
+            }
+            _ => changes.push(Change {
+                first_line,
+                last_line,
+                edits: vec![edit],
+            }),
+        }
+    }
+
+    let mut hunks: Vec<Vec<Change>> = Vec::new();
+    for change in changes {
+        match hunks.last_mut() {
+            Some(hunk)
+                if change.first_line
+                    <= hunk.last().map_or(0, |last| last.last_line) + 2 * DIFF_CONTEXT + 1 =>
+            {
+                hunk.push(change)
+            }
+            _ => hunks.push(vec![change]),
+        }
+    }
+
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", label, label);
+    let mut line_delta: isize = 0;
+    for hunk in hunks {
+        let (Some(first), Some(last)) = (hunk.first(), hunk.last()) else {
+            continue;
+        };
+        let start = first.first_line.saturating_sub(DIFF_CONTEXT);
+        let end = (last.last_line + DIFF_CONTEXT).min(lines.len().saturating_sub(1));
+
+        let mut body = String::new();
+        let mut old_count = 0;
+        let mut new_count = 0;
+        let mut line = start;
+        for change in &hunk {
+            for context_line in &lines[line..change.first_line] {
+                push_diff_line(&mut body, ' ', context_line);
+            }
+            old_count += change.first_line - line;
+            new_count += change.first_line - line;
+
+            let segment_start = TextSize::from(line_starts[change.first_line] as u32);
+            let segment_end = TextSize::from(line_starts[change.last_line + 1] as u32);
+            let segment = &text[TextRange::new(segment_start, segment_end)];
+            let segment_edits: Vec<FixEdit> = change
+                .edits
+                .iter()
+                .map(|edit| FixEdit {
+                    range: edit.range - segment_start,
+                    new_text: edit.new_text.clone(),
+                })
+                .collect();
+            let new_segment = apply_fix_edits(segment, &segment_edits);
+            let old_lines: Vec<&str> = segment.split_inclusive('\n').collect();
+            let new_lines: Vec<&str> = new_segment.split_inclusive('\n').collect();
+            // an insertion or deletion of whole lines leaves the rest of the segment unchanged
+            let prefix = old_lines
+                .iter()
+                .zip(&new_lines)
+                .take_while(|(old, new)| old == new)
+                .count();
+            let suffix = old_lines[prefix..]
+                .iter()
+                .rev()
+                .zip(new_lines[prefix..].iter().rev())
+                .take_while(|(old, new)| old == new)
+                .count();
+            for context_line in &old_lines[..prefix] {
+                push_diff_line(&mut body, ' ', context_line);
+            }
+            for old_line in &old_lines[prefix..old_lines.len() - suffix] {
+                push_diff_line(&mut body, '-', old_line);
+            }
+            for new_line in &new_lines[prefix..new_lines.len() - suffix] {
+                push_diff_line(&mut body, '+', new_line);
+            }
+            for context_line in &old_lines[old_lines.len() - suffix..] {
+                push_diff_line(&mut body, ' ', context_line);
+            }
+            old_count += old_lines.len();
+            new_count += new_lines.len();
+            line = change.last_line + 1;
+        }
+        if line <= end {
+            for context_line in &lines[line..=end] {
+                push_diff_line(&mut body, ' ', context_line);
+            }
+            old_count += end + 1 - line;
+            new_count += end + 1 - line;
+        }
+
+        let new_start = (start as isize + 1 + line_delta).max(0);
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            start + 1,
+            old_count,
+            new_start,
+            new_count
+        ));
+        diff.push_str(&body);
+        line_delta += new_count as isize - old_count as isize;
+    }
+
+    diff
+}
+
+fn push_diff_line(diff: &mut String, prefix: char, line: &str) {
+    diff.push(prefix);
+    diff.push_str(line);
+    if !line.ends_with('\n') {
+        diff.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use emmylua_code_analysis::FixEdit;
+    use rowan::{TextRange, TextSize};
+
+    use super::unified_diff;
+
+    fn edit(start: u32, end: u32, new_text: &str) -> FixEdit {
+        FixEdit {
+            range: TextRange::new(TextSize::from(start), TextSize::from(end)),
+            new_text: new_text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_insert_only() {
+        let diff = unified_diff("a.lua", "a\nb\nc\n", &[edit(2, 2, "x\n")]);
+        assert_eq!(
+            diff,
+            "--- a/a.lua\n+++ b/a.lua\n@@ -1,3 +1,4 @@\n a\n+x\n b\n c\n"
+        );
+    }
+
+    #[test]
+    fn test_delete_only() {
+        let diff = unified_diff("a.lua", "a\nb\nc\n", &[edit(2, 4, "")]);
+        assert_eq!(
+            diff,
+            "--- a/a.lua\n+++ b/a.lua\n@@ -1,3 +1,2 @@\n a\n-b\n c\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_hunk() {
+        let text: String = (1..=20).map(|i| format!("l{}\n", i)).collect();
+        // `l2` is removed and `l16` replaced by two lines, the second hunk starts one line
+        // earlier in the new text
+        let diff = unified_diff("a.lua", &text, &[edit(3, 6, ""), edit(51, 55, "x\ny\n")]);
+        assert_eq!(
+            diff,
+            concat!(
+                "--- a/a.lua\n+++ b/a.lua\n",
+                "@@ -1,5 +1,4 @@\n l1\n-l2\n l3\n l4\n l5\n",
+                "@@ -13,7 +12,8 @@\n l13\n l14\n l15\n-l16\n+x\n+y\n l17\n l18\n l19\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_no_trailing_newline() {
+        let diff = unified_diff("a.lua", "a\nb", &[edit(2, 3, "c")]);
+        assert_eq!(
+            diff,
+            concat!(
+                "--- a/a.lua\n+++ b/a.lua\n@@ -1,2 +1,2 @@\n a\n",
+                "-b\n\\ No newline at end of file\n",
+                "+c\n\\ No newline at end of file\n"
+            )
+        );
+    }
+}
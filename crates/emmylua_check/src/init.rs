@@ -1,5 +1,6 @@
 use emmylua_code_analysis::{
-    EmmyLuaAnalysis, Emmyrc, LuaFileInfo, load_configs, load_workspace_files, update_code_style,
+    EmmyLuaAnalysis, Emmyrc, LuaFileInfo, get_index_cache_path, load_configs, load_workspace_files,
+    update_code_style,
 };
 use fern::Dispatch;
 use log::LevelFilter;
@@ -131,7 +132,19 @@ pub fn load_workspace(
             }
         })
         .collect();
-    analysis.update_files_by_path(files);
+    let index_cache_path = if emmyrc.workspace.enable_index_cache {
+        get_index_cache_path(&emmyrc, &main_path)
+    } else {
+        None
+    };
+    if let Some(cache_path) = index_cache_path {
+        analysis.update_files_by_path_with_cache(files, &cache_path);
+        if let Err(e) = analysis.save_index_cache(&cache_path) {
+            log::warn!("Failed to save index cache {:?}: {}", cache_path, e);
+        }
+    } else {
+        analysis.update_files_by_path(files);
+    }
 
     Some(analysis)
 }
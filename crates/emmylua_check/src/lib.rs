@@ -1,9 +1,14 @@
+mod baseline;
+mod changed_files;
 pub mod cmd_args;
+mod fix;
 mod init;
 mod output;
 mod terminal_display;
 
+use baseline::{Baseline, BaselineFilter, print_stale_entries};
 pub use cmd_args::*;
+use emmylua_code_analysis::{DiagnosticCode, EmmyLuaAnalysis};
 use output::output_result;
 use std::{error::Error, sync::Arc};
 use tokio_util::sync::CancellationToken;
@@ -30,7 +35,12 @@ pub async fn run_check(cmd_args: CmdArgs) -> Result<(), Box<dyn Error + Sync + S
         .ok_or("Failed to load workspace")?
         .clone();
 
-    let analysis = match init::load_workspace(
+    let baseline = match &cmd_args.baseline {
+        Some(path) => Some(Baseline::load(path)?),
+        None => None,
+    };
+
+    let mut analysis = match init::load_workspace(
         main_path.clone(),
         workspaces.clone(),
         cmd_args.config,
@@ -43,7 +53,45 @@ pub async fn run_check(cmd_args: CmdArgs) -> Result<(), Box<dyn Error + Sync + S
     };
 
     let db = analysis.compilation.get_db();
-    let need_check_files = db.get_module_index().get_main_workspace_file_ids();
+    let mut need_check_files = db.get_module_index().get_main_workspace_file_ids();
+    let changed_paths = changed_files::collect_changed_paths(
+        cmd_args.changed_files,
+        cmd_args.changed_since,
+        &cwd,
+        &main_path,
+    )?;
+    if let Some(changed_paths) = &changed_paths {
+        let total = need_check_files.len();
+        need_check_files =
+            changed_files::filter_affected_files(db, need_check_files, changed_paths);
+        log::info!(
+            "Checking {} of {} files affected by {} changed paths",
+            need_check_files.len(),
+            total,
+            changed_paths.len()
+        );
+    }
+
+    if cmd_args.fix || cmd_args.fix_dry_run {
+        analysis = fix::fix_files(
+            analysis,
+            &need_check_files,
+            &main_path,
+            cmd_args.fix_dry_run,
+        )
+        .await?;
+    }
+
+    let mut report_codes = Vec::new();
+    if cmd_args.unused_exports {
+        report_codes.push(DiagnosticCode::UnusedExport);
+    }
+    if cmd_args.unreachable_files {
+        report_codes.push(DiagnosticCode::UnreachableFile);
+    }
+    if !report_codes.is_empty() {
+        enable_diagnostics(&mut analysis, &report_codes);
+    }
 
     let (sender, receiver) = tokio::sync::mpsc::channel(100);
     let analysis = Arc::new(analysis);
@@ -57,7 +105,22 @@ pub async fn run_check(cmd_args: CmdArgs) -> Result<(), Box<dyn Error + Sync + S
             sender.send((file_id, diagnostics)).await.unwrap();
         });
     }
+    // the receiver stops once every task is done, even when no file needs to be checked
+    drop(sender);
+
+    if let Some(path) = cmd_args.write_baseline {
+        let baseline = Baseline::collect(need_check_files.len(), db, &main_path, receiver).await;
+        baseline.save(&path)?;
+        eprintln!(
+            "Wrote {} diagnostics to baseline {}",
+            baseline.diagnostic_count(),
+            path.display()
+        );
+        return Ok(());
+    }
 
+    let mut baseline_filter = baseline
+        .map(|baseline| BaselineFilter::new(baseline, main_path.clone(), changed_paths.is_some()));
     let exit_code = output_result(
         need_check_files.len(),
         db,
@@ -66,9 +129,14 @@ pub async fn run_check(cmd_args: CmdArgs) -> Result<(), Box<dyn Error + Sync + S
         cmd_args.output_format,
         cmd_args.output,
         cmd_args.warnings_as_errors,
+        baseline_filter.as_mut(),
     )
     .await;
 
+    if let (Some(baseline_filter), Some(path)) = (baseline_filter, &cmd_args.baseline) {
+        print_stale_entries(path, &baseline_filter.finish());
+    }
+
     if exit_code != 0 {
         return Err(format!("exit code: {}", exit_code).into());
     }
@@ -76,3 +144,14 @@ pub async fn run_check(cmd_args: CmdArgs) -> Result<(), Box<dyn Error + Sync + S
     eprintln!("Check finished");
     Ok(())
 }
+
+/// Enable `codes` for this run, even when the configuration disables them.
+fn enable_diagnostics(analysis: &mut EmmyLuaAnalysis, codes: &[DiagnosticCode]) {
+    let mut emmyrc = analysis.get_emmyrc().as_ref().clone();
+    emmyrc
+        .diagnostics
+        .disable
+        .retain(|code| !codes.contains(code));
+    emmyrc.diagnostics.enables.extend_from_slice(codes);
+    analysis.diagnostic.update_config(Arc::new(emmyrc));
+}
@@ -0,0 +1,72 @@
+use std::{fs::File, io::Write};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::Diagnostic;
+
+use crate::cmd_args::OutputDestination;
+
+use super::{OutputWriter, Severity, diagnostic_code, open_output, xml_escape};
+
+/// Checkstyle XML, every checked file is listed with its diagnostics as `error` elements.
+#[derive(Debug)]
+pub struct CheckstyleOutputWriter {
+    output: Option<File>,
+    files: Vec<(String, Vec<Diagnostic>)>,
+}
+
+impl CheckstyleOutputWriter {
+    pub fn new(output: OutputDestination) -> Self {
+        CheckstyleOutputWriter {
+            output: open_output(output),
+            files: Vec::new(),
+        }
+    }
+
+    /// The whole report, sorted by file so the output does not depend on the check order
+    pub(super) fn render(&mut self) -> String {
+        self.files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<checkstyle version=\"4.3\">\n");
+        for (file_path, diagnostics) in &self.files {
+            xml.push_str(&format!("  <file name=\"{}\">\n", xml_escape(file_path)));
+            for diagnostic in diagnostics {
+                // checkstyle only knows error, warning, info and ignore
+                let severity = match Severity::of(diagnostic) {
+                    Some(Severity::Error) => "error",
+                    Some(Severity::Warning) => "warning",
+                    _ => "info",
+                };
+                xml.push_str(&format!(
+                    "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"emmylua.{}\"/>\n",
+                    diagnostic.range.start.line + 1,
+                    diagnostic.range.start.character + 1,
+                    severity,
+                    xml_escape(&diagnostic.message),
+                    xml_escape(&diagnostic_code(diagnostic))
+                ));
+            }
+            xml.push_str("  </file>\n");
+        }
+        xml.push_str("</checkstyle>\n");
+        xml
+    }
+}
+
+impl OutputWriter for CheckstyleOutputWriter {
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        let file_path = db.get_vfs().get_file_path(&file_id).unwrap();
+        self.files
+            .push((file_path.to_string_lossy().to_string(), diagnostics));
+    }
+
+    fn finish(&mut self) {
+        let xml = self.render();
+        if let Some(output) = self.output.as_mut() {
+            output.write_all(xml.as_bytes()).unwrap();
+        } else {
+            print!("{}", xml);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::{fs::File, io::Write, path::PathBuf};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::Diagnostic;
+
+use crate::cmd_args::OutputDestination;
+
+use super::{OutputWriter, Severity, diagnostic_code, open_output, relative_path};
+
+/// GitHub Actions workflow commands, the runner turns every line into an annotation on the pull
+/// request.
+#[derive(Debug)]
+pub struct GithubOutputWriter {
+    output: Option<File>,
+    root: PathBuf,
+    commands: Vec<(String, u32, String)>,
+}
+
+impl GithubOutputWriter {
+    pub fn new(output: OutputDestination, root: PathBuf) -> Self {
+        GithubOutputWriter {
+            output: open_output(output),
+            root,
+            commands: Vec::new(),
+        }
+    }
+
+    /// The whole report, sorted by file so the output does not depend on the check order
+    pub(super) fn render(&mut self) -> String {
+        self.commands.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+        let mut text = String::new();
+        for (_, _, command) in &self.commands {
+            text.push_str(command);
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl OutputWriter for GithubOutputWriter {
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        let file_path = relative_path(db, file_id, &self.root);
+        for diagnostic in diagnostics {
+            let level = match Severity::of(&diagnostic) {
+                Some(Severity::Error) => "error",
+                Some(Severity::Warning) => "warning",
+                _ => "notice",
+            };
+            let range = diagnostic.range;
+            let command = format!(
+                "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+                level,
+                escape_property(&file_path),
+                range.start.line + 1,
+                range.start.character + 1,
+                range.end.line + 1,
+                range.end.character + 1,
+                escape_property(&format!("emmylua_check ({})", diagnostic_code(&diagnostic))),
+                escape_data(&diagnostic.message)
+            );
+            self.commands
+                .push((file_path.clone(), range.start.line, command));
+        }
+    }
+
+    fn finish(&mut self) {
+        let text = self.render();
+        if let Some(output) = self.output.as_mut() {
+            output.write_all(text.as_bytes()).unwrap();
+        } else {
+            print!("{}", text);
+        }
+    }
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
@@ -0,0 +1,92 @@
+use std::{collections::HashMap, fs::File, io::Write, path::PathBuf};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::Diagnostic;
+use serde_json::{Value, json};
+
+use crate::{baseline::Fingerprinter, cmd_args::OutputDestination};
+
+use super::{OutputWriter, Severity, diagnostic_code, open_output, relative_path};
+
+/// GitLab Code Quality report. GitLab compares the fingerprints of the base and the merge request
+/// pipeline, so they come from the source line instead of the line number.
+#[derive(Debug)]
+pub struct GitlabOutputWriter {
+    output: Option<File>,
+    root: PathBuf,
+    issues: Vec<Value>,
+}
+
+impl GitlabOutputWriter {
+    pub fn new(output: OutputDestination, root: PathBuf) -> Self {
+        GitlabOutputWriter {
+            output: open_output(output),
+            root,
+            issues: Vec::new(),
+        }
+    }
+
+    /// The whole report, sorted by file so the output does not depend on the check order
+    pub(super) fn render(&mut self) -> String {
+        self.issues.sort_by(|a, b| {
+            let key = |issue: &Value| {
+                (
+                    issue["location"]["path"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    issue["location"]["lines"]["begin"].as_u64(),
+                )
+            };
+            key(a).cmp(&key(b))
+        });
+
+        serde_json::to_string_pretty(&self.issues).unwrap()
+    }
+}
+
+impl OutputWriter for GitlabOutputWriter {
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        if diagnostics.is_empty() {
+            return;
+        }
+
+        let file_path = relative_path(db, file_id, &self.root);
+        let fingerprinter = Fingerprinter::new(db, &self.root, file_id);
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for diagnostic in diagnostics {
+            let occurrence = occurrences
+                .entry(fingerprinter.issue_id(&diagnostic, 0))
+                .or_default();
+            let fingerprint = fingerprinter.issue_id(&diagnostic, *occurrence);
+            *occurrence += 1;
+
+            let severity = match Severity::of(&diagnostic) {
+                Some(Severity::Error) => "major",
+                Some(Severity::Warning) => "minor",
+                _ => "info",
+            };
+            self.issues.push(json!({
+                "description": diagnostic.message,
+                "check_name": diagnostic_code(&diagnostic),
+                "fingerprint": fingerprint,
+                "severity": severity,
+                "location": {
+                    "path": file_path,
+                    "lines": {
+                        "begin": diagnostic.range.start.line + 1
+                    }
+                }
+            }));
+        }
+    }
+
+    fn finish(&mut self) {
+        let pretty_json = self.render();
+        if let Some(output) = self.output.as_mut() {
+            output.write_all(pretty_json.as_bytes()).unwrap();
+        } else {
+            println!("{}", pretty_json);
+        }
+    }
+}
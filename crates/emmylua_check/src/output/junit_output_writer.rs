@@ -0,0 +1,105 @@
+use std::{fs::File, io::Write, path::PathBuf};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+use lsp_types::Diagnostic;
+
+use crate::cmd_args::OutputDestination;
+
+use super::{OutputWriter, Severity, diagnostic_code, open_output, relative_path, xml_escape};
+
+/// JUnit XML for Jenkins and other test report consumers, every file is a test suite and every
+/// diagnostic a failed test case. A file without diagnostics is reported as one passed test case.
+#[derive(Debug)]
+pub struct JunitOutputWriter {
+    output: Option<File>,
+    root: PathBuf,
+    suites: Vec<(String, Vec<Diagnostic>)>,
+}
+
+impl JunitOutputWriter {
+    pub fn new(output: OutputDestination, root: PathBuf) -> Self {
+        JunitOutputWriter {
+            output: open_output(output),
+            root,
+            suites: Vec::new(),
+        }
+    }
+
+    /// The whole report, sorted by file so the output does not depend on the check order
+    pub(super) fn render(&mut self) -> String {
+        self.suites.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let tests: usize = self
+            .suites
+            .iter()
+            .map(|(_, diagnostics)| diagnostics.len().max(1))
+            .sum();
+        let failures: usize = self
+            .suites
+            .iter()
+            .map(|(_, diagnostics)| diagnostics.len())
+            .sum();
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"emmylua_check\" tests=\"{}\" failures=\"{}\">\n",
+            tests, failures
+        ));
+        for (file_path, diagnostics) in &self.suites {
+            let file_path = xml_escape(file_path);
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+                file_path,
+                diagnostics.len().max(1),
+                diagnostics.len()
+            ));
+            if diagnostics.is_empty() {
+                xml.push_str(&format!(
+                    "    <testcase name=\"emmylua_check\" classname=\"{}\"/>\n",
+                    file_path
+                ));
+            }
+            for diagnostic in diagnostics {
+                let line = diagnostic.range.start.line + 1;
+                let column = diagnostic.range.start.character + 1;
+                let code = xml_escape(&diagnostic_code(diagnostic));
+                let message = xml_escape(&diagnostic.message);
+                let severity = match Severity::of(diagnostic) {
+                    Some(Severity::Error) => "error",
+                    Some(Severity::Warning) => "warning",
+                    Some(Severity::Hint) => "hint",
+                    _ => "info",
+                };
+                xml.push_str(&format!(
+                    "    <testcase name=\"{}:{}:{} {}\" classname=\"{}\">\n",
+                    file_path, line, column, code, file_path
+                ));
+                xml.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\">{}: {} at {}:{}:{}</failure>\n",
+                    code, message, severity, message, file_path, line, column
+                ));
+                xml.push_str("    </testcase>\n");
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+impl OutputWriter for JunitOutputWriter {
+    fn write(&mut self, db: &DbIndex, file_id: FileId, diagnostics: Vec<Diagnostic>) {
+        let file_path = relative_path(db, file_id, &self.root);
+        self.suites.push((file_path, diagnostics));
+    }
+
+    fn finish(&mut self) {
+        let xml = self.render();
+        if let Some(output) = self.output.as_mut() {
+            output.write_all(xml.as_bytes()).unwrap();
+        } else {
+            print!("{}", xml);
+        }
+    }
+}
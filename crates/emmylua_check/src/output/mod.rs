@@ -1,20 +1,29 @@
+mod checkstyle_output_writer;
+mod github_output_writer;
+mod gitlab_output_writer;
 mod json_output_writer;
+mod junit_output_writer;
 mod sarif_output_writer;
 mod text_output_writer;
 
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
 
 use emmylua_code_analysis::{DbIndex, FileId};
-use lsp_types::Diagnostic;
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
 use tokio::sync::mpsc::Receiver;
 
+use crate::baseline::BaselineFilter;
 use crate::cmd_args::{OutputDestination, OutputFormat};
 
 use crate::terminal_display::TerminalDisplay;
 
 /// Type alias for diagnostic result channel
-type DiagnosticReceiver = Receiver<(FileId, Option<Vec<Diagnostic>>)>;
+pub type DiagnosticReceiver = Receiver<(FileId, Option<Vec<Diagnostic>>)>;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn output_result(
     total_count: usize,
     db: &DbIndex,
@@ -23,6 +32,7 @@ pub async fn output_result(
     output_format: OutputFormat,
     output: OutputDestination,
     warnings_as_errors: bool,
+    mut baseline: Option<&mut BaselineFilter>,
 ) -> i32 {
     let mut writer: Box<dyn OutputWriter> = match output_format {
         OutputFormat::Json => Box::new(json_output_writer::JsonOutputWriter::new(output)),
@@ -30,6 +40,21 @@ pub async fn output_result(
             Box::new(text_output_writer::TextOutputWriter::new(workspace.clone()))
         }
         OutputFormat::Sarif => Box::new(sarif_output_writer::SarifOutputWriter::new(output)),
+        OutputFormat::Junit => Box::new(junit_output_writer::JunitOutputWriter::new(
+            output,
+            workspace.clone(),
+        )),
+        OutputFormat::Checkstyle => Box::new(
+            checkstyle_output_writer::CheckstyleOutputWriter::new(output),
+        ),
+        OutputFormat::Gitlab => Box::new(gitlab_output_writer::GitlabOutputWriter::new(
+            output,
+            workspace.clone(),
+        )),
+        OutputFormat::Github => Box::new(github_output_writer::GithubOutputWriter::new(
+            output,
+            workspace.clone(),
+        )),
     };
 
     let terminal_display = TerminalDisplay::new(workspace);
@@ -42,26 +67,29 @@ pub async fn output_result(
 
     while let Some((file_id, diagnostics)) = receiver.recv().await {
         count += 1;
-        if let Some(diagnostics) = diagnostics {
+        if let Some(mut diagnostics) = diagnostics {
+            if let Some(baseline) = baseline.as_mut() {
+                diagnostics = baseline.filter(db, file_id, diagnostics);
+            }
             for diagnostic in &diagnostics {
-                match diagnostic.severity {
-                    Some(lsp_types::DiagnosticSeverity::ERROR) => {
+                match Severity::of(diagnostic) {
+                    Some(Severity::Error) => {
                         has_error = true;
                         error_count += 1;
                     }
-                    Some(lsp_types::DiagnosticSeverity::WARNING) => {
+                    Some(Severity::Warning) => {
                         if warnings_as_errors {
                             has_error = true;
                         }
                         warning_count += 1;
                     }
-                    Some(lsp_types::DiagnosticSeverity::INFORMATION) => {
+                    Some(Severity::Information) => {
                         info_count += 1;
                     }
-                    Some(lsp_types::DiagnosticSeverity::HINT) => {
+                    Some(Severity::Hint) => {
                         hint_count += 1;
                     }
-                    _ => {}
+                    None => {}
                 }
             }
             writer.write(db, file_id, diagnostics);
@@ -87,3 +115,250 @@ trait OutputWriter {
 
     fn finish(&mut self);
 }
+
+/// Severity shared by every writer, each output format maps it to its own levels. A diagnostic
+/// without a severity is not counted and written with the lowest level of the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+impl Severity {
+    fn of(diagnostic: &Diagnostic) -> Option<Self> {
+        match diagnostic.severity? {
+            DiagnosticSeverity::ERROR => Some(Severity::Error),
+            DiagnosticSeverity::WARNING => Some(Severity::Warning),
+            DiagnosticSeverity::INFORMATION => Some(Severity::Information),
+            DiagnosticSeverity::HINT => Some(Severity::Hint),
+            _ => None,
+        }
+    }
+}
+
+pub fn diagnostic_code(diagnostic: &Diagnostic) -> String {
+    match &diagnostic.code {
+        Some(NumberOrString::Number(n)) => n.to_string(),
+        Some(NumberOrString::String(s)) => s.clone(),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Open the output file, `None` writes to stdout
+fn open_output(output: OutputDestination) -> Option<File> {
+    match output {
+        OutputDestination::Stdout => None,
+        OutputDestination::File(path) => {
+            if let Some(parent) = path.parent()
+                && !parent.exists()
+            {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+            Some(File::create(path).unwrap())
+        }
+    }
+}
+
+/// Path of the file relative to `root` with `/` separators, CI tools expect repository relative
+/// paths
+fn relative_path(db: &DbIndex, file_id: FileId, root: &Path) -> String {
+    let file_path = db.get_vfs().get_file_path(&file_id).unwrap();
+    file_path
+        .strip_prefix(root)
+        .unwrap_or(file_path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use emmylua_code_analysis::{FileId, VirtualWorkspace};
+    use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+    use super::{
+        OutputWriter, Severity, checkstyle_output_writer::CheckstyleOutputWriter,
+        github_output_writer::GithubOutputWriter, gitlab_output_writer::GitlabOutputWriter,
+        junit_output_writer::JunitOutputWriter, xml_escape,
+    };
+    use crate::cmd_args::OutputDestination;
+
+    struct Fixture {
+        ws: VirtualWorkspace,
+        a: FileId,
+        b: FileId,
+    }
+
+    impl Fixture {
+        /// Two files below `scripts/`, which is the workspace root of the writers, so a path
+        /// relative to the current directory would keep the `scripts/` prefix.
+        fn new() -> Self {
+            let mut ws = VirtualWorkspace::new();
+            let a = ws.def_file("scripts/a.lua", "local x = y\n");
+            let b = ws.def_file("scripts/b.lua", "print(z)\n");
+            Fixture { ws, a, b }
+        }
+
+        fn write(&self, writer: &mut dyn OutputWriter) {
+            let db = self.ws.analysis.compilation.get_db();
+            // written in reverse order, the reports are sorted by file
+            writer.write(
+                db,
+                self.b,
+                vec![diagnostic(None, "undefined-global", "z, is: undefined%")],
+            );
+            writer.write(
+                db,
+                self.a,
+                vec![diagnostic(
+                    Some(DiagnosticSeverity::ERROR),
+                    "type-mismatch",
+                    "Cannot assign `A<T>` & \"B\" to 'C'",
+                )],
+            );
+        }
+
+        fn root(&self) -> std::path::PathBuf {
+            self.ws.virtual_url_generator.new_path("scripts")
+        }
+    }
+
+    fn diagnostic(severity: Option<DiagnosticSeverity>, code: &str, message: &str) -> Diagnostic {
+        Diagnostic {
+            range: Range::new(Position::new(0, 6), Position::new(0, 7)),
+            severity,
+            code: Some(NumberOrString::String(code.to_string())),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(
+            xml_escape("a < b && c > \"d\" 'e'\n"),
+            "a &lt; b &amp;&amp; c &gt; &quot;d&quot; &apos;e&apos;&#10;"
+        );
+        assert_eq!(xml_escape("plain"), "plain");
+    }
+
+    #[test]
+    fn test_severity_without_level() {
+        let error = diagnostic(Some(DiagnosticSeverity::ERROR), "code", "");
+        assert_eq!(Severity::of(&error), Some(Severity::Error));
+        assert_eq!(Severity::of(&diagnostic(None, "code", "")), None);
+    }
+
+    #[test]
+    fn test_junit_output() {
+        let fixture = Fixture::new();
+        let mut writer = JunitOutputWriter::new(OutputDestination::Stdout, fixture.root());
+        fixture.write(&mut writer);
+        assert_eq!(
+            writer.render(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="emmylua_check" tests="2" failures="2">
+  <testsuite name="a.lua" tests="1" failures="1">
+    <testcase name="a.lua:1:7 type-mismatch" classname="a.lua">
+      <failure type="type-mismatch" message="Cannot assign `A&lt;T&gt;` &amp; &quot;B&quot; to &apos;C&apos;">error: Cannot assign `A&lt;T&gt;` &amp; &quot;B&quot; to &apos;C&apos; at a.lua:1:7</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="b.lua" tests="1" failures="1">
+    <testcase name="b.lua:1:7 undefined-global" classname="b.lua">
+      <failure type="undefined-global" message="z, is: undefined%">info: z, is: undefined% at b.lua:1:7</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn test_checkstyle_output() {
+        let fixture = Fixture::new();
+        let mut writer = CheckstyleOutputWriter::new(OutputDestination::Stdout);
+        fixture.write(&mut writer);
+        assert_eq!(
+            writer.render(),
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<checkstyle version="4.3">
+  <file name="{root}/a.lua">
+    <error line="1" column="7" severity="error" message="Cannot assign `A&lt;T&gt;` &amp; &quot;B&quot; to &apos;C&apos;" source="emmylua.type-mismatch"/>
+  </file>
+  <file name="{root}/b.lua">
+    <error line="1" column="7" severity="info" message="z, is: undefined%" source="emmylua.undefined-global"/>
+  </file>
+</checkstyle>
+"#,
+                root = fixture.root().to_string_lossy()
+            )
+        );
+    }
+
+    #[test]
+    fn test_gitlab_output() {
+        let fixture = Fixture::new();
+        let mut writer = GitlabOutputWriter::new(OutputDestination::Stdout, fixture.root());
+        fixture.write(&mut writer);
+        assert_eq!(
+            writer.render(),
+            r#"[
+  {
+    "check_name": "type-mismatch",
+    "description": "Cannot assign `A<T>` & \"B\" to 'C'",
+    "fingerprint": "ec5d7728ba2e6ec5",
+    "location": {
+      "lines": {
+        "begin": 1
+      },
+      "path": "a.lua"
+    },
+    "severity": "major"
+  },
+  {
+    "check_name": "undefined-global",
+    "description": "z, is: undefined%",
+    "fingerprint": "32ccd602c9918e5d",
+    "location": {
+      "lines": {
+        "begin": 1
+      },
+      "path": "b.lua"
+    },
+    "severity": "info"
+  }
+]"#
+        );
+    }
+
+    #[test]
+    fn test_github_output() {
+        let fixture = Fixture::new();
+        let mut writer = GithubOutputWriter::new(OutputDestination::Stdout, fixture.root());
+        fixture.write(&mut writer);
+        assert_eq!(
+            writer.render(),
+            r#"::error file=a.lua,line=1,col=7,endLine=1,endColumn=8,title=emmylua_check (type-mismatch)::Cannot assign `A<T>` & "B" to 'C'
+::notice file=b.lua,line=1,col=7,endLine=1,endColumn=8,title=emmylua_check (undefined-global)::z, is: undefined%25
+"#
+        );
+    }
+}
@@ -1,12 +1,12 @@
 use std::{collections::HashMap, fs::File, io::Write};
 
 use emmylua_code_analysis::{DbIndex, FileId, file_path_to_uri};
-use lsp_types::{Diagnostic, DiagnosticSeverity};
+use lsp_types::Diagnostic;
 use serde_json::{Value, json};
 
 use crate::cmd_args::OutputDestination;
 
-use super::OutputWriter;
+use super::{OutputWriter, Severity, diagnostic_code, open_output};
 
 const CRATE_NAME: &str = env!("CARGO_PKG_NAME");
 const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -20,17 +20,7 @@ pub struct SarifOutputWriter {
 
 impl SarifOutputWriter {
     pub fn new(output: OutputDestination) -> Self {
-        let output = match output {
-            OutputDestination::Stdout => None,
-            OutputDestination::File(path) => {
-                if let Some(parent) = path.parent()
-                    && !parent.exists()
-                {
-                    std::fs::create_dir_all(parent).unwrap();
-                }
-                Some(std::fs::File::create(path).unwrap())
-            }
-        };
+        let output = open_output(output);
 
         SarifOutputWriter {
             output,
@@ -39,14 +29,11 @@ impl SarifOutputWriter {
         }
     }
 
-    fn get_sarif_level(&self, severity: Option<DiagnosticSeverity>) -> &'static str {
+    fn get_sarif_level(&self, severity: Option<Severity>) -> &'static str {
         match severity {
-            Some(DiagnosticSeverity::ERROR) => "error",
-            Some(DiagnosticSeverity::WARNING) => "warning",
-            Some(DiagnosticSeverity::INFORMATION) => "note",
-            Some(DiagnosticSeverity::HINT) => "note",
-            None => "note",
-            _ => "note", // Handle other possible values
+            Some(Severity::Error) => "error",
+            Some(Severity::Warning) => "warning",
+            _ => "note",
         }
     }
 
@@ -86,18 +73,11 @@ impl SarifOutputWriter {
             }
         });
 
-        let rule_id = diagnostic
-            .code
-            .as_ref()
-            .map(|code| match code {
-                lsp_types::NumberOrString::Number(n) => n.to_string(),
-                lsp_types::NumberOrString::String(s) => s.clone(),
-            })
-            .unwrap_or_else(|| "unknown".to_string());
+        let rule_id = diagnostic_code(diagnostic);
 
         let result = json!({
             "ruleId": rule_id,
-            "level": self.get_sarif_level(diagnostic.severity),
+            "level": self.get_sarif_level(Severity::of(diagnostic)),
             "message": {
                 "text": diagnostic.message
             },
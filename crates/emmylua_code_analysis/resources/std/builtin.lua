@@ -123,6 +123,42 @@
 --- built-in type for Rawget
 ---@alias std.RawGet<T, K> unknown
 
+---
+--- All fields of `T` become optional
+---@alias std.Partial<T> unknown
+
+---
+--- All fields of `T` become required
+---@alias std.Required<T> unknown
+
+---
+--- The fields of `T` whose names are in `K`, eg: `std.Pick<User, "id" | "name">`
+---@alias std.Pick<T, K> unknown
+
+---
+--- The fields of `T` whose names are not in `K`, eg: `std.Omit<User, "password">`
+---@alias std.Omit<T, K> unknown
+
+---
+--- The fields of `T`, which cannot be assigned to
+---@alias std.Readonly<T> unknown
+
+---
+--- A table with the keys `K` and values `V`, eg: `std.Record<"x" | "y", number>`
+---@alias std.Record<K, V> unknown
+
+---
+--- The parameter types of the function `F` as a tuple, eg: `std.Parameters<fun(a: integer, b: string)>` is `[integer, string]`
+---@alias std.Parameters<F> unknown
+
+---
+--- The return type of the function `F`
+---@alias std.ReturnType<F> unknown
+
+---
+--- The return type of `T` when it is an async function, otherwise `T`
+---@alias std.Awaited<T> unknown
+
 ---
 --- built-in type for generic template, for match integer const and true/false
 ---@alias std.ConstTpl<T> unknown
@@ -175,3 +211,7 @@
 --- - `getter`: Getter method name. Takes precedence over `convention`.
 --- - `setter`: Setter method name. Takes precedence over `convention`.
 ---@attribute field_accessor(convention: "camelCase"|"PascalCase"|"snake_case"|nil, getter: string?, setter: string?)
+
+--- Marks a module member, global function, class field or module table as intentionally public,
+--- it is never reported by `unused-export` even when nothing in the workspace uses it.
+---@attribute entry_point
@@ -109,10 +109,9 @@ pub fn analyze_doc_tag_namespace(
     let name = namespace.get_name_token()?.get_name_text().to_string();
 
     let file_id = analyzer.get_file_id();
-    analyzer
-        .db
-        .get_type_index_mut()
-        .add_file_namespace(file_id, name);
+    analyzer.write(move |db| {
+        db.get_type_index_mut().add_file_namespace(file_id, name);
+    });
 
     Some(())
 }
@@ -121,39 +120,40 @@ pub fn analyze_doc_tag_using(analyzer: &mut DeclAnalyzer, using: LuaDocTagUsing)
     let name = using.get_name_token()?.get_name_text().to_string();
 
     let file_id = analyzer.get_file_id();
-    analyzer
-        .db
-        .get_type_index_mut()
-        .add_file_using_namespace(file_id, name);
+    analyzer.write(move |db| {
+        db.get_type_index_mut()
+            .add_file_using_namespace(file_id, name);
+    });
 
     Some(())
 }
 
 pub fn analyze_doc_tag_meta(analyzer: &mut DeclAnalyzer, tag: LuaDocTagMeta) -> Option<()> {
     let file_id = analyzer.get_file_id();
-    analyzer.db.get_module_index_mut().set_meta(file_id);
+    analyzer.write(move |db| {
+        db.get_module_index_mut().set_meta(file_id);
+    });
     analyzer.is_meta = true;
 
     if let Some(name_token) = tag.get_name_token() {
-        let text = name_token.get_name_text();
+        let text = name_token.get_name_text().to_string();
         // compact luals
         if text == "no-require" || text == "_" {
-            analyzer
-                .db
-                .get_module_index_mut()
-                .set_module_visibility(file_id, false);
+            analyzer.write(move |db| {
+                db.get_module_index_mut()
+                    .set_module_visibility(file_id, false);
+            });
         } else {
-            let workspace_id = analyzer
-                .db
-                .get_module_index()
-                .get_module(file_id)?
-                .workspace_id;
-
-            analyzer
-                .db
-                .get_module_index_mut()
-                .add_module_by_module_path(file_id, text.to_string(), workspace_id);
-            analyzer.db.get_module_index_mut().set_meta(file_id);
+            analyzer.write(move |db| {
+                let Some(module_info) = db.get_module_index().get_module(file_id) else {
+                    return;
+                };
+                let workspace_id = module_info.workspace_id;
+
+                db.get_module_index_mut()
+                    .add_module_by_module_path(file_id, text, workspace_id);
+                db.get_module_index_mut().set_meta(file_id);
+            });
         }
     }
 
@@ -172,10 +172,10 @@ pub fn analyze_doc_tag_meta(analyzer: &mut DeclAnalyzer, tag: LuaDocTagMeta) ->
         version_conds.push(version_condition);
     }
 
-    analyzer
-        .db
-        .get_module_index_mut()
-        .set_module_version_conds(file_id, version_conds);
+    analyzer.write(move |db| {
+        db.get_module_index_mut()
+            .set_module_version_conds(file_id, version_conds);
+    });
 
     Some(())
 }
@@ -188,17 +188,19 @@ fn add_type_decl(
     flag: FlagSet<LuaTypeFlag>,
 ) {
     let file_id = analyzer.get_file_id();
-    let type_index = analyzer.db.get_type_index_mut();
-
-    let basic_name = name;
-    let option_namespace = type_index.get_file_namespace(&file_id);
-    let full_name = option_namespace
-        .map(|ns| format!("{}.{}", ns, basic_name))
-        .unwrap_or(basic_name.to_string());
-    let id = LuaTypeDeclId::new(&full_name);
-    let simple_name = id.get_simple_name();
-    type_index.add_type_decl(
-        file_id,
-        LuaTypeDecl::new(file_id, range, simple_name.to_string(), kind, flag, id),
-    );
+    let basic_name = name.to_string();
+    // the namespace of the file is only known once the writes before this one are applied
+    analyzer.write(move |db| {
+        let type_index = db.get_type_index_mut();
+        let option_namespace = type_index.get_file_namespace(&file_id);
+        let full_name = option_namespace
+            .map(|ns| format!("{}.{}", ns, basic_name))
+            .unwrap_or(basic_name);
+        let id = LuaTypeDeclId::new(&full_name);
+        let simple_name = id.get_simple_name();
+        type_index.add_type_decl(
+            file_id,
+            LuaTypeDecl::new(file_id, range, simple_name.to_string(), kind, flag, id),
+        );
+    });
 }
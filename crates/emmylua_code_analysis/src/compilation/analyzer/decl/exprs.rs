@@ -1,17 +1,15 @@
 use emmylua_parser::{
-    LuaAst, LuaAstNode, LuaAstToken, LuaCallExpr, LuaClosureExpr, LuaDocTagCast, LuaExpr,
-    LuaFuncStat, LuaIndexExpr, LuaIndexKey, LuaLiteralExpr, LuaLiteralToken, LuaNameExpr,
+    LuaAst, LuaAstNode, LuaAstPtr, LuaAstToken, LuaCallExpr, LuaClosureExpr, LuaDocTagCast,
+    LuaExpr, LuaFuncStat, LuaIndexExpr, LuaIndexKey, LuaLiteralExpr, LuaLiteralToken, LuaNameExpr,
     LuaTableExpr, LuaVarExpr,
 };
 
 use crate::{
-    FileId, InFiled, InferFailReason, LuaDeclExtra, LuaDeclId, LuaMemberFeature, LuaMemberId,
-    LuaSignatureId,
-    compilation::analyzer::unresolve::UnResolveTableField,
+    FileId, InFiled, LuaDeclExtra, LuaDeclId, LuaMemberFeature, LuaMemberId, LuaSignatureId,
     db_index::{LuaDecl, LuaMember, LuaMemberKey, LuaMemberOwner},
 };
 
-use super::DeclAnalyzer;
+use super::{DeclAnalyzer, UnResolveTableFieldPtr};
 
 pub fn analyze_name_expr(analyzer: &mut DeclAnalyzer, expr: LuaNameExpr) -> Option<()> {
     let name_token = expr.get_name_token()?;
@@ -37,15 +35,19 @@ pub fn analyze_name_expr(analyzer: &mut DeclAnalyzer, expr: LuaNameExpr) -> Opti
         (None, false)
     };
 
-    let reference_index = analyzer.db.get_reference_index_mut();
+    let name = name.to_string();
+    let syntax_id = expr.get_syntax_id();
+    analyzer.write(move |db| {
+        let reference_index = db.get_reference_index_mut();
 
-    if let Some(id) = decl_id {
-        reference_index.add_decl_reference(id, file_id, range, false);
-    }
+        if let Some(id) = decl_id {
+            reference_index.add_decl_reference(id, file_id, range, false);
+        }
 
-    if !is_local {
-        reference_index.add_global_reference(name, file_id, expr.get_syntax_id());
-    }
+        if !is_local {
+            reference_index.add_global_reference(&name, file_id, syntax_id);
+        }
+    });
 
     Some(())
 }
@@ -76,21 +78,21 @@ pub fn analyze_index_expr(analyzer: &mut DeclAnalyzer, index_expr: LuaIndexExpr)
         let name_token = name_expr.get_name_token()?;
         let name_token_text = name_token.get_name_text();
         if name_token_text == "_G" || name_token_text == "_ENV" {
-            if let LuaMemberKey::Name(name) = &key {
-                analyzer
-                    .db
-                    .get_reference_index_mut()
-                    .add_global_reference(name, file_id, syntax_id);
+            if let LuaMemberKey::Name(name) = key {
+                analyzer.write(move |db| {
+                    db.get_reference_index_mut()
+                        .add_global_reference(&name, file_id, syntax_id);
+                });
             }
 
             return Some(());
         }
     }
 
-    analyzer
-        .db
-        .get_reference_index_mut()
-        .add_index_reference(key, file_id, syntax_id);
+    analyzer.write(move |db| {
+        db.get_reference_index_mut()
+            .add_index_reference(key, file_id, syntax_id);
+    });
 
     Some(())
 }
@@ -194,10 +196,21 @@ fn analyze_closure_params(
     signature_id: &LuaSignatureId,
     closure: &LuaClosureExpr,
 ) -> Option<()> {
-    let signature = analyzer
-        .db
-        .get_signature_index_mut()
-        .get_or_create(*signature_id);
+    let signature_id = *signature_id;
+    let mut param_names = Vec::new();
+    let result = collect_closure_param_names(closure, &mut param_names);
+    analyzer.write(move |db| {
+        let signature = db.get_signature_index_mut().get_or_create(signature_id);
+        signature.params.extend(param_names);
+    });
+
+    result
+}
+
+fn collect_closure_param_names(
+    closure: &LuaClosureExpr,
+    param_names: &mut Vec<String>,
+) -> Option<()> {
     let params = closure.get_params_list()?.get_params();
     for param in params {
         let name = if let Some(name_token) = param.get_name_token() {
@@ -208,7 +221,7 @@ fn analyze_closure_params(
             return None;
         };
 
-        signature.params.push(name);
+        param_names.push(name);
     }
 
     Some(())
@@ -235,40 +248,30 @@ pub fn analyze_table_expr(analyzer: &mut DeclAnalyzer, table_expr: LuaTableExpr)
                     LuaIndexKey::Integer(i) => LuaMemberKey::Integer(i.get_int_value()),
                     LuaIndexKey::Idx(idx) => LuaMemberKey::Integer(idx as i64),
                     LuaIndexKey::Expr(field_expr) => {
-                        let unresolve_member = UnResolveTableField {
-                            file_id: analyzer.get_file_id(),
-                            table_expr: table_expr.clone(),
-                            field: field.clone(),
+                        analyzer.unresolve_fields.push(UnResolveTableFieldPtr {
+                            table_expr: LuaAstPtr::new(&table_expr),
+                            field: LuaAstPtr::new(&field),
+                            field_expr: LuaAstPtr::new(&field_expr),
                             decl_feature,
-                        };
-                        analyzer.context.add_unresolve(
-                            unresolve_member.into(),
-                            InferFailReason::UnResolveExpr(InFiled::new(
-                                analyzer.get_file_id(),
-                                field_expr.clone(),
-                            )),
-                        );
+                        });
                         continue;
                     }
                 };
 
-                analyzer.db.get_reference_index_mut().add_index_reference(
-                    key.clone(),
-                    file_id,
-                    field.get_syntax_id(),
-                );
-
-                let member_id = LuaMemberId::new(field.get_syntax_id(), file_id);
+                let syntax_id = field.get_syntax_id();
+                let member_id = LuaMemberId::new(syntax_id, file_id);
                 let member = match &owner_id {
                     LuaMemberOwner::GlobalPath(path) => {
-                        LuaMember::new(member_id, key, decl_feature, Some(path.clone()))
+                        LuaMember::new(member_id, key.clone(), decl_feature, Some(path.clone()))
                     }
-                    _ => LuaMember::new(member_id, key, decl_feature, None),
+                    _ => LuaMember::new(member_id, key.clone(), decl_feature, None),
                 };
-                analyzer
-                    .db
-                    .get_member_index_mut()
-                    .add_member(owner_id.clone(), member);
+                let owner_id = owner_id.clone();
+                analyzer.write(move |db| {
+                    db.get_reference_index_mut()
+                        .add_index_reference(key, file_id, syntax_id);
+                    db.get_member_index_mut().add_member(owner_id, member);
+                });
             }
         }
     }
@@ -288,11 +291,11 @@ pub fn analyze_literal_expr(analyzer: &mut DeclAnalyzer, expr: LuaLiteralExpr) -
 
             let value = string_token.get_value();
             if value.len() <= 64 {
-                analyzer.db.get_reference_index_mut().add_string_reference(
-                    file_id,
-                    &value,
-                    string_token.get_range(),
-                );
+                let range = string_token.get_range();
+                analyzer.write(move |db| {
+                    db.get_reference_index_mut()
+                        .add_string_reference(file_id, &value, range);
+                });
             }
         }
         LuaLiteralToken::Dots(dots_token) => {
@@ -311,10 +314,10 @@ pub fn analyze_literal_expr(analyzer: &mut DeclAnalyzer, expr: LuaLiteralExpr) -
                 });
 
             if let Some(id) = decl_id {
-                analyzer
-                    .db
-                    .get_reference_index_mut()
-                    .add_decl_reference(id, file_id, range, false);
+                analyzer.write(move |db| {
+                    db.get_reference_index_mut()
+                        .add_decl_reference(id, file_id, range, false);
+                });
             }
         }
         _ => {}
@@ -331,12 +334,21 @@ pub fn analyze_call_expr(analyzer: &mut DeclAnalyzer, expr: LuaCallExpr) -> Opti
         {
             let module_path = string_token.get_value();
             let file_id = analyzer.get_file_id();
-            let module_info = analyzer.db.get_module_index().find_module(&module_path)?;
-            let module_file_id = module_info.file_id;
-            analyzer
-                .db
-                .get_file_dependencies_index_mut()
-                .add_required_file(file_id, module_file_id);
+            let is_load_time = expr.ancestors::<LuaClosureExpr>().next().is_none();
+            // modules may be renamed by `---@meta` of files written before, look it up late
+            analyzer.write(move |db| {
+                let Some(module_info) = db.get_module_index().find_module(&module_path) else {
+                    db.get_file_dependencies_index_mut()
+                        .add_unresolved_require(file_id, module_path);
+                    return;
+                };
+                let module_file_id = module_info.file_id;
+                let dependency_index = db.get_file_dependencies_index_mut();
+                dependency_index.add_required_file(file_id, module_file_id);
+                if is_load_time {
+                    dependency_index.add_load_time_required_file(file_id, module_file_id);
+                }
+            });
         }
     }
 
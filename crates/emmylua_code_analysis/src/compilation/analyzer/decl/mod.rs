@@ -4,13 +4,21 @@ mod members;
 mod stats;
 
 use crate::{
-    compilation::analyzer::AnalysisPipeline,
+    InFiled, InferFailReason, LuaMemberFeature,
+    compilation::analyzer::{
+        AnalysisPipeline,
+        parallel::{analysis_threads, par_map_files},
+        unresolve::UnResolveTableField,
+    },
     db_index::{DbIndex, LuaScopeKind},
     profile::Profile,
 };
 
 use super::AnalyzeContext;
-use emmylua_parser::{LuaAst, LuaAstNode, LuaChunk, LuaFuncStat, LuaSyntaxKind, LuaVarExpr};
+use emmylua_parser::{
+    LuaAst, LuaAstNode, LuaAstPtr, LuaChunk, LuaExpr, LuaFuncStat, LuaSyntaxKind, LuaTableExpr,
+    LuaTableField, LuaVarExpr,
+};
 use rowan::{TextRange, TextSize, WalkEvent};
 
 use crate::{
@@ -23,23 +31,67 @@ pub struct DeclAnalysisPipeline;
 impl AnalysisPipeline for DeclAnalysisPipeline {
     fn analyze(db: &mut DbIndex, context: &mut AnalyzeContext) {
         let _p = Profile::cond_new("decl analyze", context.tree_list.len() > 1);
-        let tree_list = context.tree_list.clone();
-        for in_filed_tree in tree_list.iter() {
-            db.get_reference_index_mut()
-                .create_local_reference(in_filed_tree.file_id);
-            let mut analyzer = DeclAnalyzer::new(
-                db,
-                in_filed_tree.file_id,
-                in_filed_tree.value.clone(),
-                context,
-            );
+        let threads = analysis_threads(&context.config);
+        let shared_db: &DbIndex = db;
+        let results = par_map_files(&context.tree_list, threads, |file_id, chunk| {
+            let mut analyzer = DeclAnalyzer::new(shared_db, file_id, chunk);
             analyzer.analyze();
-            let decl_tree = analyzer.get_decl_tree();
-            db.get_decl_index_mut().add_decl_tree(decl_tree);
+            analyzer.finish()
+        });
+
+        // apply the writes in file order, the index ends up the same as analyzing one by one
+        let tree_list = context.tree_list.clone();
+        for (in_filed_tree, result) in tree_list.iter().zip(results) {
+            let file_id = in_filed_tree.file_id;
+            db.get_reference_index_mut().create_local_reference(file_id);
+            for write in result.writes {
+                write(db);
+            }
+            for field in result.unresolve_fields {
+                add_unresolve_table_field(context, file_id, &in_filed_tree.value, field);
+            }
+            db.get_decl_index_mut().add_decl_tree(result.decl_tree);
         }
     }
 }
 
+/// A write into the index recorded while walking a file. The decl analysis never reads back what
+/// it writes, so files are walked in parallel and the writes are applied afterwards.
+type DeclIndexWrite = Box<dyn FnOnce(&mut DbIndex) + Send>;
+
+struct DeclAnalysisResult {
+    decl_tree: LuaDeclarationTree,
+    writes: Vec<DeclIndexWrite>,
+    unresolve_fields: Vec<UnResolveTableFieldPtr>,
+}
+
+/// Table field with an expression key, resolved against the file chunk when the writes are applied.
+struct UnResolveTableFieldPtr {
+    table_expr: LuaAstPtr<LuaTableExpr>,
+    field: LuaAstPtr<LuaTableField>,
+    field_expr: LuaAstPtr<LuaExpr>,
+    decl_feature: LuaMemberFeature,
+}
+
+fn add_unresolve_table_field(
+    context: &mut AnalyzeContext,
+    file_id: FileId,
+    root: &LuaChunk,
+    field: UnResolveTableFieldPtr,
+) -> Option<()> {
+    let unresolve_member = UnResolveTableField {
+        file_id,
+        table_expr: field.table_expr.to_node(root)?,
+        field: field.field.to_node(root)?,
+        decl_feature: field.decl_feature,
+    };
+    context.add_unresolve(
+        unresolve_member.into(),
+        InferFailReason::UnResolveExpr(InFiled::new(file_id, field.field_expr.to_node(root)?)),
+    );
+    Some(())
+}
+
 fn walk_node_enter(analyzer: &mut DeclAnalyzer, node: LuaAst) {
     match node {
         LuaAst::LuaChunk(chunk) => {
@@ -145,30 +197,26 @@ fn is_scope_owner(node: &LuaAst) -> bool {
     )
 }
 
-#[derive(Debug)]
 pub struct DeclAnalyzer<'a> {
-    db: &'a mut DbIndex,
+    db: &'a DbIndex,
     root: LuaChunk,
     decl: LuaDeclarationTree,
     scopes: Vec<LuaScopeId>,
     is_meta: bool,
-    context: &'a mut AnalyzeContext,
+    writes: Vec<DeclIndexWrite>,
+    unresolve_fields: Vec<UnResolveTableFieldPtr>,
 }
 
 impl<'a> DeclAnalyzer<'a> {
-    pub fn new(
-        db: &'a mut DbIndex,
-        file_id: FileId,
-        root: LuaChunk,
-        context: &'a mut AnalyzeContext,
-    ) -> DeclAnalyzer<'a> {
+    pub fn new(db: &'a DbIndex, file_id: FileId, root: LuaChunk) -> DeclAnalyzer<'a> {
         DeclAnalyzer {
             db,
             root,
             decl: LuaDeclarationTree::new(file_id),
             scopes: Vec::new(),
             is_meta: false,
-            context,
+            writes: Vec::new(),
+            unresolve_fields: Vec::new(),
         }
     }
 
@@ -186,8 +234,17 @@ impl<'a> DeclAnalyzer<'a> {
         self.decl.file_id()
     }
 
-    pub fn get_decl_tree(self) -> LuaDeclarationTree {
-        self.decl
+    fn finish(self) -> DeclAnalysisResult {
+        DeclAnalysisResult {
+            decl_tree: self.decl,
+            writes: self.writes,
+            unresolve_fields: self.unresolve_fields,
+        }
+    }
+
+    /// Record a write into the index, it is applied after all files have been walked.
+    pub fn write(&mut self, write: impl FnOnce(&mut DbIndex) + Send + 'static) {
+        self.writes.push(Box::new(write));
     }
 
     pub fn create_scope(&mut self, range: TextRange, kind: LuaScopeKind) {
@@ -218,11 +275,12 @@ impl<'a> DeclAnalyzer<'a> {
         self.add_decl_to_current_scope(id);
 
         if is_global {
-            self.db.get_global_index_mut().add_global_decl(&name, id);
+            self.write(move |db| {
+                db.get_global_index_mut().add_global_decl(&name, id);
 
-            self.db
-                .get_reference_index_mut()
-                .add_global_reference(&name, file_id, syntax_id);
+                db.get_reference_index_mut()
+                    .add_global_reference(&name, file_id, syntax_id);
+            });
         }
 
         id
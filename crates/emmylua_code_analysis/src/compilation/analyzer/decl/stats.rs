@@ -85,10 +85,10 @@ pub fn analyze_assign_stat(analyzer: &mut DeclAnalyzer, stat: LuaAssignStat) ->
 
                 if let Some(decl) = analyzer.find_decl(name, position) {
                     let decl_id = decl.get_id();
-                    analyzer
-                        .db
-                        .get_reference_index_mut()
-                        .add_decl_reference(decl_id, file_id, range, true);
+                    analyzer.write(move |db| {
+                        db.get_reference_index_mut()
+                            .add_decl_reference(decl_id, file_id, range, true);
+                    });
                 } else {
                     let decl = LuaDecl::new(
                         name,
@@ -126,7 +126,9 @@ pub fn analyze_assign_stat(analyzer: &mut DeclAnalyzer, stat: LuaAssignStat) ->
                 let (owner, global_id) = find_index_owner(analyzer, index_expr.clone());
                 let member = LuaMember::new(member_id, key.clone(), decl_feature, global_id);
 
-                analyzer.db.get_member_index_mut().add_member(owner, member);
+                analyzer.write(move |db| {
+                    db.get_member_index_mut().add_member(owner, member);
+                });
                 if let LuaMemberKey::Name(name) = &key {
                     analyze_maybe_global_index_expr(analyzer, index_expr, name, value_expr_id);
                 }
@@ -154,10 +156,10 @@ fn analyze_maybe_global_index_expr(
             let range = index_expr.get_range();
             if let Some(decl) = analyzer.find_decl(name, position) {
                 let decl_id = decl.get_id();
-                analyzer
-                    .db
-                    .get_reference_index_mut()
-                    .add_decl_reference(decl_id, file_id, range, true);
+                analyzer.write(move |db| {
+                    db.get_reference_index_mut()
+                        .add_decl_reference(decl_id, file_id, range, true);
+                });
             } else {
                 let decl = LuaDecl::new(
                     index_name,
@@ -194,11 +196,9 @@ pub fn analyze_for_stat(analyzer: &mut DeclAnalyzer, stat: LuaForStat) -> Option
     );
     let decl_id = decl.get_id();
     analyzer.add_decl(decl);
-    bind_type(
-        analyzer.db,
-        decl_id.into(),
-        LuaTypeCache::DocType(LuaType::Integer),
-    );
+    analyzer.write(move |db| {
+        bind_type(db, decl_id.into(), LuaTypeCache::DocType(LuaType::Integer));
+    });
 
     Some(())
 }
@@ -273,10 +273,9 @@ pub fn analyze_func_stat(analyzer: &mut DeclAnalyzer, stat: LuaFuncStat) -> Opti
 
             let (owner_id, global_id) = find_index_owner(analyzer, index_expr.clone());
             let member = LuaMember::new(member_id, key.clone(), decl_feature, global_id);
-            let member_id = analyzer
-                .db
-                .get_member_index_mut()
-                .add_member(owner_id, member);
+            analyzer.write(move |db| {
+                db.get_member_index_mut().add_member(owner_id, member);
+            });
 
             if let LuaMemberKey::Name(name) = &key {
                 analyze_maybe_global_index_expr(analyzer, &index_expr, name, None);
@@ -289,11 +288,10 @@ pub fn analyze_func_stat(analyzer: &mut DeclAnalyzer, stat: LuaFuncStat) -> Opti
     let file_id = analyzer.get_file_id();
     let closure_owner_id =
         LuaSemanticDeclId::Signature(LuaSignatureId::from_closure(file_id, &closure));
-    analyzer.db.get_property_index_mut().add_owner_map(
-        property_owner_id,
-        closure_owner_id,
-        file_id,
-    );
+    analyzer.write(move |db| {
+        db.get_property_index_mut()
+            .add_owner_map(property_owner_id, closure_owner_id, file_id);
+    });
 
     Some(())
 }
@@ -320,10 +318,10 @@ pub fn analyze_local_func_stat(analyzer: &mut DeclAnalyzer, stat: LuaLocalFuncSt
     let closure_owner_id =
         LuaSemanticDeclId::Signature(LuaSignatureId::from_closure(file_id, &closure));
     let property_decl_id = LuaSemanticDeclId::LuaDecl(decl_id);
-    analyzer
-        .db
-        .get_property_index_mut()
-        .add_owner_map(property_decl_id, closure_owner_id, file_id);
+    analyzer.write(move |db| {
+        db.get_property_index_mut()
+            .add_owner_map(property_decl_id, closure_owner_id, file_id);
+    });
 
     Some(())
 }
@@ -26,9 +26,33 @@ impl FileGenericIndex {
         params: Vec<GenericParam>,
         is_func: bool,
     ) {
+        self.add_scope(ranges, TagGenericParams::new(params, is_func));
+    }
+
+    /// The key parameter of a mapped type `{ [K in keyof T]: T[K] }` is visible in `range`,
+    /// nested mapped types get distinct ids.
+    pub fn add_mapped_scope(&mut self, range: TextRange, name: &str) {
+        let depth = self
+            .find_generic_params(range.start())
+            .unwrap_or_default()
+            .iter()
+            .filter(|id| self.generic_params[**id].mapped_depth.is_some())
+            .count();
+        let mut params = HashMap::new();
+        params.insert(name.to_string(), (0, false));
+        self.add_scope(
+            vec![range],
+            TagGenericParams {
+                params,
+                is_func: false,
+                mapped_depth: Some(depth as u32),
+            },
+        );
+    }
+
+    fn add_scope(&mut self, ranges: Vec<TextRange>, params: TagGenericParams) {
         let params_id = self.generic_params.len();
-        self.generic_params
-            .push(TagGenericParams::new(params, is_func));
+        self.generic_params.push(params);
         let params_id = GenericParamId::new(params_id);
         let root_node_ids: Vec<_> = self.root_node_ids.clone();
         for range in ranges {
@@ -102,7 +126,9 @@ impl FileGenericIndex {
             if let Some(params) = self.generic_params.get(*params_id)
                 && let Some((id, is_variadic)) = params.params.get(name)
             {
-                if params.is_func {
+                if let Some(depth) = params.mapped_depth {
+                    return Some((GenericTplId::Mapped(depth), false));
+                } else if params.is_func {
                     return Some((GenericTplId::Func(*id as u32), *is_variadic));
                 } else {
                     return Some((GenericTplId::Type(*id as u32), *is_variadic));
@@ -182,6 +208,7 @@ impl GenericEffectId {
 pub struct TagGenericParams {
     params: HashMap<String, (usize, bool)>, // bool: is_variadic
     is_func: bool,
+    mapped_depth: Option<u32>,
 }
 
 impl TagGenericParams {
@@ -190,6 +217,10 @@ impl TagGenericParams {
         for (i, param) in generic_params.into_iter().enumerate() {
             params.insert(param.name.to_string(), (i, param.is_variadic));
         }
-        Self { params, is_func }
+        Self {
+            params,
+            is_func,
+            mapped_depth: None,
+        }
     }
 }
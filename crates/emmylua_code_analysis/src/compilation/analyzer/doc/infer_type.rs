@@ -1,21 +1,24 @@
 use std::sync::Arc;
 
 use emmylua_parser::{
-    LuaAst, LuaAstNode, LuaDocAttributeType, LuaDocBinaryType, LuaDocDescriptionOwner,
-    LuaDocFuncType, LuaDocGenericType, LuaDocMultiLineUnionType, LuaDocObjectFieldKey,
-    LuaDocObjectType, LuaDocStrTplType, LuaDocType, LuaDocUnaryType, LuaDocVariadicType,
-    LuaLiteralToken, LuaSyntaxKind, LuaTypeBinaryOperator, LuaTypeUnaryOperator, LuaVarExpr,
+    LuaAst, LuaAstNode, LuaAstToken, LuaDocAttributeType, LuaDocBinaryType, LuaDocDescriptionOwner,
+    LuaDocFuncType, LuaDocGenericType, LuaDocMappedType, LuaDocMultiLineUnionType,
+    LuaDocObjectFieldKey, LuaDocObjectType, LuaDocStrTplPart, LuaDocStrTplType, LuaDocType,
+    LuaDocUnaryType, LuaDocVariadicType, LuaLiteralToken, LuaSyntaxKind, LuaTypeBinaryOperator,
+    LuaTypeUnaryOperator, LuaVarExpr,
 };
 use rowan::TextRange;
 use smol_str::SmolStr;
 
 use crate::{
     AsyncState, DiagnosticCode, GenericTpl, InFiled, LuaAliasCallKind, LuaArrayLen, LuaArrayType,
-    LuaAttributeType, LuaMultiLineUnion, LuaTupleStatus, LuaTypeDeclId, TypeOps, VariadicType,
+    LuaAttributeType, LuaMappedModifier, LuaMappedType, LuaMultiLineUnion, LuaTemplateLiteralType,
+    LuaTupleStatus, LuaTypeDeclId, TypeOps, VariadicType,
     db_index::{
         AnalyzeError, LuaAliasCallType, LuaFunctionType, LuaGenericType, LuaIndexAccessKey,
         LuaIntersectionType, LuaObjectType, LuaStringTplType, LuaTupleType, LuaType,
     },
+    evaluate_alias_call, evaluate_template_literal,
 };
 
 use super::{DocAnalyzer, preprocess_description};
@@ -111,6 +114,22 @@ pub fn infer_type(analyzer: &mut DocAnalyzer, node: LuaDocType) -> LuaType {
         LuaDocType::Attribute(attribute_type) => {
             return infer_attribute_type(analyzer, attribute_type);
         }
+        LuaDocType::Mapped(mapped_type) => {
+            return infer_mapped_type(analyzer, mapped_type).unwrap_or(LuaType::Unknown);
+        }
+        LuaDocType::IndexAccess(index_access) => {
+            if let Some((base_type, key_type)) = index_access.get_types() {
+                let base = infer_type(analyzer, base_type);
+                let key = infer_type(analyzer, key_type);
+                if base.is_unknown() || key.is_unknown() {
+                    return LuaType::Unknown;
+                }
+
+                return LuaType::Call(
+                    LuaAliasCallType::new(LuaAliasCallKind::Index, vec![base, key]).into(),
+                );
+            }
+        }
         _ => {} // LuaDocType::Conditional(lua_doc_conditional_type) => todo!(),
     }
     LuaType::Unknown
@@ -125,6 +144,7 @@ fn infer_buildin_or_ref_type(
     let position = range.start();
     match name {
         "unknown" => LuaType::Unknown,
+        "never" => LuaType::Never,
         "nil" | "void" => LuaType::Nil,
         "any" => LuaType::Any,
         "userdata" => LuaType::Userdata,
@@ -309,6 +329,45 @@ fn infer_special_generic_type(
                 LuaAliasCallType::new(LuaAliasCallKind::RawGet, params).into(),
             ));
         }
+        "std.Partial" | "std.Required" | "std.Pick" | "std.Omit" | "std.Readonly"
+        | "std.Record" => {
+            let call_kind = match name {
+                "std.Partial" => LuaAliasCallKind::Partial,
+                "std.Required" => LuaAliasCallKind::Required,
+                "std.Pick" => LuaAliasCallKind::Pick,
+                "std.Omit" => LuaAliasCallKind::Omit,
+                "std.Readonly" => LuaAliasCallKind::Readonly,
+                _ => LuaAliasCallKind::Record,
+            };
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                let param_type = infer_type(analyzer, param);
+                params.push(param_type);
+            }
+            return Some(LuaType::Call(
+                LuaAliasCallType::new(call_kind, params).into(),
+            ));
+        }
+        "std.Parameters" | "std.ReturnType" | "std.Awaited" => {
+            let call_kind = match name {
+                "std.Parameters" => LuaAliasCallKind::Parameters,
+                "std.ReturnType" => LuaAliasCallKind::ReturnType,
+                _ => LuaAliasCallKind::Awaited,
+            };
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                let param_type = infer_type(analyzer, param);
+                params.push(param_type);
+            }
+            let alias_call = LuaAliasCallType::new(call_kind, params);
+            // a literal function type needs no other file, it is evaluated right away
+            if let [LuaType::DocFunction(_)] = alias_call.get_operands().as_slice()
+                && let Some(typ) = evaluate_alias_call(analyzer.db, &alias_call)
+            {
+                return Some(typ);
+            }
+            return Some(LuaType::Call(alias_call.into()));
+        }
         "TypeGuard" => {
             let first_doc_param_type = generic_type.get_generic_types()?.get_types().next()?;
             let first_param = infer_type(analyzer, first_doc_param_type);
@@ -575,11 +634,50 @@ fn infer_object_type(analyzer: &mut DocAnalyzer, object_type: &LuaDocObjectType)
     LuaType::Object(LuaObjectType::new(fields).into())
 }
 
+// { [K in keyof T as R]+?: V }, `K` is visible in `R` and `V`
+fn infer_mapped_type(
+    analyzer: &mut DocAnalyzer,
+    mapped_type: &LuaDocMappedType,
+) -> Option<LuaType> {
+    let mapped_keys = mapped_type.get_mapped_keys()?;
+    let name_token = mapped_keys.get_name_token()?;
+    let name = name_token.get_name_text();
+    let key_type = infer_type(analyzer, mapped_keys.get_key_type()?);
+
+    analyzer
+        .generic_index
+        .add_mapped_scope(mapped_type.get_range(), name);
+    let (tpl_id, _) = analyzer
+        .generic_index
+        .find_generic(name_token.get_position(), name)?;
+    let param = GenericTpl::new(tpl_id, SmolStr::new(name).into(), false);
+
+    let remap_type = mapped_keys
+        .get_remap_type()
+        .map(|remap_type| infer_type(analyzer, remap_type));
+    let value_type = infer_type(analyzer, mapped_type.get_value_type()?);
+    let modifier = if mapped_type.is_optional() {
+        LuaMappedModifier::Optional
+    } else if mapped_type.is_remove_optional() {
+        LuaMappedModifier::Required
+    } else {
+        LuaMappedModifier::None
+    };
+
+    Some(LuaType::Mapped(
+        LuaMappedType::new(param.into(), key_type, remap_type, value_type, modifier).into(),
+    ))
+}
+
 fn infer_str_tpl(
     analyzer: &mut DocAnalyzer,
     str_tpl: &LuaDocStrTplType,
     node: &LuaDocType,
 ) -> LuaType {
+    if let Some(parts) = str_tpl.get_template_parts() {
+        return infer_template_literal(analyzer, str_tpl, parts, node);
+    }
+
     let (prefix, tpl_name, suffix) = str_tpl.get_name();
     if let Some(tpl) = tpl_name {
         let typ = infer_buildin_or_ref_type(analyzer, &tpl, str_tpl.get_range(), node);
@@ -597,6 +695,34 @@ fn infer_str_tpl(
     LuaType::Unknown
 }
 
+fn infer_template_literal(
+    analyzer: &mut DocAnalyzer,
+    str_tpl: &LuaDocStrTplType,
+    parts: Vec<LuaDocStrTplPart>,
+    node: &LuaDocType,
+) -> LuaType {
+    let mut types = Vec::new();
+    for part in parts {
+        let typ = match part {
+            LuaDocStrTplPart::Text(text) => LuaType::DocStringConst(SmolStr::new(text).into()),
+            LuaDocStrTplPart::Type(name) => {
+                infer_buildin_or_ref_type(analyzer, &name, str_tpl.get_range(), node)
+            }
+        };
+        types.push(typ);
+    }
+
+    let template = LuaTemplateLiteralType::new(types);
+    // literal placeholders need no other file, the strings are known right away
+    if !template.get_parts().iter().any(|part| part.is_ref())
+        && let Some(typ) = evaluate_template_literal(analyzer.db, &template)
+    {
+        return typ;
+    }
+
+    LuaType::TemplateLiteral(template.into())
+}
+
 fn infer_variadic_type(
     analyzer: &mut DocAnalyzer,
     variadic_type: &LuaDocVariadicType,
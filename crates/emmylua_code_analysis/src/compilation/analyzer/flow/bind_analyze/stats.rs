@@ -45,8 +45,7 @@ pub fn bind_local_stat(
 
 fn check_local_immutable(binder: &mut FlowBinder, decl_id: LuaDeclId) -> bool {
     let Some(decl_ref) = binder
-        .db
-        .get_reference_index()
+        .reference_index
         .get_decl_references(&binder.file_id, &decl_id)
     else {
         return true;
@@ -341,7 +340,12 @@ pub fn bind_if_stat(binder: &mut FlowBinder, if_stat: LuaIfStat, current: FlowId
                 post_elseif_label,
             );
         }
-        else_label = finish_flow_label(binder, post_elseif_label, current);
+        // an incomplete `elseif` without condition leaves the label without antecedents, the
+        // branches after it still start from the state before it
+        else_label = match binder.get_flow(post_elseif_label) {
+            Some(flow_node) if flow_node.antecedent.is_some() => post_elseif_label,
+            _ => pre_elseif_label,
+        };
         if let Some(elseif_block) = elseif_clause.get_block() {
             let current = finish_flow_label(binder, elseif_then_label, current);
             let block_id = bind_block(binder, elseif_block, current);
@@ -6,13 +6,13 @@ use rowan::TextSize;
 use smol_str::SmolStr;
 
 use crate::{
-    AnalyzeError, DbIndex, FileId, FlowAntecedent, FlowId, FlowNode, FlowNodeKind, FlowTree,
-    LuaClosureId, LuaDeclId,
+    AnalyzeError, FileId, FlowAntecedent, FlowId, FlowNode, FlowNodeKind, FlowTree, LuaClosureId,
+    LuaDeclId, LuaReferenceIndex,
 };
 
 #[derive(Debug)]
 pub struct FlowBinder<'a> {
-    pub db: &'a mut DbIndex,
+    pub reference_index: &'a LuaReferenceIndex,
     pub file_id: FileId,
     pub decl_bind_expr_ref: HashMap<LuaDeclId, LuaAstPtr<LuaExpr>>,
     pub start: FlowId,
@@ -26,12 +26,13 @@ pub struct FlowBinder<'a> {
     labels: HashMap<LuaClosureId, HashMap<SmolStr, FlowId>>,
     goto_stats: Vec<GotoCache>,
     bindings: HashMap<LuaSyntaxId, FlowId>,
+    errors: Vec<AnalyzeError>,
 }
 
 impl<'a> FlowBinder<'a> {
-    pub fn new(db: &'a mut DbIndex, file_id: FileId) -> Self {
+    pub fn new(reference_index: &'a LuaReferenceIndex, file_id: FileId) -> Self {
         let mut binder = FlowBinder {
-            db,
+            reference_index,
             file_id,
             flow_nodes: Vec::new(),
             multiple_antecedents: Vec::new(),
@@ -45,6 +46,7 @@ impl<'a> FlowBinder<'a> {
             loop_label: FlowId::default(),
             true_target: FlowId::default(),
             false_target: FlowId::default(),
+            errors: Vec::new(),
         };
 
         binder.start = binder.create_start();
@@ -181,19 +183,19 @@ impl<'a> FlowBinder<'a> {
     }
 
     pub fn report_error(&mut self, error: AnalyzeError) {
-        self.db
-            .get_diagnostic_index_mut()
-            .add_diagnostic(self.file_id, error);
+        self.errors.push(error);
     }
 
-    pub fn finish(self) -> FlowTree {
-        FlowTree::new(
+    /// Returns the flow tree and the errors reported while binding.
+    pub fn finish(self) -> (FlowTree, Vec<AnalyzeError>) {
+        let flow_tree = FlowTree::new(
             self.decl_bind_expr_ref,
             self.flow_nodes,
             self.multiple_antecedents,
             // self.labels,
             self.bindings,
-        )
+        );
+        (flow_tree, self.errors)
     }
 }
 
@@ -8,6 +8,7 @@ use crate::{
             bind_analyze::{bind_analyze, check_goto_label},
             binder::FlowBinder,
         },
+        parallel::{analysis_threads, par_map_files},
     },
     db_index::DbIndex,
     profile::Profile,
@@ -20,15 +21,20 @@ pub struct FlowAnalysisPipeline;
 impl AnalysisPipeline for FlowAnalysisPipeline {
     fn analyze(db: &mut DbIndex, context: &mut AnalyzeContext) {
         let _p = Profile::cond_new("flow analyze", context.tree_list.len() > 1);
-        let tree_list = context.tree_list.clone();
-        // build decl and ref flow chain
-        for in_filed_tree in &tree_list {
-            let chunk = in_filed_tree.value.clone();
-            let file_id = in_filed_tree.file_id;
-            let mut binder = FlowBinder::new(db, file_id);
+        let threads = analysis_threads(&context.config);
+        // build decl and ref flow chain, binding only reads the reference index
+        let reference_index = db.get_reference_index();
+        let results = par_map_files(&context.tree_list, threads, |file_id, chunk| {
+            let mut binder = FlowBinder::new(reference_index, file_id);
             bind_analyze(&mut binder, chunk);
             check_goto_label(&mut binder);
-            let flow_tree = binder.finish();
+            (file_id, binder.finish())
+        });
+
+        for (file_id, (flow_tree, errors)) in results {
+            for error in errors {
+                db.get_diagnostic_index_mut().add_diagnostic(file_id, error);
+            }
             db.get_flow_index_mut().add_flow_tree(file_id, flow_tree);
         }
     }
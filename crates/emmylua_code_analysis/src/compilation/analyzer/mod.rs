@@ -4,6 +4,7 @@ mod doc;
 mod flow;
 mod infer_cache_manager;
 mod lua;
+mod parallel;
 mod unresolve;
 
 use std::{collections::HashMap, sync::Arc};
@@ -119,7 +120,6 @@ fn module_analyze(
 #[derive(Debug)]
 pub struct AnalyzeContext {
     tree_list: Vec<InFiled<LuaChunk>>,
-    #[allow(unused)]
     config: Arc<Emmyrc>,
     unresolves: Vec<(UnResolve, InferFailReason)>,
     infer_manager: InferCacheManager,
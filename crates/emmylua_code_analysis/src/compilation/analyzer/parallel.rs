@@ -0,0 +1,83 @@
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use emmylua_parser::{LuaAstNode, LuaChunk, LuaSyntaxNode};
+use rowan::GreenNode;
+
+use crate::{Emmyrc, FileId, InFiled};
+
+/// Number of worker threads for the per-file analysis phases.
+pub fn analysis_threads(emmyrc: &Emmyrc) -> usize {
+    match emmyrc.workspace.analysis_threads {
+        0 => thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1),
+        threads => threads,
+    }
+}
+
+/// Run `f` for every file on up to `threads` threads and return the results in the order of
+/// `tree_list`, no matter which thread produced them.
+///
+/// Red syntax nodes can not cross threads, every worker builds its own red tree from the green
+/// tree of the file.
+pub fn par_map_files<R, F>(tree_list: &[InFiled<LuaChunk>], threads: usize, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(FileId, LuaChunk) -> R + Sync,
+{
+    let threads = threads.min(tree_list.len());
+    if threads <= 1 {
+        return tree_list
+            .iter()
+            .map(|in_filed_tree| f(in_filed_tree.file_id, in_filed_tree.value.clone()))
+            .collect();
+    }
+
+    let green_list: Vec<(FileId, GreenNode)> = tree_list
+        .iter()
+        .map(|in_filed_tree| {
+            (
+                in_filed_tree.file_id,
+                in_filed_tree.value.syntax().green().into_owned(),
+            )
+        })
+        .collect();
+
+    // files differ a lot in size, so workers take the next file instead of a fixed slice
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((file_id, green)) = green_list.get(idx) else {
+                            break;
+                        };
+                        // the green tree was taken from a chunk, so its root is one
+                        let chunk = LuaChunk::cast(LuaSyntaxNode::new_root(green.clone()))
+                            .expect("root of a chunk green tree is a chunk");
+                        results.push((idx, f(*file_id, chunk)));
+                    }
+                    results
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| match worker.join() {
+                Ok(results) => results,
+                Err(e) => std::panic::resume_unwind(e),
+            })
+            .collect()
+    });
+
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
@@ -1,10 +1,11 @@
 mod analyzer;
 mod test;
 
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use crate::{
-    Emmyrc, FileId, InFiled, LuaIndex, LuaInferCache, db_index::DbIndex, semantic::SemanticModel,
+    Emmyrc, FileId, InFiled, LuaIndex, LuaInferCache, collect_affected_files, db_index::DbIndex,
+    semantic::SemanticModel,
 };
 
 #[derive(Debug)]
@@ -55,6 +56,35 @@ impl LuaCompilation {
         analyzer::analyze(&mut self.db, need_analyzed_files, self.emmyrc.clone());
     }
 
+    /// Re-analyze the changed files together with every file depending on them, the rest of the
+    /// index is kept. The vfs must already hold the new content, removed files have no content.
+    ///
+    /// Returns all files which were removed from the index.
+    pub fn reanalyze_files(&mut self, changed_file_ids: Vec<FileId>) -> Vec<FileId> {
+        let mut invalidated = HashSet::new();
+        // dependents of what the changed files declared before the change
+        let mut pending = collect_affected_files(&self.db, &changed_file_ids);
+        while !pending.is_empty() {
+            invalidated.extend(pending.iter().copied());
+            self.db.remove_index(pending.clone());
+            let need_analyzed_files: Vec<FileId> = pending
+                .into_iter()
+                .filter(|file_id| self.db.get_vfs().get_syntax_tree(file_id).is_some())
+                .collect();
+            self.update_index(need_analyzed_files.clone());
+
+            // files analyzed before may read what the new content declares
+            pending = collect_affected_files(&self.db, &need_analyzed_files)
+                .into_iter()
+                .filter(|file_id| !invalidated.contains(file_id))
+                .collect();
+        }
+
+        let mut invalidated: Vec<FileId> = invalidated.into_iter().collect();
+        invalidated.sort();
+        invalidated
+    }
+
     pub fn remove_index(&mut self, file_ids: Vec<FileId>) {
         self.db.remove_index(file_ids);
     }
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod test {
+    use crate::VirtualWorkspace;
+
+    const SHAPE: &str = r#"
+        ---@class Circle
+        ---@field kind "circle"
+        ---@field radius number
+
+        ---@class Square
+        ---@field kind "square"
+        ---@field size number
+
+        ---@class Triangle
+        ---@field kind "triangle"
+        ---@field base number
+
+        ---@alias Shape Circle | Square | Triangle
+    "#;
+
+    #[test]
+    fn test_narrow_alias_union() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(SHAPE);
+        ws.def(
+            r#"
+            ---@param s Shape
+            function f(s)
+                if s.kind == "circle" then
+                    A = s
+                else
+                    B = s
+                end
+            end
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("Circle"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("Square | Triangle"));
+    }
+
+    #[test]
+    fn test_narrow_elseif_chain() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(SHAPE);
+        ws.def(
+            r#"
+            ---@param s Shape
+            function f(s)
+                if s.kind == "circle" then
+                elseif s.kind == "square" then
+                    A = s
+                elseif s.kind == "triangle" then
+                    B = s
+                else
+                    C = s
+                end
+            end
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("Square"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("Triangle"));
+        assert_eq!(ws.expr_ty("C"), ws.ty("never"));
+    }
+
+    #[test]
+    fn test_narrow_early_return() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(SHAPE);
+        ws.def(
+            r#"
+            ---@param s Shape?
+            function f(s)
+                if s == nil or s.kind == "circle" then
+                    return
+                end
+                if s.kind ~= "square" then
+                    return
+                end
+
+                A = s
+            end
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("Square"));
+    }
+
+    #[test]
+    fn test_narrow_shared_tag() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@class Ok
+            ---@field status "ok"
+
+            ---@class Pending
+            ---@field status "pending" | "queued"
+
+            ---@class Failed
+            ---@field status "failed"
+
+            ---@param r Ok | Pending | Failed
+            function f(r)
+                if r.status == "queued" then
+                    A = r
+                else
+                    B = r
+                end
+            end
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("Pending"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("Ok | Pending | Failed"));
+    }
+}
@@ -1459,4 +1459,26 @@ _2 = a[1]
         let e_expected = ws.ty("string");
         assert_eq!(e, e_expected);
     }
+
+    #[test]
+    fn test_incomplete_elseif() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+        ---@type string|integer
+        local x
+
+        if type(x) == "string" then
+        elseif
+        else
+            A = x
+        end
+        B = x
+        "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("integer"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("string|integer"));
+    }
 }
@@ -0,0 +1,290 @@
+#[cfg(test)]
+mod test {
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{FileId, VirtualWorkspace};
+
+    /// Analyze `files`, apply `edits` one by one, then compare with a workspace which analyzed the
+    /// final content at once. Returns the incrementally updated workspace.
+    fn check_same_as_full_analysis(
+        files: &[(&str, &str)],
+        edits: &[(&str, Option<&str>)],
+        exprs: &[&str],
+    ) -> VirtualWorkspace {
+        let mut incremental = VirtualWorkspace::new_with_init_std_lib();
+        incremental.def_files(files.to_vec());
+        for (name, text) in edits {
+            let uri = incremental.virtual_url_generator.new_uri(name);
+            match text {
+                Some(text) => {
+                    incremental.def_file(name, text);
+                }
+                None => {
+                    incremental.analysis.remove_file_by_uri(&uri);
+                }
+            }
+        }
+
+        let mut final_files: Vec<(&str, &str)> = Vec::new();
+        for (name, text) in files.iter().map(|(name, text)| (*name, Some(*text))).chain(
+            edits
+                .iter()
+                .map(|(name, text)| (*name, text.as_ref().map(|text| *text))),
+        ) {
+            final_files.retain(|(file_name, _)| *file_name != name);
+            if let Some(text) = text {
+                final_files.push((name, text));
+            }
+        }
+        let mut full = VirtualWorkspace::new_with_init_std_lib();
+        full.def_files(final_files.clone());
+
+        for (name, _) in &final_files {
+            let uri = incremental.virtual_url_generator.new_uri(name);
+            let incremental_id = incremental.analysis.get_file_id(&uri).unwrap();
+            let full_id = full.analysis.get_file_id(&uri).unwrap();
+            assert_eq!(
+                diagnostics(&incremental, incremental_id),
+                diagnostics(&full, full_id),
+                "{}",
+                name
+            );
+        }
+
+        for expr in exprs {
+            let incremental_ty = incremental.expr_ty(expr);
+            let full_ty = full.expr_ty(expr);
+            assert_eq!(
+                incremental.humanize_type_detailed(incremental_ty),
+                full.humanize_type_detailed(full_ty),
+                "{}",
+                expr
+            );
+        }
+
+        incremental
+    }
+
+    fn diagnostics(ws: &VirtualWorkspace, file_id: FileId) -> Vec<String> {
+        let mut diagnostics: Vec<String> = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|diagnostic| format!("{:?}", diagnostic))
+            .collect();
+        diagnostics.sort();
+        diagnostics
+    }
+
+    #[test]
+    fn test_incremental_require() {
+        let mut ws = check_same_as_full_analysis(
+            &[
+                (
+                    "provider.lua",
+                    r#"
+                    local M = {}
+                    function M.get()
+                        return "value"
+                    end
+                    return M
+                    "#,
+                ),
+                (
+                    "consumer.lua",
+                    r#"
+                    local provider = require("provider")
+                    local value = provider.get()
+                    ---@type integer
+                    local n = value
+                    return { value = value }
+                    "#,
+                ),
+            ],
+            &[(
+                "provider.lua",
+                Some(
+                    r#"
+                    local M = {}
+                    function M.get()
+                        return 1
+                    end
+                    return M
+                    "#,
+                ),
+            )],
+            &["require('consumer').value"],
+        );
+        let ty = ws.expr_ty("require('consumer').value");
+        assert_eq!(ws.humanize_type(ty), "1");
+    }
+
+    #[test]
+    fn test_incremental_global() {
+        let mut ws = check_same_as_full_analysis(
+            &[
+                ("global.lua", "GlobalValue = 'text'"),
+                ("user.lua", "DerivedValue = GlobalValue"),
+            ],
+            &[("global.lua", Some("GlobalValue = 1"))],
+            &["DerivedValue"],
+        );
+        let ty = ws.expr_ty("DerivedValue");
+        assert_eq!(ws.humanize_type(ty), "1");
+    }
+
+    #[test]
+    fn test_incremental_type_reference() {
+        let mut ws = check_same_as_full_analysis(
+            &[
+                (
+                    "point.lua",
+                    r#"
+                    ---@class Point
+                    ---@field x string
+                    "#,
+                ),
+                (
+                    "user.lua",
+                    r#"
+                    ---@type Point
+                    local p
+                    PointX = p.x
+                    "#,
+                ),
+            ],
+            &[(
+                "point.lua",
+                Some(
+                    r#"
+                    ---@class Point
+                    ---@field x integer
+                    "#,
+                ),
+            )],
+            &["PointX"],
+        );
+        let ty = ws.expr_ty("PointX");
+        assert_eq!(ws.humanize_type(ty), "integer");
+    }
+
+    #[test]
+    fn test_incremental_member() {
+        let mut ws = check_same_as_full_analysis(
+            &[
+                (
+                    "class.lua",
+                    r#"
+                    ---@class Counter
+                    local Counter = {}
+                    ---@return Counter
+                    function Counter.new()
+                        return setmetatable({}, { __index = Counter })
+                    end
+                    return Counter
+                    "#,
+                ),
+                (
+                    "method.lua",
+                    r#"
+                    ---@class Counter
+                    local Counter = require("class")
+                    ---@return string
+                    function Counter:count()
+                    end
+                    "#,
+                ),
+                (
+                    "user.lua",
+                    r#"
+                    local counter = require("class").new()
+                    local result = counter:count()
+                    return { result = result }
+                    "#,
+                ),
+            ],
+            &[(
+                "method.lua",
+                Some(
+                    r#"
+                    ---@class Counter
+                    local Counter = require("class")
+                    ---@return integer
+                    function Counter:count()
+                    end
+                    "#,
+                ),
+            )],
+            &["require('user').result"],
+        );
+        let ty = ws.expr_ty("require('user').result");
+        assert_eq!(ws.humanize_type(ty), "integer");
+    }
+
+    #[test]
+    fn test_incremental_new_and_removed_module() {
+        let files = [(
+            "consumer.lua",
+            r#"
+            local late = require("late")
+            return { value = late.value }
+            "#,
+        )];
+        let late = r#"
+            return { value = 1 }
+        "#;
+
+        let mut ws = check_same_as_full_analysis(
+            &files,
+            &[("late.lua", Some(late))],
+            &["require('consumer').value"],
+        );
+        let ty = ws.expr_ty("require('consumer').value");
+        assert_eq!(ws.humanize_type(ty), "1");
+
+        check_same_as_full_analysis(
+            &files,
+            &[("late.lua", Some(late)), ("late.lua", None)],
+            &["require('consumer').value"],
+        );
+    }
+
+    #[test]
+    fn test_incremental_keeps_unrelated_files() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let file_ids = ws.def_files(vec![
+            (
+                "provider.lua",
+                "local M = {}\nfunction M.new() return 1 end\nreturn M",
+            ),
+            ("consumer.lua", "return require('provider').new()"),
+            // same member names, but no require of provider.lua
+            (
+                "unrelated.lua",
+                "local M = {}\nfunction M.new() return M.name end\nM.name = 'x'\nreturn M",
+            ),
+        ]);
+
+        let uri = ws.virtual_url_generator.new_uri("provider.lua");
+        let provider_id = ws
+            .analysis
+            .compilation
+            .get_db_mut()
+            .get_vfs_mut()
+            .set_file_content(
+                &uri,
+                Some("local M = {}\nfunction M.new() return 'text' end\nreturn M".to_string()),
+            );
+        let invalidated = ws.analysis.compilation.reanalyze_files(vec![provider_id]);
+
+        let consumer_id = ws
+            .analysis
+            .get_file_id(&ws.virtual_url_generator.new_uri("consumer.lua"))
+            .unwrap();
+        let mut expected = vec![provider_id, consumer_id];
+        expected.sort();
+        assert_eq!(invalidated, expected);
+        assert_eq!(file_ids.len(), 3);
+    }
+}
@@ -0,0 +1,261 @@
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use tempfile::TempDir;
+
+    use crate::{
+        DiagnosticCode, DiagnosticSeveritySetting, LuaTypeDeclId, VirtualWorkspace,
+        collect_affected_files,
+        index_cache::{config_hash, restore_index_cache},
+    };
+
+    const SUBJECT: &str = r#"
+        ---@class Subject
+        local subject = {}
+
+        ---@return Subject
+        function subject.new()
+        end
+
+        return subject
+    "#;
+
+    const RX: &str = r#"
+        local subject = require("subject")
+
+        local rx = {
+            subject = subject,
+        }
+
+        return rx
+    "#;
+
+    const OTHER: &str = r#"
+        ---@class Other
+        ---@field name string
+    "#;
+
+    fn cache_path(dir: &TempDir) -> PathBuf {
+        dir.path().join("index.idx")
+    }
+
+    fn write_cache(path: &Path) {
+        let mut ws = VirtualWorkspace::new();
+        let files = vec![
+            (
+                ws.virtual_url_generator.new_path("rx.lua"),
+                Some(RX.to_string()),
+            ),
+            (
+                ws.virtual_url_generator.new_path("subject.lua"),
+                Some(SUBJECT.to_string()),
+            ),
+            (
+                ws.virtual_url_generator.new_path("other.lua"),
+                Some(OTHER.to_string()),
+            ),
+        ];
+        ws.analysis.update_files_by_path_with_cache(files, path);
+        ws.analysis.save_index_cache(path).unwrap();
+    }
+
+    #[test]
+    fn test_index_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache_path(&dir);
+        write_cache(&path);
+
+        let mut ws = VirtualWorkspace::new();
+        let files = vec![
+            (
+                ws.virtual_url_generator.new_path("rx.lua"),
+                Some(RX.to_string()),
+            ),
+            (
+                ws.virtual_url_generator.new_path("subject.lua"),
+                Some(SUBJECT.to_string()),
+            ),
+            (
+                ws.virtual_url_generator.new_path("other.lua"),
+                Some(OTHER.to_string()),
+            ),
+        ];
+        ws.analysis.update_files_by_path_with_cache(files, &path);
+
+        let ty = ws.expr_ty("require('rx').subject.new()");
+        let expected = ws.ty("Subject");
+        assert_eq!(ty, expected);
+        assert!(ws.check_code_for(
+            crate::DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type Other
+            local o = { name = "a" }
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_index_cache_only_dirty_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache_path(&dir);
+        write_cache(&path);
+
+        let mut ws = VirtualWorkspace::new();
+        let emmyrc = ws.get_emmyrc();
+        let mut file_ids = Vec::new();
+        for (name, text) in [
+            ("rx.lua", RX),
+            ("subject.lua", "return {}"),
+            ("other.lua", OTHER),
+        ] {
+            let uri = ws.virtual_url_generator.new_uri(name);
+            file_ids.push(
+                ws.analysis
+                    .compilation
+                    .get_db_mut()
+                    .get_vfs_mut()
+                    .set_file_content(&uri, Some(text.to_string())),
+            );
+        }
+
+        let changed_files =
+            restore_index_cache(ws.analysis.compilation.get_db_mut(), &emmyrc, &path);
+        assert_eq!(changed_files, Some(vec![file_ids[1]]));
+        // subject.lua changed and rx.lua requires it, other.lua is reused
+        let affected_files =
+            collect_affected_files(ws.analysis.compilation.get_db(), &[file_ids[1]]);
+        assert_eq!(affected_files, vec![file_ids[0], file_ids[1]]);
+        assert!(
+            ws.analysis
+                .compilation
+                .get_db()
+                .get_type_index()
+                .find_type_decl(file_ids[2], "Other")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_index_cache_outdated_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache_path(&dir);
+        write_cache(&path);
+
+        let mut ws = VirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.strict.require_path = !emmyrc.strict.require_path;
+        ws.update_emmyrc(emmyrc.clone());
+        let uri = ws.virtual_url_generator.new_uri("rx.lua");
+        ws.analysis
+            .compilation
+            .get_db_mut()
+            .get_vfs_mut()
+            .set_file_content(&uri, Some(RX.to_string()));
+
+        let changed_files =
+            restore_index_cache(ws.analysis.compilation.get_db_mut(), &emmyrc, &path);
+        assert_eq!(changed_files, None);
+    }
+
+    #[test]
+    fn test_index_cache_file_inserted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache_path(&dir);
+        write_cache(&path);
+
+        // `added.lua` takes the id `subject.lua` had when the cache was written
+        let mut ws = VirtualWorkspace::new();
+        let emmyrc = ws.get_emmyrc();
+        let mut file_ids = Vec::new();
+        for (name, text) in [
+            ("rx.lua", RX),
+            ("added.lua", "return {}"),
+            ("subject.lua", SUBJECT),
+            ("other.lua", OTHER),
+        ] {
+            let uri = ws.virtual_url_generator.new_uri(name);
+            file_ids.push(
+                ws.analysis
+                    .compilation
+                    .get_db_mut()
+                    .get_vfs_mut()
+                    .set_file_content(&uri, Some(text.to_string())),
+            );
+        }
+
+        let changed_files =
+            restore_index_cache(ws.analysis.compilation.get_db_mut(), &emmyrc, &path);
+        assert_eq!(changed_files, Some(vec![file_ids[1]]));
+        let type_index = ws.analysis.compilation.get_db().get_type_index();
+        assert!(type_index.find_type_decl(file_ids[2], "Subject").is_some());
+        assert!(type_index.find_type_decl(file_ids[3], "Other").is_some());
+        assert_eq!(
+            ws.analysis
+                .compilation
+                .get_db()
+                .get_file_dependencies_index()
+                .get_required_files(&file_ids[0])
+                .map(|files| files.iter().copied().collect::<Vec<_>>()),
+            Some(vec![file_ids[2]])
+        );
+    }
+
+    #[test]
+    fn test_index_cache_file_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache_path(&dir);
+        write_cache(&path);
+
+        let mut ws = VirtualWorkspace::new();
+        let files = vec![
+            (
+                ws.virtual_url_generator.new_path("subject.lua"),
+                Some(SUBJECT.to_string()),
+            ),
+            (
+                ws.virtual_url_generator.new_path("rx.lua"),
+                Some(RX.to_string()),
+            ),
+        ];
+        ws.analysis.update_files_by_path_with_cache(files, &path);
+
+        let type_index = ws.analysis.compilation.get_db().get_type_index();
+        assert!(
+            type_index
+                .get_type_decl(&LuaTypeDeclId::new("Other"))
+                .is_none()
+        );
+        assert_eq!(ws.expr_ty("require('rx').subject.new()"), ws.ty("Subject"));
+    }
+
+    #[test]
+    fn test_config_hash_map_order() {
+        let ws = VirtualWorkspace::new();
+        let codes = [
+            DiagnosticCode::Unused,
+            DiagnosticCode::UndefinedGlobal,
+            DiagnosticCode::MissingFields,
+            DiagnosticCode::ParamTypeMismatch,
+            DiagnosticCode::AssignTypeMismatch,
+            DiagnosticCode::NeedCheckNil,
+        ];
+        let mut emmyrc = ws.get_emmyrc();
+        for code in codes {
+            emmyrc
+                .diagnostics
+                .severity
+                .insert(code, DiagnosticSeveritySetting::Hint);
+        }
+        let mut reversed = ws.get_emmyrc();
+        for code in codes.into_iter().rev() {
+            reversed
+                .diagnostics
+                .severity
+                .insert(code, DiagnosticSeveritySetting::Hint);
+        }
+
+        let db = ws.analysis.compilation.get_db();
+        assert_eq!(config_hash(db, &emmyrc), config_hash(db, &reversed));
+    }
+}
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    const USER: &str = r#"
+        ---@class User
+        ---@field id integer
+        ---@field name string
+        ---@field email? string
+    "#;
+
+    #[test]
+    fn test_mapped_keyof() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@type { [K in keyof User]: User[K] }
+            local user
+            A = user.name
+            B = user.email
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("string"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("string?"));
+    }
+
+    #[test]
+    fn test_mapped_modifiers() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@type { [K in keyof User]+?: User[K] }
+            local patch
+            A = patch.id
+
+            ---@type { [K in keyof User]-?: User[K] }
+            local full
+            B = full.email
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("integer?"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("string"));
+    }
+
+    #[test]
+    fn test_mapped_literal_keys() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@type { [K in "x" | "y"]: number }
+            local point
+            A = point.y
+            B = point.z
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("number"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("nil"));
+    }
+
+    #[test]
+    fn test_mapped_remap() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@class Columns
+            ---@field id "user_id"
+            ---@field name "user_name"
+
+            ---@type { [K in keyof Columns as Columns[K]]: K }
+            local by_column
+            A = by_column.user_id
+            B = by_column.id
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("\"id\""));
+        assert_eq!(ws.expr_ty("B"), ws.ty("nil"));
+    }
+
+    #[test]
+    fn test_mapped_generic_alias() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@alias Nullable<T> { [K in keyof T]: T[K] | nil }
+
+            ---@type Nullable<User>
+            local user
+            A = user.id
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("integer?"));
+    }
+
+    #[test]
+    fn test_mapped_index_signature() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@type { [K in string]: integer }
+            local counts
+            A = counts.apples
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("integer"));
+    }
+
+    #[test]
+    fn test_mapped_assign() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@alias Opt<T> { [K in keyof T]?: T[K] }
+            ---@alias Same<T> { [K in keyof T]: T[K] }
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type Opt<User>
+            local o = { name = "x" }
+            "#,
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type User
+            local user
+
+            ---@type Same<User>
+            local same = user
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type Opt<User>
+            local o = { name = 1 }
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type { [K in "x" | "y"]: number }
+            local point = { x = 1, y = "a" }
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_mapped_param() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@alias Opt<T> { [K in keyof T]?: T[K] }
+
+            ---@param patch Opt<User>
+            function update_user(patch) end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            update_user({ name = "x" })
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            update_user(5)
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            update_user({ id = "x" })
+            "#,
+        ));
+    }
+}
@@ -7,12 +7,16 @@ mod closure_param_infer_test;
 mod closure_return_test;
 mod decl_test;
 mod diagnostic_disable_test;
+mod discriminated_union_test;
 mod export_test;
 mod flow;
 mod for_range_var_infer_test;
 mod generic_test;
+mod incremental_analysis_test;
+mod index_cache_test;
 mod infer_str_tpl_test;
 mod inherit_type;
+mod mapped_type_test;
 mod mathlib_test;
 mod member_infer_test;
 mod metatable_test;
@@ -21,10 +25,13 @@ mod multi_return;
 mod out_of_order;
 mod overload_field;
 mod overload_test;
+mod parallel_analysis_test;
 mod pcall_test;
 mod return_unwrap_test;
 mod static_cal_cmp;
 mod syntax_error_test;
+mod template_literal_test;
 mod tuple_test;
 mod type_check_test;
 mod unpack_test;
+mod utility_type_test;
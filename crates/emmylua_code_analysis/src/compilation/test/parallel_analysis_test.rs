@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod test {
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{FileId, VirtualWorkspace};
+
+    const FILES: [(&str, &str); 5] = [
+        (
+            "meta/renamed.lua",
+            r#"
+            ---@meta lib.renamed
+            ---@class Renamed
+            ---@field value integer
+            local renamed = {}
+            return renamed
+            "#,
+        ),
+        (
+            "shape.lua",
+            r#"
+            ---@namespace Geometry
+
+            ---@class Shape
+            ---@field area fun(self: Shape): number
+            local Shape = {}
+
+            ---@return Geometry.Shape
+            function Shape.new()
+                return setmetatable({}, { __index = Shape })
+            end
+
+            function Shape:area()
+                return 0
+            end
+
+            return Shape
+            "#,
+        ),
+        (
+            "app.lua",
+            r#"
+            local Shape = require("shape")
+            local renamed = require("lib.renamed")
+            local key = "dynamic"
+
+            GlobalConfig = {
+                name = "app",
+                [key] = 1,
+                count = renamed.value,
+            }
+
+            local shape = Shape.new()
+            local area = shape:area()
+            for i = 1, 10 do
+                area = area + i
+            end
+
+            goto missing
+            return area
+            "#,
+        ),
+        (
+            "user.lua",
+            r#"
+            local app = GlobalConfig
+            _G.Extra = app.name
+
+            local function sum(a, b, ...)
+                return a + b + select(2, ...)
+            end
+
+            return sum(app.count, 1)
+            "#,
+        ),
+        (
+            "broken.lua",
+            r#"
+            local x <const> = 1
+            x = 2
+            ::dup::
+            ::dup::
+            "#,
+        ),
+    ];
+
+    fn new_workspace(threads: usize) -> (VirtualWorkspace, Vec<FileId>) {
+        let mut ws = VirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.workspace.analysis_threads = threads;
+        ws.update_emmyrc(emmyrc);
+        ws.analysis.init_std_lib(None);
+        let file_ids = ws.def_files(FILES.to_vec());
+        (ws, file_ids)
+    }
+
+    fn diagnostics(ws: &VirtualWorkspace, file_id: FileId) -> Vec<String> {
+        let mut diagnostics: Vec<String> = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|diagnostic| format!("{:?}", diagnostic))
+            .collect();
+        diagnostics.sort();
+        diagnostics
+    }
+
+    #[test]
+    fn test_parallel_analysis_same_as_sequential() {
+        let (mut sequential, sequential_ids) = new_workspace(1);
+        let (mut parallel, parallel_ids) = new_workspace(8);
+        assert_eq!(sequential_ids, parallel_ids);
+
+        for file_id in sequential_ids {
+            assert_eq!(
+                diagnostics(&sequential, file_id),
+                diagnostics(&parallel, file_id)
+            );
+        }
+
+        for expr in [
+            "require('shape').new()",
+            "require('shape').new():area()",
+            "require('lib.renamed').value",
+            "GlobalConfig",
+            "GlobalConfig.count",
+            "Extra",
+            "string.format('%d', 1)",
+        ] {
+            let sequential_ty = sequential.expr_ty(expr);
+            let parallel_ty = parallel.expr_ty(expr);
+            assert_eq!(
+                sequential.humanize_type_detailed(sequential_ty),
+                parallel.humanize_type_detailed(parallel_ty),
+                "{}",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn test_parallel_analysis_resolves_cross_file() {
+        let (mut ws, _) = new_workspace(4);
+        let ty = ws.expr_ty("require('shape').new()");
+        let expected = ws.ty("Geometry.Shape");
+        assert_eq!(ty, expected);
+
+        let ty = ws.expr_ty("require('lib.renamed').value");
+        assert_eq!(ty, crate::LuaType::Integer);
+    }
+}
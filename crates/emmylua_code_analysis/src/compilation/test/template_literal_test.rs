@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    const EVENT: &str = r#"
+        ---@alias Event "click" | "close"
+    "#;
+
+    #[test]
+    fn test_literal_placeholder() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        assert_eq!(ws.ty("`is_${boolean}`"), ws.ty(r#""is_true" | "is_false""#));
+    }
+
+    #[test]
+    fn test_alias_placeholder() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(EVENT);
+        ws.def(
+            r#"
+            ---@param name `on_${Event}`
+            function on(name) end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            on("on_click")
+            on("on_close")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            on("on_open")
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_union_of_literals() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(EVENT);
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type `on_${Event}`
+            local handler
+
+            ---@type "on_click" | "on_close"
+            local name = handler
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type `on_${Event}`
+            local handler
+
+            ---@type "on_click"
+            local name = handler
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_pattern_placeholder() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@param name `rpc:${string}_${integer}`
+            function call(name) end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            call("rpc:save_1")
+            call("rpc:load_player_20")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            call("rpc:save_x")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            ---@type string
+            local name
+            call(name)
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_generic_alias() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(EVENT);
+        ws.def(
+            r#"
+            ---@alias Handler<E> `on_${E}`
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type Handler<Event>
+            local handler = "on_close"
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type Handler<Event>
+            local handler = "close"
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_generic_capture() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@generic T
+            ---@param name `on_${T}`
+            ---@return T
+            local function event_of(name) end
+
+            A = event_of("on_click")
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty(r#""click""#));
+    }
+
+    #[test]
+    fn test_humanize() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let ty = ws.ty("`rpc:${string}`");
+        assert_eq!(ws.humanize_type(ty), "`rpc:${string}`");
+    }
+}
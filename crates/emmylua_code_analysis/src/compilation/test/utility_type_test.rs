@@ -0,0 +1,387 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    const USER: &str = r#"
+        ---@class User
+        ---@field id integer
+        ---@field name string
+        ---@field password string
+        ---@field email? string
+    "#;
+
+    #[test]
+    fn test_partial() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@type std.Partial<User>
+            local user
+            A = user.name
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("string?"));
+        assert!(ws.check_code_for(
+            DiagnosticCode::MissingFields,
+            r#"
+            ---@type std.Partial<User>
+            local user = { name = "a" }
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_required() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@type std.Required<User>
+            local user
+            A = user.email
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("string"));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::MissingFields,
+            r#"
+            ---@type std.Required<User>
+            local user = { id = 1, name = "a", password = "b" }
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_pick() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@type std.Pick<User, "id" | "name">
+            local user
+            A = user.id
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("integer"));
+        assert!(ws.check_code_for(
+            DiagnosticCode::MissingFields,
+            r#"
+            ---@type std.Pick<User, "id" | "name">
+            local user = { id = 1, name = "a" }
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::MissingFields,
+            r#"
+            ---@type std.Pick<User, "id" | "name">
+            local user = { id = 1 }
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_omit() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::MissingFields,
+            r#"
+            ---@type std.Omit<User, "password">
+            local user = { id = 1, name = "a" }
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::MissingFields,
+            r#"
+            ---@type std.Omit<User, "password">
+            local user = { name = "a" }
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_readonly() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@type std.Readonly<User>
+            local user
+            A = user.name
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("string"));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ReadOnly,
+            r#"
+            ---@type std.Readonly<User>
+            local user
+            user.name = "b"
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ReadOnly,
+            r#"
+            ---@alias FrozenUser std.Readonly<User>
+
+            ---@type FrozenUser
+            local user
+            user.id = 2
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_readonly_generic_alias() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ReadOnly,
+            r#"
+            ---@alias Frozen<T> std.Readonly<T>
+
+            ---@type Frozen<User>
+            local user
+            user.id = 2
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ReadOnly,
+            r#"
+            ---@alias Locked<T> Frozen<T>
+
+            ---@type Locked<User>
+            local user
+            user.id = 2
+            "#,
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::ReadOnly,
+            r#"
+            ---@alias Patch<T> std.Partial<T>
+
+            ---@type Patch<User>
+            local user
+            user.id = 2
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_param_type_check() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@param user std.Partial<User>
+            function patch_user(user) end
+
+            ---@param user std.Pick<User, "name">
+            function rename_user(user) end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            patch_user({ name = "a" })
+            rename_user({ name = "a" })
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            patch_user(5)
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            patch_user({ name = 1 })
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            rename_user({ name = 1 })
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_assign_type_check() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        assert!(ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type std.Record<"x" | "y", number>
+            local point = { x = 1, y = 2 }
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@type std.Record<"x" | "y", number>
+            local point = { x = "a", y = 2 }
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@alias Patch<T> std.Partial<T>
+
+            ---@type Patch<User>
+            local user = { id = "a" }
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_record() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@type std.Record<"x" | "y", number>
+            local point
+            A = point.x
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("number"));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::MissingFields,
+            r#"
+            ---@type std.Record<"x" | "y", number>
+            local point = { x = 1 }
+            "#,
+        ));
+        ws.def(
+            r#"
+            ---@type std.Record<string, number>
+            local scores
+            B = scores["alice"]
+            "#,
+        );
+        assert_eq!(ws.expr_ty("B"), ws.ty("number"));
+    }
+
+    #[test]
+    fn test_generic_alias() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(USER);
+        ws.def(
+            r#"
+            ---@alias Patch<T> std.Partial<T>
+
+            ---@type Patch<User>
+            local patch
+            A = patch.id
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("integer?"));
+    }
+
+    #[test]
+    fn test_return_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@generic F: function
+            ---@param f F
+            ---@return std.ReturnType<F>
+            function call(f) end
+
+            ---@return string
+            local function get_name() end
+
+            ---@type fun(): integer
+            local get_id
+
+            A = call(get_name)
+            B = call(get_id)
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("string"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("integer"));
+        assert_eq!(ws.ty("std.ReturnType<fun(): boolean>"), ws.ty("boolean"));
+    }
+
+    #[test]
+    fn test_parameters() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@generic F: function
+            ---@param f F
+            ---@return std.Parameters<F>
+            function params(f) end
+
+            ---@param a integer
+            ---@param b string
+            local function add(a, b) end
+
+            A = params(add)
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("[integer, string]"));
+        assert_eq!(
+            ws.ty("std.Parameters<fun(a: integer, b: string)>"),
+            ws.ty("[integer, string]")
+        );
+    }
+
+    #[test]
+    fn test_wrapper() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@generic F: function
+            ---@param f F
+            ---@return fun(...: std.Parameters<F>): std.ReturnType<F>
+            function memoize(f) end
+
+            ---@param id integer
+            ---@return string
+            local function load(id) end
+
+            local cached = memoize(load)
+            A = cached(1)
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("string"));
+    }
+
+    #[test]
+    fn test_awaited() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@generic T
+            ---@param value T
+            ---@return std.Awaited<T>
+            function await(value) end
+
+            ---@async
+            ---@return integer
+            local function fetch() end
+
+            A = await(fetch)
+            B = await("done")
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("integer"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("string"));
+    }
+}
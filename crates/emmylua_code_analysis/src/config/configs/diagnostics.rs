@@ -31,6 +31,10 @@ pub struct EmmyrcDiagnostic {
     /// Delay between opening/changing a file and scanning it for errors, in milliseconds.
     #[schemars(extend("x-vscode-setting" = true))]
     pub diagnostic_interval: Option<u64>,
+    /// Layering rules between the directories of the workspace, files matching `from` must not
+    /// require modules or use globals defined in files matching `deny`.
+    #[serde(default)]
+    pub layer_rules: Vec<EmmyrcLayerRule>,
 }
 
 impl Default for EmmyrcDiagnostic {
@@ -43,10 +47,22 @@ impl Default for EmmyrcDiagnostic {
             severity: HashMap::new(),
             enables: Vec::new(),
             diagnostic_interval: Some(500),
+            layer_rules: Vec::new(),
         }
     }
 }
 
+/// A layering rule, the globs are relative to the workspace root. eg:
+/// { "from": "client/**", "deny": ["server/**"] }
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+pub struct EmmyrcLayerRule {
+    /// Glob of the files the rule applies to.
+    pub from: String,
+    /// Globs of the files those files must not depend on.
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -19,14 +19,14 @@ mod workspace;
 pub use code_action::EmmyrcCodeAction;
 pub use codelen::EmmyrcCodeLens;
 pub use completion::{EmmyrcCompletion, EmmyrcFilenameConvention};
-pub use diagnostics::{DiagnosticSeveritySetting, EmmyrcDiagnostic};
+pub use diagnostics::{DiagnosticSeveritySetting, EmmyrcDiagnostic, EmmyrcLayerRule};
 pub use doc::{DocSyntax, EmmyrcDoc};
 pub use document_color::EmmyrcDocumentColor;
 pub use hover::EmmyrcHover;
 pub use inlayhint::EmmyrcInlayHint;
 pub use inline_values::EmmyrcInlineValues;
 pub use references::EmmyrcReference;
-pub use reformat::{EmmyrcExternalTool, EmmyrcReformat};
+pub use reformat::{EmmyrcExternalTool, EmmyrcFormatter, EmmyrcReformat};
 pub use resource::EmmyrcResource;
 pub use runtime::{EmmyrcLuaVersion, EmmyrcRuntime};
 pub use semantictoken::EmmyrcSemanticToken;
@@ -1,7 +1,8 @@
+use emmylua_code_style::LuaCodeStyle;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct EmmyrcReformat {
     /// Whether to enable external tool formatting.
@@ -15,6 +16,59 @@ pub struct EmmyrcReformat {
     /// Whether to use the diff algorithm for formatting.
     #[serde(default = "default_false")]
     pub use_diff: bool,
+
+    /// The formatter used when no external tool is configured. `native` is opt-in, the default
+    /// keeps EmmyLuaCodeStyle and `.editorconfig`.
+    #[serde(default)]
+    pub formatter: EmmyrcFormatter,
+
+    /// The maximum line width of the native formatter, longer lines are wrapped. 0 disables
+    /// wrapping.
+    #[serde(default = "default_max_line_width")]
+    pub max_line_width: usize,
+
+    /// Options of the native formatter and of the `code-style-check` diagnostic, with the keys of
+    /// the `emmylua_format` config file. `maxLineWidth` replaces its `max_line_width`.
+    #[serde(default)]
+    pub style: Option<LuaCodeStyle>,
+
+    /// Whether document formatting with the native formatter only formats the lines changed since
+    /// the file was opened or last saved.
+    #[serde(default = "default_false")]
+    pub modified_lines_only: bool,
+}
+
+impl Default for EmmyrcReformat {
+    fn default() -> Self {
+        Self {
+            external_tool: None,
+            external_tool_range_format: None,
+            use_diff: false,
+            formatter: EmmyrcFormatter::default(),
+            max_line_width: default_max_line_width(),
+            style: None,
+            modified_lines_only: false,
+        }
+    }
+}
+
+impl EmmyrcReformat {
+    /// Style of the native formatter, options missing from `style` have their default value.
+    pub fn get_native_style(&self) -> LuaCodeStyle {
+        let mut style = self.style.clone().unwrap_or_default();
+        style.max_line_width = self.max_line_width;
+        style
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum EmmyrcFormatter {
+    /// The EmmyLuaCodeStyle formatter, configured by `.editorconfig`.
+    #[default]
+    CodeStyle,
+    /// The built-in formatter of `emmylua_code_style`.
+    Native,
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Default)]
@@ -35,6 +89,10 @@ fn default_timeout() -> u64 {
     5000
 }
 
+fn default_max_line_width() -> usize {
+    120
+}
+
 fn default_false() -> bool {
     false
 }
@@ -37,6 +37,19 @@ pub struct EmmyrcWorkspace {
     #[serde(default = "enable_reindex_default")]
     #[schemars(extend("x-vscode-setting" = true))]
     pub enable_reindex: bool,
+    /// Cache the analyzed index on disk, unchanged files are loaded from the cache on the next start.
+    #[serde(default)]
+    pub enable_index_cache: bool,
+    /// Index cache directory. Defaults to the system cache directory.
+    #[serde(default)]
+    pub index_cache_dir: Option<String>,
+    /// Number of threads used to analyze files. `0` uses all available cores.
+    #[serde(default)]
+    pub analysis_threads: usize,
+    /// Entry point files, eg: ["main.lua", "conf.lua"]. Files of the workspace which they never
+    /// reach through `require` are reported by `unreachable-file`.
+    #[serde(default)]
+    pub entry_points: Vec<String>,
 }
 
 impl Default for EmmyrcWorkspace {
@@ -51,6 +64,10 @@ impl Default for EmmyrcWorkspace {
             module_map: Vec::new(),
             reindex_duration: 5000,
             enable_reindex: false,
+            enable_index_cache: false,
+            index_cache_dir: None,
+            analysis_threads: 0,
+            entry_points: Vec::new(),
         }
     }
 }
@@ -11,9 +11,9 @@ pub use config_loader::{load_configs, load_configs_raw};
 pub use configs::{
     DiagnosticSeveritySetting, DocSyntax, EmmyrcCodeAction, EmmyrcCodeLens, EmmyrcCompletion,
     EmmyrcDiagnostic, EmmyrcDoc, EmmyrcDocumentColor, EmmyrcExternalTool, EmmyrcFilenameConvention,
-    EmmyrcHover, EmmyrcInlayHint, EmmyrcInlineValues, EmmyrcLuaVersion, EmmyrcReference,
-    EmmyrcReformat, EmmyrcResource, EmmyrcRuntime, EmmyrcSemanticToken, EmmyrcSignature,
-    EmmyrcStrict, EmmyrcWorkspace, EmmyrcWorkspaceModuleMap,
+    EmmyrcFormatter, EmmyrcHover, EmmyrcInlayHint, EmmyrcInlineValues, EmmyrcLayerRule,
+    EmmyrcLuaVersion, EmmyrcReference, EmmyrcReformat, EmmyrcResource, EmmyrcRuntime,
+    EmmyrcSemanticToken, EmmyrcSignature, EmmyrcStrict, EmmyrcWorkspace, EmmyrcWorkspaceModuleMap,
 };
 use emmylua_parser::{LuaLanguageLevel, LuaNonStdSymbolSet, ParserConfig, SpecialFunction};
 use regex::Regex;
@@ -123,6 +123,14 @@ impl Emmyrc {
         self.workspace.ignore_dir =
             process_and_dedup(self.workspace.ignore_dir.iter(), workspace_root);
 
+        self.workspace.entry_points =
+            process_and_dedup(self.workspace.entry_points.iter(), workspace_root);
+
+        if let Some(index_cache_dir) = &self.workspace.index_cache_dir {
+            self.workspace.index_cache_dir =
+                Some(pre_process_path(index_cache_dir, workspace_root));
+        }
+
         self.resource.paths = process_and_dedup(self.resource.paths.iter(), workspace_root);
     }
 }
@@ -53,11 +53,11 @@ impl<'de> Deserialize<'de> for LuaDeclId {
                     return Err(E::custom("expected format 'file_id:position'"));
                 }
 
-                let file_id = FileId {
-                    id: parts[0]
+                let file_id = FileId::from_cached(
+                    parts[0]
                         .parse()
                         .map_err(|e| E::custom(format!("invalid file_id: {}", e)))?,
-                };
+                );
                 let position = TextSize::new(
                     parts[1]
                         .parse()
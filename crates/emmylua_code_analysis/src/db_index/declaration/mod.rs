@@ -8,13 +8,14 @@ pub use decl::{LocalAttribute, LuaDecl};
 pub use decl_id::LuaDeclId;
 pub use decl_tree::{LuaDeclOrMemberId, LuaDeclarationTree};
 pub use scope::{LuaScope, LuaScopeId, LuaScopeKind, ScopeOrDeclId};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::FileId;
 
 use super::traits::LuaIndex;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaDeclIndex {
     decl_trees: HashMap<FileId, LuaDeclarationTree>,
 }
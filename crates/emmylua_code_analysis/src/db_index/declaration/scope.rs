@@ -1,10 +1,11 @@
 use rowan::{TextRange, TextSize};
+use serde::{Deserialize, Serialize};
 
 use crate::FileId;
 
 use super::LuaDeclId;
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum LuaScopeKind {
     Normal,
     Repeat,
@@ -15,7 +16,7 @@ pub enum LuaScopeKind {
     MethodStat,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub struct LuaScope {
     parent: Option<LuaScopeId>,
     children: Vec<ScopeOrDeclId>,
@@ -72,13 +73,13 @@ impl LuaScope {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub struct LuaScopeId {
     pub file_id: FileId,
     pub id: u32,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum ScopeOrDeclId {
     Scope(LuaScopeId),
     Decl(LuaDeclId),
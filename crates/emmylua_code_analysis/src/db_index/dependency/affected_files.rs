@@ -0,0 +1,93 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{DbIndex, FileId, LuaMemberOwner, WorkspaceId};
+
+/// Collect `file_ids` and every file whose analysis may have read something they declare,
+/// transitively. The result is sorted.
+///
+/// A file depends on another one when it requires it, or names a global or a type declared
+/// there. Member keys alone are no edge, `.new` or `.name` would tie together unrelated files. Dependents are only collected in the same or a later load stage, a full reindex
+/// analyzes std files before libraries and libraries before the main workspace, so a main file
+/// never affects the analysis of a library.
+pub fn collect_affected_files(db: &DbIndex, file_ids: &[FileId]) -> Vec<FileId> {
+    let mut affected: HashSet<FileId> = file_ids.iter().copied().collect();
+    let mut queue: VecDeque<FileId> = file_ids.iter().copied().collect();
+    while let Some(file_id) = queue.pop_front() {
+        let stage = load_stage(db, &file_id);
+        for dependent in collect_direct_dependents(db, file_id) {
+            if load_stage(db, &dependent) >= stage && affected.insert(dependent) {
+                queue.push_back(dependent);
+            }
+        }
+    }
+
+    let mut affected: Vec<FileId> = affected.into_iter().collect();
+    affected.sort();
+    affected
+}
+
+fn load_stage(db: &DbIndex, file_id: &FileId) -> u8 {
+    let workspace_id = db
+        .get_module_index()
+        .get_module(*file_id)
+        .map_or(WorkspaceId::MAIN, |module_info| module_info.workspace_id);
+    if workspace_id.is_std() {
+        0
+    } else if workspace_id.is_library() {
+        1
+    } else {
+        2
+    }
+}
+
+fn collect_direct_dependents(db: &DbIndex, file_id: FileId) -> HashSet<FileId> {
+    let dependency_index = db.get_file_dependencies_index();
+    let reference_index = db.get_reference_index();
+    let mut dependents: HashSet<FileId> = dependency_index
+        .get_dependent_files(&file_id)
+        .into_iter()
+        .collect();
+
+    if let Some(module_info) = db.get_module_index().get_module(file_id) {
+        dependents.extend(dependency_index.get_unresolved_require_files(&module_info.name));
+    }
+
+    if let Some(decl_tree) = db.get_decl_index().get_decl_tree(&file_id) {
+        for decl in decl_tree.get_decls().values() {
+            if decl.is_global() {
+                dependents.extend(reference_index.get_global_reference_files(decl.get_name()));
+            }
+        }
+    }
+
+    if let Some(type_ids) = db.get_type_index().get_file_types(&file_id) {
+        for type_id in type_ids {
+            dependents.extend(reference_index.get_type_reference_files(type_id));
+        }
+    }
+
+    let member_index = db.get_member_index();
+    for member in member_index.get_file_members(&file_id) {
+        match member_index.get_current_owner(&member.get_id()) {
+            // members of a local table are read through the require or the global of this file
+            Some(LuaMemberOwner::Element(element)) if element.file_id == file_id => {}
+            // a table of another file extended here is read through the require of that file
+            Some(LuaMemberOwner::Element(element)) => {
+                dependents.insert(element.file_id);
+                dependents.extend(dependency_index.get_dependent_files(&element.file_id));
+            }
+            Some(LuaMemberOwner::Type(type_id)) => {
+                dependents.extend(reference_index.get_type_reference_files(type_id));
+            }
+            Some(LuaMemberOwner::GlobalPath(global_id)) => {
+                let name = global_id.get_name();
+                let root_name = name.split('.').next().unwrap_or(name);
+                dependents.extend(reference_index.get_global_reference_files(root_name));
+            }
+            _ => {}
+        }
+    }
+
+    dependents.remove(&file_id);
+    dependents
+}
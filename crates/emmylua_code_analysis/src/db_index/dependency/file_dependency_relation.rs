@@ -93,6 +93,146 @@ impl<'a> FileDependencyRelation<'a> {
         }
         result.into_iter().collect()
     }
+
+    /// Require cycles, the strongly connected components of more than one file and the files
+    /// requiring themselves. Each cycle is sorted, and so is the list.
+    pub fn get_require_cycles(&self) -> Vec<Vec<FileId>> {
+        let mut files: Vec<FileId> = self.dependencies.keys().copied().collect();
+        files.sort();
+        let mut tarjan = Tarjan {
+            dependencies: self.dependencies,
+            next_index: 0,
+            indices: HashMap::new(),
+            low_links: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+        for file_id in files {
+            if !tarjan.indices.contains_key(&file_id) {
+                tarjan.visit(file_id);
+            }
+        }
+
+        let mut cycles: Vec<Vec<FileId>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || self
+                        .dependencies
+                        .get(&component[0])
+                        .is_some_and(|deps| deps.contains(&component[0]))
+            })
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Shortest require chain from `from` to `to` through the files of `within`, both ends
+    /// included.
+    pub fn find_require_path(
+        &self,
+        from: FileId,
+        to: FileId,
+        within: &[FileId],
+    ) -> Option<Vec<FileId>> {
+        let mut previous: HashMap<FileId, FileId> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(file_id) = queue.pop_front() {
+            if file_id == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = previous[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            let Some(deps) = self.dependencies.get(&file_id) else {
+                continue;
+            };
+            let mut deps: Vec<FileId> = deps.iter().copied().collect();
+            deps.sort();
+            for dep in deps {
+                if within.contains(&dep) && dep != from && !previous.contains_key(&dep) {
+                    previous.insert(dep, file_id);
+                    queue.push_back(dep);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Iterative Tarjan's strongly connected components, deep require chains must not overflow the
+/// stack.
+struct Tarjan<'a> {
+    dependencies: &'a HashMap<FileId, HashSet<FileId>>,
+    next_index: usize,
+    indices: HashMap<FileId, usize>,
+    low_links: HashMap<FileId, usize>,
+    stack: Vec<FileId>,
+    on_stack: HashSet<FileId>,
+    components: Vec<Vec<FileId>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, root: FileId) {
+        // each frame is a file and its dependencies left to visit
+        let mut frames: Vec<(FileId, Vec<FileId>)> = vec![(root, self.enter(root))];
+        while let Some((file_id, pending)) = frames.last_mut() {
+            let file_id = *file_id;
+            if let Some(dep) = pending.pop() {
+                if !self.indices.contains_key(&dep) {
+                    let deps = self.enter(dep);
+                    frames.push((dep, deps));
+                } else if self.on_stack.contains(&dep) {
+                    let low = self.low_links[&file_id].min(self.indices[&dep]);
+                    self.low_links.insert(file_id, low);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some((parent, _)) = frames.last() {
+                let low = self.low_links[parent].min(self.low_links[&file_id]);
+                self.low_links.insert(*parent, low);
+            }
+            if self.low_links[&file_id] == self.indices[&file_id] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(&member);
+                    component.push(member);
+                    if member == file_id {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn enter(&mut self, file_id: FileId) -> Vec<FileId> {
+        self.indices.insert(file_id, self.next_index);
+        self.low_links.insert(file_id, self.next_index);
+        self.next_index += 1;
+        self.stack.push(file_id);
+        self.on_stack.insert(file_id);
+        let mut deps: Vec<FileId> = self
+            .dependencies
+            .get(&file_id)
+            .map(|deps| deps.iter().copied().collect())
+            .unwrap_or_default();
+        // popped from the back, visit in ascending order
+        deps.sort_by(|a, b| b.cmp(a));
+        deps
+    }
 }
 
 #[cfg(test)]
@@ -148,4 +288,26 @@ mod tests {
         result.sort();
         assert_eq!(result, vec![FileId::new(1), FileId::new(2), FileId::new(4)]);
     }
+
+    #[test]
+    fn test_require_cycles() {
+        let mut deps: HashMap<FileId, HashSet<FileId>> = HashMap::new();
+        deps.insert(1.into(), [2.into()].into_iter().collect());
+        deps.insert(2.into(), [3.into()].into_iter().collect());
+        deps.insert(3.into(), [1.into(), 4.into()].into_iter().collect());
+        deps.insert(4.into(), HashSet::new());
+        deps.insert(5.into(), [5.into()].into_iter().collect());
+
+        let rel = FileDependencyRelation::new(&deps);
+        let cycles = rel.get_require_cycles();
+        assert_eq!(
+            cycles,
+            vec![vec![1.into(), 2.into(), 3.into()], vec![5.into()]]
+        );
+        assert_eq!(
+            rel.find_require_path(2.into(), 1.into(), &cycles[0]),
+            Some(vec![2.into(), 3.into(), 1.into()])
+        );
+        assert_eq!(rel.find_require_path(1.into(), 4.into(), &cycles[0]), None);
+    }
 }
@@ -1,16 +1,37 @@
+mod affected_files;
 mod file_dependency_relation;
+mod unreachable_files;
 
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex, OnceLock},
+};
 
+pub use affected_files::collect_affected_files;
 use file_dependency_relation::FileDependencyRelation;
+pub use unreachable_files::{collect_unreachable_files, is_unreachable_file};
 
 use crate::FileId;
 
 use super::LuaIndex;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaDependencyIndex {
     dependencies: HashMap<FileId, HashSet<FileId>>,
+    /// Reverse of `dependencies`, the files requiring each file.
+    dependents: HashMap<FileId, HashSet<FileId>>,
+    unresolved_requires: HashMap<FileId, HashSet<String>>,
+    /// The requires of `dependencies` which run when the file is loaded, outside of any function.
+    load_time_dependencies: HashMap<FileId, HashSet<FileId>>,
+    /// Require cycles of `load_time_dependencies`, computed once for all the files checked in a
+    /// pass.
+    #[serde(skip)]
+    require_cycles: OnceLock<Vec<Vec<FileId>>>,
+    /// Files reached from the entry points of the last `get_reachable_files` call, the diagnostics
+    /// of every file in a pass share it. Any change of the requires drops it.
+    #[serde(skip)]
+    reachable_files: Mutex<Option<(Vec<FileId>, Arc<HashSet<FileId>>)>>,
 }
 
 impl Default for LuaDependencyIndex {
@@ -23,31 +44,161 @@ impl LuaDependencyIndex {
     pub fn new() -> Self {
         Self {
             dependencies: HashMap::new(),
+            dependents: HashMap::new(),
+            unresolved_requires: HashMap::new(),
+            load_time_dependencies: HashMap::new(),
+            require_cycles: OnceLock::new(),
+            reachable_files: Mutex::default(),
         }
     }
 
     pub fn add_required_file(&mut self, file_id: FileId, dependency_id: FileId) {
+        self.reachable_files = Mutex::default();
+        self.require_cycles = OnceLock::new();
+        self.dependents
+            .entry(dependency_id)
+            .or_default()
+            .insert(file_id);
         self.dependencies
             .entry(file_id)
             .or_default()
             .insert(dependency_id);
     }
 
+    /// Record a require which runs when the file is loaded, it may close a require cycle. The
+    /// require is also added with `add_required_file`.
+    pub fn add_load_time_required_file(&mut self, file_id: FileId, dependency_id: FileId) {
+        self.require_cycles = OnceLock::new();
+        self.load_time_dependencies
+            .entry(file_id)
+            .or_default()
+            .insert(dependency_id);
+    }
+
+    /// Record a require whose module could not be found, the file depends on any file which later
+    /// provides that module.
+    pub fn add_unresolved_require(&mut self, file_id: FileId, module_path: String) {
+        self.unresolved_requires
+            .entry(file_id)
+            .or_default()
+            .insert(module_path);
+    }
+
+    /// Files with an unresolved require whose last module path part is `module_name`.
+    pub fn get_unresolved_require_files(&self, module_name: &str) -> Vec<FileId> {
+        self.unresolved_requires
+            .iter()
+            .filter(|(_, module_paths)| {
+                module_paths.iter().any(|module_path| {
+                    module_path.rsplit(['.', '/', '\\']).next() == Some(module_name)
+                })
+            })
+            .map(|(file_id, _)| *file_id)
+            .collect()
+    }
+
+    /// Files with an unresolved require of the module at `module_path`, like `foo.bar`. A require
+    /// of a shorter path such as `bar` matches too, fuzzy module search may resolve it there.
+    pub fn get_unresolved_require_files_by_path(&self, module_path: &str) -> Vec<FileId> {
+        self.unresolved_requires
+            .iter()
+            .filter(|(_, required_paths)| {
+                required_paths.iter().any(|required_path| {
+                    let required_path = required_path.replace(['\\', '/'], ".");
+                    module_path == required_path
+                        || module_path
+                            .strip_suffix(required_path.as_str())
+                            .is_some_and(|prefix| prefix.ends_with('.'))
+                })
+            })
+            .map(|(file_id, _)| *file_id)
+            .collect()
+    }
+
     pub fn get_required_files(&self, file_id: &FileId) -> Option<&HashSet<FileId>> {
         self.dependencies.get(file_id)
     }
 
+    /// Files which directly require `file_id`.
+    pub fn get_dependent_files(&self, file_id: &FileId) -> Vec<FileId> {
+        self.dependents
+            .get(file_id)
+            .map(|dependents| dependents.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Files required from `entry_file_ids` transitively, the entry points included.
+    pub fn get_reachable_files(&self, mut entry_file_ids: Vec<FileId>) -> Arc<HashSet<FileId>> {
+        entry_file_ids.sort();
+        let mut cache = self
+            .reachable_files
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        if let Some((cached_entry_file_ids, reachable)) = cache.as_ref()
+            && *cached_entry_file_ids == entry_file_ids
+        {
+            return reachable.clone();
+        }
+
+        let mut reachable: HashSet<FileId> = entry_file_ids.iter().copied().collect();
+        let mut queue: VecDeque<FileId> = entry_file_ids.iter().copied().collect();
+        while let Some(file_id) = queue.pop_front() {
+            if let Some(required_files) = self.dependencies.get(&file_id) {
+                for required in required_files {
+                    if reachable.insert(*required) {
+                        queue.push_back(*required);
+                    }
+                }
+            }
+        }
+
+        let reachable = Arc::new(reachable);
+        *cache = Some((entry_file_ids, reachable.clone()));
+        reachable
+    }
+
     pub fn get_file_dependencies<'a>(&'a self) -> FileDependencyRelation<'a> {
         FileDependencyRelation::new(&self.dependencies)
     }
+
+    /// The requires which run when the files are loaded, a require inside a function only runs
+    /// when the function is called.
+    pub fn get_load_time_dependencies<'a>(&'a self) -> FileDependencyRelation<'a> {
+        FileDependencyRelation::new(&self.load_time_dependencies)
+    }
+
+    /// See `FileDependencyRelation::get_require_cycles`, only load time requires count.
+    pub fn get_require_cycles(&self) -> &[Vec<FileId>] {
+        self.require_cycles
+            .get_or_init(|| self.get_load_time_dependencies().get_require_cycles())
+    }
 }
 
 impl LuaIndex for LuaDependencyIndex {
     fn remove(&mut self, file_id: FileId) {
-        self.dependencies.remove(&file_id);
+        self.reachable_files = Mutex::default();
+        self.require_cycles = OnceLock::new();
+        self.load_time_dependencies.remove(&file_id);
+        // files requiring `file_id` keep their edges, only its own requires go away
+        if let Some(dependencies) = self.dependencies.remove(&file_id) {
+            for dependency_id in dependencies {
+                if let Some(dependents) = self.dependents.get_mut(&dependency_id) {
+                    dependents.remove(&file_id);
+                    if dependents.is_empty() {
+                        self.dependents.remove(&dependency_id);
+                    }
+                }
+            }
+        }
+        self.unresolved_requires.remove(&file_id);
     }
 
     fn clear(&mut self) {
+        self.reachable_files = Mutex::default();
+        self.require_cycles = OnceLock::new();
+        self.load_time_dependencies.clear();
         self.dependencies.clear();
+        self.dependents.clear();
+        self.unresolved_requires.clear();
     }
 }
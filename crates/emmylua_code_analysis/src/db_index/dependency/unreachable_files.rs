@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use crate::{DbIndex, FileId, file_path_to_uri};
+
+/// Files of the main workspace which no entry point of `workspace.entryPoints` reaches through
+/// `require`, transitively. The result is sorted and empty when no entry point is configured or
+/// none of them is loaded.
+///
+/// Meta files only describe definitions and are never reported.
+pub fn collect_unreachable_files(db: &DbIndex) -> Vec<FileId> {
+    let mut unreachable: Vec<FileId> = db
+        .get_module_index()
+        .get_main_workspace_file_ids()
+        .into_iter()
+        .filter(|file_id| is_unreachable_file(db, *file_id))
+        .collect();
+    unreachable.sort();
+    unreachable
+}
+
+/// Whether `collect_unreachable_files` lists the file. The reachable files are computed once and
+/// kept by the dependency index until a require changes, so checking every file stays linear.
+pub fn is_unreachable_file(db: &DbIndex, file_id: FileId) -> bool {
+    let module_index = db.get_module_index();
+    if !module_index.is_main(&file_id) || module_index.is_meta_file(&file_id) {
+        return false;
+    }
+
+    let entry_file_ids = get_entry_point_file_ids(db);
+    if entry_file_ids.is_empty() {
+        return false;
+    }
+    !db.get_file_dependencies_index()
+        .get_reachable_files(entry_file_ids)
+        .contains(&file_id)
+}
+
+fn get_entry_point_file_ids(db: &DbIndex) -> Vec<FileId> {
+    db.get_emmyrc()
+        .workspace
+        .entry_points
+        .iter()
+        .filter_map(|path| {
+            let uri = file_path_to_uri(&PathBuf::from(path))?;
+            db.get_vfs().get_file_id(&uri)
+        })
+        .collect()
+}
@@ -1,6 +1,7 @@
 mod analyze_error;
 mod diagnostic_action;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 pub use analyze_error::AnalyzeError;
@@ -11,7 +12,7 @@ use crate::{DiagnosticCode, FileId};
 
 use super::traits::LuaIndex;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DiagnosticIndex {
     diagnostic_actions: HashMap<FileId, Vec<DiagnosticAction>>,
     diagnostics: HashMap<FileId, Vec<AnalyzeError>>,
@@ -2,6 +2,7 @@ mod flow_node;
 mod flow_tree;
 mod signature_cast;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{FileId, LuaSignatureId};
@@ -12,7 +13,7 @@ pub use signature_cast::LuaSignatureCast;
 
 use super::traits::LuaIndex;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaFlowIndex {
     file_flow_tree: HashMap<FileId, FlowTree>,
     signature_cast_cache: HashMap<FileId, HashMap<LuaSignatureId, LuaSignatureCast>>,
@@ -1,6 +1,7 @@
 use emmylua_parser::{LuaAstPtr, LuaDocOpType};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LuaSignatureCast {
     pub name: String,
     pub cast: LuaAstPtr<LuaDocOpType>,
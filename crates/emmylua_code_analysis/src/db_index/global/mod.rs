@@ -1,5 +1,6 @@
 mod global_id;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 pub use global_id::GlobalId;
@@ -8,7 +9,7 @@ use crate::FileId;
 
 use super::{LuaDeclId, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaGlobalIndex {
     global_decl: HashMap<GlobalId, Vec<LuaDeclId>>,
 }
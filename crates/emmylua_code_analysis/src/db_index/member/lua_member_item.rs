@@ -1,8 +1,9 @@
 use crate::{DbIndex, InferFailReason, LuaSemanticDeclId, LuaType, TypeOps};
+use serde::{Deserialize, Serialize};
 
 use super::LuaMemberId;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LuaMemberIndexItem {
     One(LuaMemberId),
     Many(Vec<LuaMemberId>),
@@ -183,7 +184,7 @@ fn resolve_type_owner_member_id(
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 enum MemberTypeResolveState {
     All,
     Meta,
@@ -254,7 +255,7 @@ fn resolve_member_semantic_id(
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 enum MemberSemanticDeclResolveState {
     MetaOrNone,
     FirstDefine,
@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{LuaMemberIndexItem, LuaMemberKey};
 
 #[allow(unused)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LuaOwnerMembers {
     members: HashMap<LuaMemberKey, LuaMemberIndexItem>,
     resolve_state: OwnerMemberStatus,
@@ -67,7 +68,7 @@ impl LuaOwnerMembers {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OwnerMemberStatus {
     UnResolved,
     Resolved,
@@ -4,6 +4,7 @@ mod lua_member_item;
 mod lua_member_owner;
 mod lua_owner_members;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 use super::traits::LuaIndex;
@@ -13,7 +14,7 @@ pub use lua_member_feature::LuaMemberFeature;
 pub use lua_member_item::LuaMemberIndexItem;
 pub use lua_member_owner::LuaMemberOwner;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaMemberIndex {
     members: HashMap<LuaMemberId, LuaMember>,
     in_filed: HashMap<FileId, HashSet<MemberOrOwner>>,
@@ -21,7 +22,7 @@ pub struct LuaMemberIndex {
     member_current_owner: HashMap<LuaMemberId, LuaMemberOwner>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 enum MemberOrOwner {
     Member(LuaMemberId),
     Owner(LuaMemberOwner),
@@ -228,6 +229,20 @@ impl LuaMemberIndex {
     pub fn get_current_owner(&self, id: &LuaMemberId) -> Option<&LuaMemberOwner> {
         self.member_current_owner.get(id)
     }
+
+    pub fn get_file_members(&self, file_id: &FileId) -> Vec<&LuaMember> {
+        let Some(member_or_owners) = self.in_filed.get(file_id) else {
+            return Vec::new();
+        };
+
+        member_or_owners
+            .iter()
+            .filter_map(|member_or_owner| match member_or_owner {
+                MemberOrOwner::Member(member_id) => self.members.get(member_id),
+                MemberOrOwner::Owner(_) => None,
+            })
+            .collect()
+    }
 }
 
 impl LuaIndex for LuaMemberIndex {
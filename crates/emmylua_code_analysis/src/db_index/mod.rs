@@ -11,6 +11,7 @@ mod property;
 mod reference;
 mod semantic_decl;
 mod signature;
+mod snapshot;
 mod traits;
 mod r#type;
 
@@ -18,7 +19,9 @@ use std::sync::Arc;
 
 use crate::{Emmyrc, FileId, Vfs};
 pub use declaration::*;
-pub use dependency::LuaDependencyIndex;
+pub use dependency::{
+    LuaDependencyIndex, collect_affected_files, collect_unreachable_files, is_unreachable_file,
+};
 pub use diagnostic::{AnalyzeError, DiagnosticAction, DiagnosticActionKind, DiagnosticIndex};
 pub use flow::*;
 pub use global::{GlobalId, LuaGlobalIndex};
@@ -30,6 +33,7 @@ pub use property::*;
 pub use reference::*;
 pub use semantic_decl::*;
 pub use signature::*;
+pub(crate) use snapshot::DbIndexSnapshot;
 pub use traits::LuaIndex;
 pub use r#type::*;
 
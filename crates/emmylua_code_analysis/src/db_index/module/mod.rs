@@ -8,6 +8,7 @@ use log::{error, info};
 pub use module_info::ModuleInfo;
 pub use module_node::{ModuleNode, ModuleNodeId};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 pub use workspace::{Workspace, WorkspaceId};
 
 use super::traits::LuaIndex;
@@ -18,16 +19,21 @@ use std::{
     sync::Arc,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaModuleIndex {
+    // config and workspace derived fields are rebuilt on startup, they are not cached
+    #[serde(skip)]
     module_patterns: Vec<Regex>,
     module_root_id: ModuleNodeId,
     module_nodes: HashMap<ModuleNodeId, ModuleNode>,
     file_module_map: HashMap<FileId, ModuleInfo>,
     module_name_to_file_ids: HashMap<String, Vec<FileId>>,
+    #[serde(skip)]
     workspaces: Vec<Workspace>,
     id_counter: u32,
+    #[serde(skip)]
     fuzzy_search: bool,
+    #[serde(skip)]
     module_replace_vec: Vec<(Regex, String)>,
 }
 
@@ -105,14 +111,21 @@ impl LuaModuleIndex {
             self.remove(file_id);
         }
 
+        let (module_path, workspace_id) = self.get_module_path(path)?;
+        self.add_module_by_module_path(file_id, module_path, workspace_id);
+        Some(workspace_id)
+    }
+
+    /// The dotted module path a file at `path` gets, like `foo` for `foo/init.lua`, whether or
+    /// not the file exists.
+    pub fn get_module_path(&self, path: &str) -> Option<(String, WorkspaceId)> {
         let (module_path, workspace_id) = self.extract_module_path(path)?;
         let mut module_path = module_path.replace(['\\', '/'], ".");
         if !self.module_replace_vec.is_empty() {
             module_path = self.replace_module_path(&module_path);
         }
 
-        self.add_module_by_module_path(file_id, module_path, workspace_id);
-        Some(workspace_id)
+        Some((module_path, workspace_id))
     }
 
     pub fn add_module_by_module_path(
@@ -365,6 +378,10 @@ impl LuaModuleIndex {
         }
     }
 
+    pub fn get_workspaces(&self) -> &[Workspace] {
+        &self.workspaces
+    }
+
     #[allow(unused)]
     pub fn remove_workspace_root(&mut self, root: &Path) {
         self.workspaces.retain(|r| r.root != root);
@@ -416,6 +433,15 @@ impl LuaModuleIndex {
         self.fuzzy_search = !config.strict.require_path;
     }
 
+    /// Replace the module tree with a cached one, keeping the current patterns and workspaces.
+    pub(crate) fn restore_modules(&mut self, cached: LuaModuleIndex) {
+        self.module_root_id = cached.module_root_id;
+        self.module_nodes = cached.module_nodes;
+        self.file_module_map = cached.file_module_map;
+        self.module_name_to_file_ids = cached.module_name_to_file_ids;
+        self.id_counter = cached.id_counter;
+    }
+
     pub fn get_std_file_ids(&self) -> Vec<FileId> {
         let mut file_ids = Vec::new();
         for module_info in self.file_module_map.values() {
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{fmt, path::PathBuf};
 
 #[derive(Debug)]
@@ -12,7 +13,7 @@ impl Workspace {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WorkspaceId {
     pub id: u32,
 }
@@ -1,6 +1,7 @@
 mod lua_operator;
 mod lua_operator_meta_method;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::FileId;
@@ -9,7 +10,7 @@ use super::traits::LuaIndex;
 pub use lua_operator::{LuaOperator, LuaOperatorId, LuaOperatorOwner, OperatorFunction};
 pub use lua_operator_meta_method::LuaOperatorMetaMethod;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaOperatorIndex {
     operators: HashMap<LuaOperatorId, LuaOperator>,
     type_operators_map:
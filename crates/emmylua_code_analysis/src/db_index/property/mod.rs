@@ -2,6 +2,7 @@ mod decl_feature;
 #[allow(clippy::module_inception)]
 mod property;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 pub use decl_feature::{DeclFeatureFlag, PropertyDeclFeature};
@@ -14,7 +15,7 @@ use crate::{DbIndex, FileId, LuaMember, LuaSignatureId};
 
 use super::{LuaSemanticDeclId, traits::LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaPropertyIndex {
     properties: HashMap<LuaPropertyId, LuaCommonProperty>,
     property_owners_map: HashMap<LuaSemanticDeclId, LuaPropertyId>,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use emmylua_parser::{LuaVersionCondition, VisibilityKind};
@@ -7,7 +8,7 @@ use crate::{
     db_index::property::decl_feature::{DeclFeatureFlag, PropertyDeclFeature},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaCommonProperty {
     pub visibility: VisibilityKind,
     pub description: Option<Box<String>>,
@@ -119,19 +120,19 @@ impl LuaCommonProperty {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaDeprecated {
     Deprecated,
     DeprecatedWithMessage(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaExportScope {
     Global,
     Namespace,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaTagContent {
     pub tags: Vec<(String, String)>,
 }
@@ -156,12 +157,12 @@ impl LuaTagContent {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaExport {
     pub scope: LuaExportScope,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Copy, Serialize, Deserialize)]
 pub struct LuaPropertyId {
     id: u32,
 }
@@ -172,7 +173,7 @@ impl LuaPropertyId {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaAttributeUse {
     pub id: LuaTypeDeclId,
     pub args: Vec<(String, Option<LuaType>)>,
@@ -1,9 +1,10 @@
 use rowan::TextRange;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::db_index::LuaDeclId;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FileReference {
     decl_references: HashMap<LuaDeclId, DeclReference>,
     references_to_decl: HashMap<TextRange, LuaDeclId>,
@@ -50,13 +51,13 @@ impl FileReference {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct DeclReferenceCell {
     pub range: TextRange,
     pub is_write: bool,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct DeclReference {
     pub cells: Vec<DeclReferenceCell>,
     pub mutable: bool,
@@ -1,6 +1,8 @@
 mod file_reference;
 mod string_reference;
+mod unused_exports;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 use emmylua_parser::LuaSyntaxId;
@@ -8,17 +10,22 @@ pub use file_reference::{DeclReference, DeclReferenceCell, FileReference};
 use rowan::TextRange;
 use smol_str::SmolStr;
 use string_reference::StringReference;
+pub use unused_exports::{
+    ENTRY_POINT_ATTRIBUTE, UnusedExport, UnusedExportKind, find_unused_exports,
+};
 
 use super::{LuaDeclId, LuaMemberKey, LuaTypeDeclId, traits::LuaIndex};
 use crate::{FileId, InFiled};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaReferenceIndex {
     file_references: HashMap<FileId, FileReference>,
     index_reference: HashMap<LuaMemberKey, HashMap<FileId, HashSet<LuaSyntaxId>>>,
     global_references: HashMap<SmolStr, HashMap<FileId, HashSet<LuaSyntaxId>>>,
     string_references: HashMap<FileId, StringReference>,
     type_references: HashMap<FileId, HashMap<LuaTypeDeclId, HashSet<TextRange>>>,
+    /// Reverse of `type_references`, the files referencing each type.
+    type_reference_files: HashMap<LuaTypeDeclId, HashSet<FileId>>,
 }
 
 impl Default for LuaReferenceIndex {
@@ -35,6 +42,7 @@ impl LuaReferenceIndex {
             global_references: HashMap::new(),
             string_references: HashMap::new(),
             type_references: HashMap::new(),
+            type_reference_files: HashMap::new(),
         }
     }
 
@@ -88,6 +96,10 @@ impl LuaReferenceIndex {
         type_decl_id: LuaTypeDeclId,
         range: TextRange,
     ) {
+        self.type_reference_files
+            .entry(type_decl_id.clone())
+            .or_default()
+            .insert(file_id);
         self.type_references
             .entry(file_id)
             .or_default()
@@ -165,6 +177,13 @@ impl LuaReferenceIndex {
         Some(results)
     }
 
+    pub fn get_global_reference_files(&self, name: &str) -> Vec<FileId> {
+        self.global_references
+            .get(name)
+            .map(|references| references.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
     pub fn get_index_references(&self, key: &LuaMemberKey) -> Option<Vec<InFiled<LuaSyntaxId>>> {
         let results = self
             .index_reference
@@ -210,13 +229,29 @@ impl LuaReferenceIndex {
 
         Some(results)
     }
+
+    pub fn get_type_reference_files(&self, type_decl_id: &LuaTypeDeclId) -> Vec<FileId> {
+        self.type_reference_files
+            .get(type_decl_id)
+            .map(|files| files.iter().copied().collect())
+            .unwrap_or_default()
+    }
 }
 
 impl LuaIndex for LuaReferenceIndex {
     fn remove(&mut self, file_id: FileId) {
         self.file_references.remove(&file_id);
         self.string_references.remove(&file_id);
-        self.type_references.remove(&file_id);
+        if let Some(type_references) = self.type_references.remove(&file_id) {
+            for type_decl_id in type_references.keys() {
+                if let Some(files) = self.type_reference_files.get_mut(type_decl_id) {
+                    files.remove(&file_id);
+                    if files.is_empty() {
+                        self.type_reference_files.remove(type_decl_id);
+                    }
+                }
+            }
+        }
         let mut to_be_remove = Vec::new();
         for (key, references) in self.index_reference.iter_mut() {
             references.remove(&file_id);
@@ -247,5 +282,7 @@ impl LuaIndex for LuaReferenceIndex {
         self.string_references.clear();
         self.index_reference.clear();
         self.global_references.clear();
+        self.type_references.clear();
+        self.type_reference_files.clear();
     }
 }
@@ -0,0 +1,182 @@
+use emmylua_parser::LuaSyntaxId;
+use smol_str::SmolStr;
+
+use crate::{
+    DbIndex, FileId, InFiled, LuaMember, LuaMemberId, LuaMemberKey, LuaMemberOwner,
+    LuaSemanticDeclId, LuaType,
+};
+
+/// Attribute marking a member, a global function or the local holding a module table as
+/// intentionally public, it and everything it owns are never reported as unused.
+pub const ENTRY_POINT_ATTRIBUTE: &str = "entry_point";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnusedExportKind {
+    /// A field of the table or class a module returns
+    ModuleMember,
+    /// A function assigned to a global name
+    GlobalFunction,
+    /// A field or method of a `---@class`
+    ClassField,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedExport {
+    pub kind: UnusedExportKind,
+    pub name: SmolStr,
+    /// Syntax id of the first declaration, the index expression, table field, doc field or
+    /// global name
+    pub syntax_id: LuaSyntaxId,
+}
+
+/// Collect what `file_id` exports and nothing in the workspace reads: the members of the module
+/// it returns, its global functions and the fields of the classes it declares.
+///
+/// Members are matched by name against the index references, so any `x.name` anywhere counts as
+/// a use of every member called `name`. Library, std and meta files are never reported, and
+/// names starting with `__` are skipped because the runtime reads metamethods.
+pub fn find_unused_exports(db: &DbIndex, file_id: FileId) -> Vec<UnusedExport> {
+    let module_index = db.get_module_index();
+    if module_index.is_library(&file_id)
+        || module_index.is_std(&file_id)
+        || module_index.is_meta_file(&file_id)
+    {
+        return Vec::new();
+    }
+
+    let mut unused = Vec::new();
+    let module_owner = get_module_export_owner(db, file_id);
+    for member in db.get_member_index().get_file_members(&file_id) {
+        if let Some(kind) = get_member_export_kind(db, member, module_owner.as_ref())
+            && let Some(name) = get_unused_member_name(db, member)
+        {
+            unused.push(UnusedExport {
+                kind,
+                name,
+                syntax_id: member.get_syntax_id(),
+            });
+        }
+    }
+
+    if let Some(decl_tree) = db.get_decl_index().get_decl_tree(&file_id) {
+        for decl in decl_tree.get_decls().values() {
+            if !decl.is_global()
+                || decl.get_name().starts_with("__")
+                || is_entry_point(db, &LuaSemanticDeclId::LuaDecl(decl.get_id()))
+            {
+                continue;
+            }
+            let is_function = db
+                .get_type_index()
+                .get_type_cache(&decl.get_id().into())
+                .is_some_and(|cache| cache.as_type().is_function());
+            if is_function && !is_global_used(db, decl.get_name()) {
+                unused.push(UnusedExport {
+                    kind: UnusedExportKind::GlobalFunction,
+                    name: decl.get_name().into(),
+                    syntax_id: decl.get_syntax_id(),
+                });
+            }
+        }
+    }
+
+    unused.sort_by_key(|export| export.syntax_id.get_range().start());
+    unused
+}
+
+fn get_module_export_owner(db: &DbIndex, file_id: FileId) -> Option<LuaMemberOwner> {
+    let module_info = db.get_module_index().get_module(file_id)?;
+    if let Some(semantic_id) = &module_info.semantic_id
+        && is_entry_point(db, semantic_id)
+    {
+        return None;
+    }
+
+    match module_info.export_type.as_ref()? {
+        LuaType::TableConst(range) => Some(LuaMemberOwner::Element(range.clone())),
+        LuaType::Def(id) | LuaType::Ref(id) => Some(LuaMemberOwner::Type(id.clone())),
+        _ => None,
+    }
+}
+
+fn get_member_export_kind(
+    db: &DbIndex,
+    member: &LuaMember,
+    module_owner: Option<&LuaMemberOwner>,
+) -> Option<UnusedExportKind> {
+    let owner = db.get_member_index().get_current_owner(&member.get_id())?;
+    if module_owner == Some(owner) {
+        return Some(UnusedExportKind::ModuleMember);
+    }
+
+    let LuaMemberOwner::Type(type_decl_id) = owner else {
+        return None;
+    };
+    let type_decl = db.get_type_index().get_type_decl(type_decl_id)?;
+    type_decl.is_class().then_some(UnusedExportKind::ClassField)
+}
+
+/// The name of `member` when it is its owner's first declaration of the key and the key is only
+/// ever declared, never read.
+fn get_unused_member_name(db: &DbIndex, member: &LuaMember) -> Option<SmolStr> {
+    let LuaMemberKey::Name(name) = member.get_key() else {
+        return None;
+    };
+    if name.starts_with("__") || is_entry_point(db, &LuaSemanticDeclId::Member(member.get_id())) {
+        return None;
+    }
+
+    let member_index = db.get_member_index();
+    let owner = member_index.get_current_owner(&member.get_id())?;
+    let member_ids = member_index
+        .get_member_item(owner, member.get_key())?
+        .get_member_ids();
+    let is_first = member_ids
+        .iter()
+        .filter_map(|id| member_index.get_member(id))
+        .all(|other| other.get_sort_key() >= member.get_sort_key());
+    if !is_first
+        || member_ids
+            .iter()
+            .any(|id| is_entry_point(db, &LuaSemanticDeclId::Member(*id)))
+    {
+        return None;
+    }
+
+    let references = db
+        .get_reference_index()
+        .get_index_references(member.get_key())
+        .unwrap_or_default();
+    let is_used = references.iter().any(|reference| {
+        let member_id = LuaMemberId::new(reference.value, reference.file_id);
+        member_index.get_member(&member_id).is_none()
+    });
+    (!is_used).then(|| name.clone())
+}
+
+fn is_global_used(db: &DbIndex, name: &str) -> bool {
+    let decl_index = db.get_decl_index();
+    let decl_syntax_ids: Vec<InFiled<LuaSyntaxId>> = db
+        .get_global_index()
+        .get_global_decl_ids(name)
+        .map(|decl_ids| {
+            decl_ids
+                .iter()
+                .filter_map(|decl_id| decl_index.get_decl(decl_id))
+                .map(|decl| InFiled::new(decl.get_file_id(), decl.get_syntax_id()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    db.get_reference_index()
+        .get_global_references(name)
+        .unwrap_or_default()
+        .iter()
+        .any(|reference| !decl_syntax_ids.contains(reference))
+}
+
+fn is_entry_point(db: &DbIndex, semantic_id: &LuaSemanticDeclId) -> bool {
+    db.get_property_index()
+        .get_property(semantic_id)
+        .is_some_and(|property| property.find_attribute_use(ENTRY_POINT_ATTRIBUTE).is_some())
+}
@@ -2,6 +2,7 @@ mod async_state;
 #[allow(clippy::module_inception)]
 mod signature;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 pub use async_state::AsyncState;
@@ -14,7 +15,7 @@ use crate::FileId;
 
 use super::traits::LuaIndex;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaSignatureIndex {
     signatures: HashMap<LuaSignatureId, LuaSignature>,
     in_file_signatures: HashMap<FileId, HashSet<LuaSignatureId>>,
@@ -13,7 +13,7 @@ use crate::{
 };
 use crate::{LuaAttributeUse, SemanticModel, VariadicType, first_param_may_not_self};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaSignature {
     pub generic_params: Vec<Arc<LuaGenericParamInfo>>,
     pub overloads: Vec<Arc<LuaFunctionType>>,
@@ -26,7 +26,7 @@ pub struct LuaSignature {
     pub nodiscard: Option<LuaNoDiscard>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaNoDiscard {
     NoDiscard,
     NoDiscardWithMessage(Box<String>),
@@ -174,7 +174,7 @@ impl LuaSignature {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaDocParamInfo {
     pub name: String,
     pub type_ref: LuaType,
@@ -192,7 +192,7 @@ impl LuaDocParamInfo {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LuaDocReturnInfo {
     pub name: Option<String>,
     pub type_ref: LuaType,
@@ -239,11 +239,11 @@ impl<'de> Deserialize<'de> for LuaSignatureId {
                     return Err(E::custom("expected format 'file_id:position'"));
                 }
 
-                let file_id = FileId {
-                    id: parts[0]
+                let file_id = FileId::from_cached(
+                    parts[0]
                         .parse()
                         .map_err(|e| E::custom(format!("invalid file_id: {}", e)))?,
-                };
+                );
                 let position = TextSize::new(
                     parts[1]
                         .parse()
@@ -282,14 +282,14 @@ impl LuaSignatureId {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SignatureReturnStatus {
     UnResolve,
     DocResolve,
     InferResolve,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LuaGenericParamInfo {
     pub name: String,
     pub type_constraint: Option<LuaType>,
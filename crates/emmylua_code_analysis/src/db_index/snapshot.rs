@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    DbIndex, DiagnosticIndex, LuaDeclIndex, LuaDependencyIndex, LuaFlowIndex, LuaGlobalIndex,
+    LuaMemberIndex, LuaMetatableIndex, LuaModuleIndex, LuaOperatorIndex, LuaPropertyIndex,
+    LuaReferenceIndex, LuaSignatureIndex, LuaTypeIndex,
+};
+
+/// Borrowed view of every index in [`DbIndex`] except the vfs, used to write the index cache.
+#[derive(Debug, Serialize)]
+pub(crate) struct DbIndexSnapshotRef<'a> {
+    decl_index: &'a LuaDeclIndex,
+    references_index: &'a LuaReferenceIndex,
+    types_index: &'a LuaTypeIndex,
+    modules_index: &'a LuaModuleIndex,
+    members_index: &'a LuaMemberIndex,
+    property_index: &'a LuaPropertyIndex,
+    signature_index: &'a LuaSignatureIndex,
+    diagnostic_index: &'a DiagnosticIndex,
+    operator_index: &'a LuaOperatorIndex,
+    flow_index: &'a LuaFlowIndex,
+    file_dependencies_index: &'a LuaDependencyIndex,
+    metatable_index: &'a LuaMetatableIndex,
+    global_index: &'a LuaGlobalIndex,
+}
+
+/// Owned counterpart of [`DbIndexSnapshotRef`], read back from the index cache.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DbIndexSnapshot {
+    decl_index: LuaDeclIndex,
+    references_index: LuaReferenceIndex,
+    types_index: LuaTypeIndex,
+    modules_index: LuaModuleIndex,
+    members_index: LuaMemberIndex,
+    property_index: LuaPropertyIndex,
+    signature_index: LuaSignatureIndex,
+    diagnostic_index: DiagnosticIndex,
+    operator_index: LuaOperatorIndex,
+    flow_index: LuaFlowIndex,
+    file_dependencies_index: LuaDependencyIndex,
+    metatable_index: LuaMetatableIndex,
+    global_index: LuaGlobalIndex,
+}
+
+impl DbIndex {
+    pub(crate) fn snapshot(&self) -> DbIndexSnapshotRef<'_> {
+        DbIndexSnapshotRef {
+            decl_index: &self.decl_index,
+            references_index: &self.references_index,
+            types_index: &self.types_index,
+            modules_index: &self.modules_index,
+            members_index: &self.members_index,
+            property_index: &self.property_index,
+            signature_index: &self.signature_index,
+            diagnostic_index: &self.diagnostic_index,
+            operator_index: &self.operator_index,
+            flow_index: &self.flow_index,
+            file_dependencies_index: &self.file_dependencies_index,
+            metatable_index: &self.metatable_index,
+            global_index: &self.global_index,
+        }
+    }
+
+    /// Replace all indexes with the snapshot content. The vfs, the config and the module
+    /// patterns of the current index are kept.
+    pub(crate) fn restore_snapshot(&mut self, snapshot: DbIndexSnapshot) {
+        self.decl_index = snapshot.decl_index;
+        self.references_index = snapshot.references_index;
+        self.types_index = snapshot.types_index;
+        self.modules_index.restore_modules(snapshot.modules_index);
+        self.members_index = snapshot.members_index;
+        self.property_index = snapshot.property_index;
+        self.signature_index = snapshot.signature_index;
+        self.diagnostic_index = snapshot.diagnostic_index;
+        self.operator_index = snapshot.operator_index;
+        self.flow_index = snapshot.flow_index;
+        self.file_dependencies_index = snapshot.file_dependencies_index;
+        self.metatable_index = snapshot.metatable_index;
+        self.global_index = snapshot.global_index;
+    }
+}
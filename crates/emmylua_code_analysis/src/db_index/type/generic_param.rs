@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
 use crate::{LuaAttributeUse, LuaType};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GenericParam {
     pub name: SmolStr,
     pub type_constraint: Option<LuaType>,
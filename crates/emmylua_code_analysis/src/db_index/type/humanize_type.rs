@@ -4,9 +4,9 @@ use itertools::Itertools;
 
 use crate::{
     AsyncState, DbIndex, GenericTpl, LuaAliasCallType, LuaFunctionType, LuaGenericType,
-    LuaInstanceType, LuaIntersectionType, LuaMemberKey, LuaMemberOwner, LuaObjectType,
-    LuaSignatureId, LuaStringTplType, LuaTupleType, LuaType, LuaTypeDeclId, LuaUnionType,
-    TypeSubstitutor, VariadicType,
+    LuaInstanceType, LuaIntersectionType, LuaMappedModifier, LuaMappedType, LuaMemberKey,
+    LuaMemberOwner, LuaObjectType, LuaSignatureId, LuaStringTplType, LuaTemplateLiteralType,
+    LuaTupleType, LuaType, LuaTypeDeclId, LuaUnionType, TypeSubstitutor, VariadicType,
 };
 
 use super::{LuaAliasCallKind, LuaMultiLineUnion};
@@ -67,6 +67,7 @@ pub fn humanize_type(db: &DbIndex, ty: &LuaType, level: RenderLevel) -> String {
         LuaType::Union(union) => humanize_union_type(db, union, level),
         LuaType::Tuple(tuple) => humanize_tuple_type(db, tuple, level),
         LuaType::Unknown => "unknown".to_string(),
+        LuaType::Never => "never".to_string(),
         LuaType::Integer => "integer".to_string(),
         LuaType::Io => "io".to_string(),
         LuaType::SelfInfer => "self".to_string(),
@@ -87,6 +88,7 @@ pub fn humanize_type(db: &DbIndex, ty: &LuaType, level: RenderLevel) -> String {
         LuaType::Call(alias_call) => humanize_call_type(db, alias_call, level),
         LuaType::DocFunction(lua_func) => humanize_doc_function_type(db, lua_func, level),
         LuaType::Object(object) => humanize_object_type(db, object, level),
+        LuaType::Mapped(mapped) => humanize_mapped_type(db, mapped, level),
         LuaType::Intersection(inter) => humanize_intersect_type(db, inter, level),
         LuaType::Generic(generic) => humanize_generic_type(db, generic, level),
         LuaType::TableGeneric(table_generic_params) => {
@@ -94,6 +96,7 @@ pub fn humanize_type(db: &DbIndex, ty: &LuaType, level: RenderLevel) -> String {
         }
         LuaType::TplRef(tpl) => humanize_tpl_ref_type(tpl),
         LuaType::StrTplRef(str_tpl) => humanize_str_tpl_ref_type(str_tpl),
+        LuaType::TemplateLiteral(template) => humanize_template_literal_type(db, template, level),
         LuaType::Variadic(multi) => humanize_variadic_type(db, multi, level),
         LuaType::Instance(ins) => humanize_instance_type(db, ins, level),
         LuaType::Signature(signature_id) => humanize_signature_type(db, signature_id, level),
@@ -354,6 +357,15 @@ fn humanize_call_type(db: &DbIndex, inner: &LuaAliasCallType, level: RenderLevel
         LuaAliasCallKind::Unpack => "unpack",
         LuaAliasCallKind::Index => "index",
         LuaAliasCallKind::RawGet => "rawget",
+        LuaAliasCallKind::Partial => "std.Partial",
+        LuaAliasCallKind::Required => "std.Required",
+        LuaAliasCallKind::Pick => "std.Pick",
+        LuaAliasCallKind::Omit => "std.Omit",
+        LuaAliasCallKind::Readonly => "std.Readonly",
+        LuaAliasCallKind::Record => "std.Record",
+        LuaAliasCallKind::Parameters => "std.Parameters",
+        LuaAliasCallKind::ReturnType => "std.ReturnType",
+        LuaAliasCallKind::Awaited => "std.Awaited",
     };
     let operands = inner
         .get_operands()
@@ -464,6 +476,48 @@ fn humanize_object_type(db: &DbIndex, object: &LuaObjectType, level: RenderLevel
     format!("{{ {}, {}{} }}", fields, access, dots)
 }
 
+fn humanize_mapped_type(db: &DbIndex, mapped: &LuaMappedType, level: RenderLevel) -> String {
+    if level == RenderLevel::Minimal {
+        return "{...}".to_string();
+    }
+
+    let key_str = humanize_type(db, mapped.get_key_type(), level.next_level());
+    let remap_str = match mapped.get_remap_type() {
+        Some(remap_type) => format!(" as {}", humanize_type(db, remap_type, level.next_level())),
+        None => String::new(),
+    };
+    let modifier = match mapped.get_modifier() {
+        LuaMappedModifier::None => "",
+        LuaMappedModifier::Optional => "?",
+        LuaMappedModifier::Required => "-?",
+    };
+    let value_str = humanize_type(db, mapped.get_value_type(), level.next_level());
+    format!(
+        "{{ [{} in {}{}]{}: {} }}",
+        mapped.get_param().get_name(),
+        key_str,
+        remap_str,
+        modifier,
+        value_str
+    )
+}
+
+fn humanize_template_literal_type(
+    db: &DbIndex,
+    template: &LuaTemplateLiteralType,
+    level: RenderLevel,
+) -> String {
+    let body = template
+        .get_parts()
+        .iter()
+        .map(|part| match part {
+            LuaType::DocStringConst(s) => s.to_string(),
+            typ => format!("${{{}}}", humanize_type(db, typ, level.next_level())),
+        })
+        .join("");
+    format!("`{}`", body)
+}
+
 fn humanize_intersect_type(
     db: &DbIndex,
     inter: &LuaIntersectionType,
@@ -11,6 +11,7 @@ use super::traits::LuaIndex;
 use crate::{DbIndex, FileId, InFiled};
 pub use generic_param::GenericParam;
 pub use humanize_type::{RenderLevel, format_union_type, humanize_type};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 pub use type_decl::{LuaDeclLocation, LuaDeclTypeKind, LuaTypeDecl, LuaTypeDeclId, LuaTypeFlag};
 pub use type_ops::TypeOps;
@@ -18,7 +19,7 @@ pub use type_owner::{LuaTypeCache, LuaTypeOwner};
 pub use type_visit_trait::TypeVisitTrait;
 pub use types::*;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaTypeIndex {
     file_namespace: HashMap<FileId, String>,
     file_using_namespace: HashMap<FileId, Vec<String>>,
@@ -26,6 +27,7 @@ pub struct LuaTypeIndex {
     full_name_type_map: HashMap<LuaTypeDeclId, LuaTypeDecl>,
     generic_params: HashMap<LuaTypeDeclId, Vec<GenericParam>>,
     supers: HashMap<LuaTypeDeclId, Vec<InFiled<LuaType>>>,
+    sub_types: HashMap<LuaTypeDeclId, Vec<InFiled<LuaTypeDeclId>>>,
     types: HashMap<LuaTypeOwner, LuaTypeCache>,
     in_filed_type_owner: HashMap<FileId, HashSet<LuaTypeOwner>>,
 }
@@ -45,6 +47,7 @@ impl LuaTypeIndex {
             full_name_type_map: HashMap::new(),
             generic_params: HashMap::new(),
             supers: HashMap::new(),
+            sub_types: HashMap::new(),
             types: HashMap::new(),
             in_filed_type_owner: HashMap::new(),
         }
@@ -80,6 +83,10 @@ impl LuaTypeIndex {
         }
     }
 
+    pub fn get_file_types(&self, file_id: &FileId) -> Option<&Vec<LuaTypeDeclId>> {
+        self.file_types.get(file_id)
+    }
+
     pub fn find_type_decl(&self, file_id: FileId, name: &str) -> Option<&LuaTypeDecl> {
         if let Some(ns) = self.get_file_namespace(&file_id) {
             let full_name = LuaTypeDeclId::new(&format!("{}.{}", ns, name));
@@ -167,6 +174,18 @@ impl LuaTypeIndex {
     }
 
     pub fn add_super_type(&mut self, decl_id: LuaTypeDeclId, file_id: FileId, super_type: LuaType) {
+        let super_decl_id = match &super_type {
+            LuaType::Ref(id) | LuaType::Def(id) => Some(id.clone()),
+            LuaType::Generic(generic) => Some(generic.get_base_type_id()),
+            _ => None,
+        };
+        if let Some(super_decl_id) = super_decl_id {
+            self.sub_types
+                .entry(super_decl_id)
+                .or_default()
+                .push(InFiled::new(file_id, decl_id.clone()));
+        }
+
         self.supers
             .entry(decl_id)
             .or_default()
@@ -188,6 +207,19 @@ impl LuaTypeIndex {
             .map(|supers| supers.iter().map(|s| &s.value))
     }
 
+    /// The types which directly extend `decl_id`, each listed once even when declared in several files.
+    pub fn get_sub_types(&self, decl_id: &LuaTypeDeclId) -> Vec<LuaTypeDeclId> {
+        let mut sub_types: Vec<LuaTypeDeclId> = Vec::new();
+        if let Some(subs) = self.sub_types.get(decl_id) {
+            for sub in subs {
+                if !sub_types.contains(&sub.value) {
+                    sub_types.push(sub.value.clone());
+                }
+            }
+        }
+        sub_types
+    }
+
     pub fn get_type_decl(&self, decl_id: &LuaTypeDeclId) -> Option<&LuaTypeDecl> {
         self.full_name_type_map.get(decl_id)
     }
@@ -254,6 +286,12 @@ impl LuaIndex for LuaTypeIndex {
             }
         }
 
+        // supers of types declared elsewhere may be added from this file, like constructor root classes
+        self.sub_types.retain(|_, subs| {
+            subs.retain(|s| s.file_id != file_id);
+            !subs.is_empty()
+        });
+
         if let Some(type_owners) = self.in_filed_type_owner.remove(&file_id) {
             for type_owner in type_owners {
                 self.types.remove(&type_owner);
@@ -268,6 +306,7 @@ impl LuaIndex for LuaTypeIndex {
         self.full_name_type_map.clear();
         self.generic_params.clear();
         self.supers.clear();
+        self.sub_types.clear();
         self.types.clear();
         self.in_filed_type_owner.clear();
     }
@@ -5,7 +5,7 @@ mod test {
     use crate::db_index::traits::LuaIndex;
     use crate::db_index::r#type::LuaTypeIndex;
     use crate::db_index::{LuaDeclTypeKind, LuaTypeFlag};
-    use crate::{FileId, LuaTypeDecl, LuaTypeDeclId};
+    use crate::{FileId, LuaType, LuaTypeDecl, LuaTypeDeclId};
 
     fn create_type_index() -> LuaTypeIndex {
         LuaTypeIndex::new()
@@ -161,4 +161,22 @@ mod test {
         assert_eq!(decl.get_namespace(), "test".into());
         assert_eq!(decl.get_full_name(), "test.new_type");
     }
+
+    #[test]
+    fn test_sub_types() {
+        let mut index = create_type_index();
+        let file_id = FileId { id: 1 };
+        let file_id2 = FileId { id: 2 };
+        let base = LuaTypeDeclId::new("Base");
+        let derived = LuaTypeDeclId::new("Derived");
+
+        index.add_super_type(derived.clone(), file_id, LuaType::Ref(base.clone()));
+        index.add_super_type(derived.clone(), file_id2, LuaType::Ref(base.clone()));
+        assert_eq!(index.get_sub_types(&base), vec![derived.clone()]);
+
+        index.remove(file_id);
+        assert_eq!(index.get_sub_types(&base), vec![derived.clone()]);
+        index.remove(file_id2);
+        assert!(index.get_sub_types(&base).is_empty());
+    }
 }
@@ -3,10 +3,11 @@ mod test;
 mod union_type;
 
 use crate::DbIndex;
+use serde::{Deserialize, Serialize};
 
 use super::LuaType;
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TypeOps {
     /// Add a type to the source type
     Union,
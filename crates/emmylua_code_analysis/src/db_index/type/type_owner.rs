@@ -1,11 +1,12 @@
 use emmylua_parser::LuaSyntaxId;
 use rowan::TextSize;
+use serde::{Deserialize, Serialize};
 
 use crate::{FileId, InFiled, LuaDeclId, LuaMemberId};
 
 use super::LuaType;
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub enum LuaTypeOwner {
     Decl(LuaDeclId),
     Member(LuaMemberId),
@@ -48,7 +49,7 @@ impl LuaTypeOwner {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LuaTypeCache {
     DocType(LuaType),
     InferType(LuaType),
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
@@ -17,7 +18,7 @@ use crate::{
 
 use super::{TypeOps, type_decl::LuaTypeDeclId};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LuaType {
     Unknown,
     Any,
@@ -65,6 +66,8 @@ pub enum LuaType {
     Language(ArcIntern<SmolStr>),
     ModuleRef(FileId),
     DocAttribute(Arc<LuaAttributeType>),
+    Mapped(Arc<LuaMappedType>),
+    TemplateLiteral(Arc<LuaTemplateLiteralType>),
 }
 
 impl PartialEq for LuaType {
@@ -116,6 +119,8 @@ impl PartialEq for LuaType {
             (LuaType::Language(a), LuaType::Language(b)) => a == b,
             (LuaType::ModuleRef(a), LuaType::ModuleRef(b)) => a == b,
             (LuaType::DocAttribute(a), LuaType::DocAttribute(b)) => a == b,
+            (LuaType::Mapped(a), LuaType::Mapped(b)) => a == b,
+            (LuaType::TemplateLiteral(a), LuaType::TemplateLiteral(b)) => a == b,
             _ => false, // 不同变体之间不相等
         }
     }
@@ -196,6 +201,8 @@ impl Hash for LuaType {
             LuaType::Language(a) => (47, a).hash(state),
             LuaType::ModuleRef(a) => (48, a).hash(state),
             LuaType::DocAttribute(a) => (52, a).hash(state),
+            LuaType::Mapped(a) => (53, a).hash(state),
+            LuaType::TemplateLiteral(a) => (54, a).hash(state),
         }
     }
 }
@@ -414,12 +421,15 @@ impl LuaType {
             LuaType::Variadic(multi) => multi.contain_tpl(),
             LuaType::TableGeneric(params) => params.iter().any(|p| p.contain_tpl()),
             LuaType::Variadic(inner) => inner.contain_tpl(),
-            LuaType::TplRef(_) => true,
+            // the key parameter of a mapped type is bound by the mapped type itself
+            LuaType::TplRef(tpl) => !tpl.get_tpl_id().is_mapped(),
             LuaType::StrTplRef(_) => true,
             LuaType::ConstTplRef(_) => true,
             LuaType::SelfInfer => true,
             LuaType::MultiLineUnion(inner) => inner.contain_tpl(),
             LuaType::TypeGuard(inner) => inner.contain_tpl(),
+            LuaType::Mapped(mapped) => mapped.contain_tpl(),
+            LuaType::TemplateLiteral(template) => template.contain_tpl(),
             _ => false,
         }
     }
@@ -500,12 +510,14 @@ impl TypeVisitTrait for LuaType {
             }
             LuaType::MultiLineUnion(inner) => inner.visit_type(f),
             LuaType::TypeGuard(inner) => inner.visit_type(f),
+            LuaType::Mapped(mapped) => mapped.visit_type(f),
+            LuaType::TemplateLiteral(template) => template.visit_type(f),
             _ => {}
         }
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaTupleType {
     types: Vec<LuaType>,
     pub status: LuaTupleStatus,
@@ -522,7 +534,7 @@ impl TypeVisitTrait for LuaTupleType {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LuaTupleStatus {
     DocResolve,
     InferResolve,
@@ -600,7 +612,7 @@ impl From<LuaTupleType> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaFunctionType {
     async_state: AsyncState,
     is_colon_define: bool,
@@ -715,14 +727,14 @@ impl From<LuaFunctionType> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaIndexAccessKey {
     Integer(i64),
     String(SmolStr),
     Type(LuaType),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaObjectType {
     fields: HashMap<LuaMemberKey, LuaType>,
     index_access: Vec<(LuaType, LuaType)>,
@@ -845,7 +857,7 @@ impl From<LuaObjectType> for LuaType {
         LuaType::Object(t.into())
     }
 }
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub enum LuaUnionType {
     Nullable(LuaType),
     Multi(Vec<LuaType>),
@@ -977,7 +989,7 @@ impl PartialEq for LuaUnionType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaIntersectionType {
     types: Vec<LuaType>,
 }
@@ -1018,7 +1030,7 @@ impl From<LuaIntersectionType> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaAliasCallKind {
     KeyOf,
     Index,
@@ -1028,9 +1040,18 @@ pub enum LuaAliasCallKind {
     Select,
     Unpack,
     RawGet,
+    Partial,
+    Required,
+    Pick,
+    Omit,
+    Readonly,
+    Record,
+    Parameters,
+    ReturnType,
+    Awaited,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaAliasCallType {
     call_kind: LuaAliasCallKind,
     operand: Vec<LuaType>,
@@ -1065,7 +1086,115 @@ impl LuaAliasCallType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+/// `{ [K in keyof T as R]+?: V }`, the fields are produced one key at a time when members are
+/// looked up, so a large key set is never materialized as an object.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LuaMappedType {
+    param: Arc<GenericTpl>,
+    key_type: LuaType,
+    remap_type: Option<LuaType>,
+    value_type: LuaType,
+    modifier: LuaMappedModifier,
+}
+
+/// The `?`, `+?` or `-?` after the keys of a mapped type.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LuaMappedModifier {
+    None,
+    Optional,
+    Required,
+}
+
+impl TypeVisitTrait for LuaMappedType {
+    fn visit_type<F>(&self, f: &mut F)
+    where
+        F: FnMut(&LuaType),
+    {
+        self.key_type.visit_type(f);
+        if let Some(remap_type) = &self.remap_type {
+            remap_type.visit_type(f);
+        }
+        self.value_type.visit_type(f);
+    }
+}
+
+impl LuaMappedType {
+    pub fn new(
+        param: Arc<GenericTpl>,
+        key_type: LuaType,
+        remap_type: Option<LuaType>,
+        value_type: LuaType,
+        modifier: LuaMappedModifier,
+    ) -> Self {
+        Self {
+            param,
+            key_type,
+            remap_type,
+            value_type,
+            modifier,
+        }
+    }
+
+    pub fn get_param(&self) -> &Arc<GenericTpl> {
+        &self.param
+    }
+
+    pub fn get_key_type(&self) -> &LuaType {
+        &self.key_type
+    }
+
+    pub fn get_remap_type(&self) -> Option<&LuaType> {
+        self.remap_type.as_ref()
+    }
+
+    pub fn get_value_type(&self) -> &LuaType {
+        &self.value_type
+    }
+
+    pub fn get_modifier(&self) -> LuaMappedModifier {
+        self.modifier
+    }
+
+    pub fn contain_tpl(&self) -> bool {
+        self.key_type.contain_tpl()
+            || self.remap_type.as_ref().is_some_and(|t| t.contain_tpl())
+            || self.value_type.contain_tpl()
+    }
+}
+
+/// `` `on_${Event}` `` with a placeholder that is not a literal yet, the text between the
+/// placeholders is kept as `DocStringConst` parts.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LuaTemplateLiteralType {
+    parts: Vec<LuaType>,
+}
+
+impl TypeVisitTrait for LuaTemplateLiteralType {
+    fn visit_type<F>(&self, f: &mut F)
+    where
+        F: FnMut(&LuaType),
+    {
+        for part in &self.parts {
+            part.visit_type(f);
+        }
+    }
+}
+
+impl LuaTemplateLiteralType {
+    pub fn new(parts: Vec<LuaType>) -> Self {
+        Self { parts }
+    }
+
+    pub fn get_parts(&self) -> &[LuaType] {
+        &self.parts
+    }
+
+    pub fn contain_tpl(&self) -> bool {
+        self.parts.iter().any(|part| part.contain_tpl())
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaGenericType {
     base: LuaTypeDeclId,
     params: Vec<LuaType>,
@@ -1114,7 +1243,7 @@ impl From<LuaGenericType> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VariadicType {
     Multi(Vec<LuaType>),
     Base(LuaType),
@@ -1259,7 +1388,7 @@ impl From<SmolStr> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaInstanceType {
     base: LuaType,
     range: InFiled<TextRange>,
@@ -1288,10 +1417,12 @@ impl LuaInstanceType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GenericTplId {
     Type(u32),
     Func(u32),
+    /// The key parameter of a mapped type, by nesting depth
+    Mapped(u32),
 }
 
 impl GenericTplId {
@@ -1299,6 +1430,7 @@ impl GenericTplId {
         match self {
             GenericTplId::Type(idx) => *idx as usize,
             GenericTplId::Func(idx) => *idx as usize,
+            GenericTplId::Mapped(idx) => *idx as usize,
         }
     }
 
@@ -1310,15 +1442,20 @@ impl GenericTplId {
         matches!(self, GenericTplId::Type(_))
     }
 
+    pub fn is_mapped(&self) -> bool {
+        matches!(self, GenericTplId::Mapped(_))
+    }
+
     pub fn with_idx(&self, idx: u32) -> Self {
         match self {
             GenericTplId::Type(_) => GenericTplId::Type(idx),
             GenericTplId::Func(_) => GenericTplId::Func(idx),
+            GenericTplId::Mapped(_) => GenericTplId::Mapped(idx),
         }
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericTpl {
     tpl_id: GenericTplId,
     name: ArcIntern<SmolStr>,
@@ -1347,7 +1484,7 @@ impl GenericTpl {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaStringTplType {
     prefix: ArcIntern<String>,
     tpl_id: GenericTplId,
@@ -1382,7 +1519,7 @@ impl LuaStringTplType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaMultiLineUnion {
     unions: Vec<(LuaType, Option<String>)>,
 }
@@ -1421,7 +1558,7 @@ impl LuaMultiLineUnion {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaArrayType {
     base: LuaType,
     len: LuaArrayLen,
@@ -1436,7 +1573,7 @@ impl TypeVisitTrait for LuaArrayType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaArrayLen {
     None,
     Max(i64),
@@ -1467,7 +1604,7 @@ impl LuaArrayType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaAttributeType {
     params: Vec<(String, Option<LuaType>)>,
 }
@@ -247,7 +247,7 @@ fn check_index_expr_preference(
                 name = alias_info.preferred_name
             )
             .to_string(),
-            None,
+            Some(serde_json::Value::String(alias_info.preferred_name.clone())),
         );
     }
 
@@ -1,7 +1,8 @@
+use emmylua_code_style::{LuaCodeStyle, QuoteStyle, StyleDiagnosticKind, check_tree_style};
 use emmylua_codestyle::check_code_style;
 use rowan::TextRange;
 
-use crate::{DiagnosticCode, SemanticModel};
+use crate::{DiagnosticCode, EmmyrcFormatter, SemanticModel, diagnostic::fix::StyleFixData};
 
 use super::{Checker, DiagnosticContext};
 
@@ -11,6 +12,12 @@ impl Checker for CodeStyleCheckChecker {
     const CODES: &[DiagnosticCode] = &[DiagnosticCode::CodeStyleCheck];
 
     fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let format = &semantic_model.get_emmyrc().format;
+        if format.formatter == EmmyrcFormatter::Native {
+            check_native_style(context, semantic_model, &format.get_native_style());
+            return;
+        }
+
         let document = semantic_model.get_document();
         let file_path = document.get_file_path();
         let text = document.get_text();
@@ -35,3 +42,49 @@ impl Checker for CodeStyleCheckChecker {
         }
     }
 }
+
+/// Report where the file differs from the output of the native formatter.
+fn check_native_style(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    style: &LuaCodeStyle,
+) {
+    let Some(tree) = semantic_model
+        .get_db()
+        .get_vfs()
+        .get_syntax_tree(&semantic_model.get_file_id())
+    else {
+        return;
+    };
+    for diagnostic in check_tree_style(tree, style) {
+        let message = match diagnostic.kind {
+            StyleDiagnosticKind::Indentation => t!("Incorrect indentation").to_string(),
+            StyleDiagnosticKind::TrailingWhitespace => t!("Trailing whitespace").to_string(),
+            StyleDiagnosticKind::LineTooLong => t!(
+                "Line is longer than %{max} characters",
+                max = style.max_line_width
+            )
+            .to_string(),
+            StyleDiagnosticKind::BlankLines => t!("Too many blank lines").to_string(),
+            StyleDiagnosticKind::QuoteStyle => match style.quote_style {
+                QuoteStyle::Single => t!("Use single quotes for strings").to_string(),
+                _ => t!("Use double quotes for strings").to_string(),
+            },
+            StyleDiagnosticKind::Format => t!("Code is not formatted").to_string(),
+        };
+        let data = diagnostic.fix.and_then(|fix| {
+            serde_json::to_value(StyleFixData {
+                start: fix.range.start().into(),
+                end: fix.range.end().into(),
+                new_text: fix.new_text,
+            })
+            .ok()
+        });
+        context.add_diagnostic(
+            DiagnosticCode::CodeStyleCheck,
+            diagnostic.range,
+            message,
+            data,
+        );
+    }
+}
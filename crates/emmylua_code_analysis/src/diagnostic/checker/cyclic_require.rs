@@ -0,0 +1,84 @@
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaClosureExpr, LuaExpr, LuaLiteralToken};
+use serde_json::json;
+
+use crate::{DiagnosticCode, FileId, SemanticModel};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct CyclicRequireChecker;
+
+impl Checker for CyclicRequireChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::CyclicRequire];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let file_id = semantic_model.get_file_id();
+        let dependency_index = semantic_model.get_db().get_file_dependencies_index();
+        let Some(cycle) = dependency_index
+            .get_require_cycles()
+            .iter()
+            .find(|cycle| cycle.contains(&file_id))
+        else {
+            return;
+        };
+        let relation = dependency_index.get_load_time_dependencies();
+
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            // a require inside a function runs after the module is loaded, it breaks the cycle
+            if !call_expr.is_require() || call_expr.ancestors::<LuaClosureExpr>().next().is_some() {
+                continue;
+            }
+            let Some(required_file_id) = get_required_file_id(semantic_model, &call_expr) else {
+                continue;
+            };
+            if !cycle.contains(&required_file_id) {
+                continue;
+            }
+            let Some(path) = relation.find_require_path(required_file_id, file_id, cycle) else {
+                continue;
+            };
+
+            let path: Vec<FileId> = std::iter::once(file_id).chain(path).collect();
+            let module_names = path
+                .iter()
+                .map(|id| get_module_name(semantic_model, *id))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            // the file paths of the cycle, for the json output of emmylua_check
+            let file_paths: Vec<String> = path
+                .iter()
+                .filter_map(|id| semantic_model.get_db().get_vfs().get_file_path(id))
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+            context.add_diagnostic(
+                DiagnosticCode::CyclicRequire,
+                call_expr.get_range(),
+                t!("Require cycle: %{path}", path = module_names).to_string(),
+                Some(json!({ "cycle": file_paths })),
+            );
+        }
+    }
+}
+
+fn get_required_file_id(semantic_model: &SemanticModel, call_expr: &LuaCallExpr) -> Option<FileId> {
+    let LuaExpr::LiteralExpr(literal_expr) = call_expr.get_args_list()?.get_args().next()? else {
+        return None;
+    };
+    let LuaLiteralToken::String(string_token) = literal_expr.get_literal()? else {
+        return None;
+    };
+    let module_info = semantic_model
+        .get_db()
+        .get_module_index()
+        .find_module(&string_token.get_value())?;
+    Some(module_info.file_id)
+}
+
+fn get_module_name(semantic_model: &SemanticModel, file_id: FileId) -> String {
+    semantic_model
+        .get_db()
+        .get_module_index()
+        .get_module(file_id)
+        .map(|module_info| module_info.full_module_name.clone())
+        .unwrap_or_default()
+}
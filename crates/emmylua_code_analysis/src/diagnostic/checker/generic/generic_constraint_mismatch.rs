@@ -230,6 +230,7 @@ fn get_extend_type(
                 _ => None,
             }
         }
+        GenericTplId::Mapped(_) => None,
     }
 }
 
@@ -440,6 +441,7 @@ fn try_instantiate_arg_type(
                     }
                     None
                 }
+                GenericTplId::Mapped(_) => None,
             }
         }
         LuaType::Union(union_type) => {
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use emmylua_parser::{
     LuaAstNode, LuaDocAttributeType, LuaDocBinaryType, LuaDocDescriptionOwner, LuaDocFuncType,
     LuaDocGenericType, LuaDocMultiLineUnionType, LuaDocObjectFieldKey, LuaDocObjectType,
-    LuaDocStrTplType, LuaDocType, LuaDocUnaryType, LuaDocVariadicType, LuaLiteralToken,
-    LuaSyntaxKind, LuaTypeBinaryOperator, LuaTypeUnaryOperator,
+    LuaDocStrTplPart, LuaDocStrTplType, LuaDocType, LuaDocUnaryType, LuaDocVariadicType,
+    LuaLiteralToken, LuaSyntaxKind, LuaTypeBinaryOperator, LuaTypeUnaryOperator,
 };
 use rowan::TextRange;
 use smol_str::SmolStr;
@@ -12,8 +12,9 @@ use smol_str::SmolStr;
 use crate::{
     AsyncState, InFiled, LuaAliasCallKind, LuaAliasCallType, LuaArrayLen, LuaArrayType,
     LuaAttributeType, LuaFunctionType, LuaGenericType, LuaIndexAccessKey, LuaIntersectionType,
-    LuaMultiLineUnion, LuaObjectType, LuaStringTplType, LuaTupleStatus, LuaTupleType, LuaType,
-    LuaTypeDeclId, SemanticModel, TypeOps, VariadicType,
+    LuaMultiLineUnion, LuaObjectType, LuaStringTplType, LuaTemplateLiteralType, LuaTupleStatus,
+    LuaTupleType, LuaType, LuaTypeDeclId, SemanticModel, TypeOps, VariadicType,
+    evaluate_alias_call, evaluate_template_literal,
 };
 
 pub fn infer_doc_type(semantic_model: &SemanticModel, node: &LuaDocType) -> LuaType {
@@ -125,6 +126,7 @@ fn infer_buildin_or_ref_type(
     let _position = range.start();
     match name {
         "unknown" => LuaType::Unknown,
+        "never" => LuaType::Never,
         "nil" | "void" => LuaType::Nil,
         "any" => LuaType::Any,
         "userdata" => LuaType::Userdata,
@@ -272,6 +274,44 @@ fn infer_special_generic_type(
                 LuaAliasCallType::new(LuaAliasCallKind::RawGet, params).into(),
             ));
         }
+        "std.Partial" | "std.Required" | "std.Pick" | "std.Omit" | "std.Readonly"
+        | "std.Record" => {
+            let call_kind = match name {
+                "std.Partial" => LuaAliasCallKind::Partial,
+                "std.Required" => LuaAliasCallKind::Required,
+                "std.Pick" => LuaAliasCallKind::Pick,
+                "std.Omit" => LuaAliasCallKind::Omit,
+                "std.Readonly" => LuaAliasCallKind::Readonly,
+                _ => LuaAliasCallKind::Record,
+            };
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                let param_type = infer_doc_type(semantic_model, &param);
+                params.push(param_type);
+            }
+            return Some(LuaType::Call(
+                LuaAliasCallType::new(call_kind, params).into(),
+            ));
+        }
+        "std.Parameters" | "std.ReturnType" | "std.Awaited" => {
+            let call_kind = match name {
+                "std.Parameters" => LuaAliasCallKind::Parameters,
+                "std.ReturnType" => LuaAliasCallKind::ReturnType,
+                _ => LuaAliasCallKind::Awaited,
+            };
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                let param_type = infer_doc_type(semantic_model, &param);
+                params.push(param_type);
+            }
+            let alias_call = LuaAliasCallType::new(call_kind, params);
+            if let [LuaType::DocFunction(_)] = alias_call.get_operands().as_slice()
+                && let Some(typ) = evaluate_alias_call(semantic_model.get_db(), &alias_call)
+            {
+                return Some(typ);
+            }
+            return Some(LuaType::Call(alias_call.into()));
+        }
         "TypeGuard" => {
             let first_doc_param_type = generic_type.get_generic_types()?.get_types().next()?;
             let first_param = infer_doc_type(semantic_model, &first_doc_param_type);
@@ -507,6 +547,22 @@ fn infer_str_tpl(
     str_tpl: &LuaDocStrTplType,
     node: &LuaDocType,
 ) -> LuaType {
+    if let Some(parts) = str_tpl.get_template_parts() {
+        let mut types = Vec::new();
+        for part in parts {
+            let typ = match part {
+                LuaDocStrTplPart::Text(text) => LuaType::DocStringConst(SmolStr::new(text).into()),
+                LuaDocStrTplPart::Type(name) => {
+                    infer_buildin_or_ref_type(semantic_model, &name, str_tpl.get_range(), node)
+                }
+            };
+            types.push(typ);
+        }
+        let template = LuaTemplateLiteralType::new(types);
+        return evaluate_template_literal(semantic_model.get_db(), &template)
+            .unwrap_or_else(|| LuaType::TemplateLiteral(template.into()));
+    }
+
     let (prefix, tpl_name, suffix) = str_tpl.get_name();
     if let Some(tpl) = tpl_name {
         let typ = infer_buildin_or_ref_type(semantic_model, &tpl, str_tpl.get_range(), node);
@@ -0,0 +1,154 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaNameExpr};
+use rowan::TextRange;
+
+use crate::{
+    DbIndex, DiagnosticCode, FileId, LuaType, SemanticModel,
+    diagnostic::lua_diagnostic_config::LuaLayerRule,
+};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct LayerViolationChecker;
+
+impl Checker for LayerViolationChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::LayerViolation];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let config = context.config.clone();
+        let db = semantic_model.get_db();
+        let paths = get_workspace_relative_paths(db, semantic_model.get_file_id());
+        let rules: Vec<&LuaLayerRule> = config
+            .layer_rules
+            .iter()
+            .filter(|rule| paths.iter().any(|path| rule.is_from(path)))
+            .collect();
+        if rules.is_empty() {
+            return;
+        }
+
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            if call_expr.is_require() {
+                check_require_call_expr(context, semantic_model, &rules, call_expr);
+            }
+        }
+
+        let local_ref_ranges = get_local_ref_ranges(semantic_model);
+        for name_expr in root.descendants::<LuaNameExpr>() {
+            if !local_ref_ranges.contains(&name_expr.get_range()) {
+                check_global_name_expr(context, semantic_model, &rules, name_expr);
+            }
+        }
+    }
+}
+
+fn check_require_call_expr(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    rules: &[&LuaLayerRule],
+    call_expr: LuaCallExpr,
+) -> Option<()> {
+    let arg_expr = call_expr.get_args_list()?.get_args().next()?;
+    let module_path = match semantic_model.infer_expr(arg_expr.clone()).ok()? {
+        LuaType::StringConst(s) => s.as_ref().to_string(),
+        _ => return None,
+    };
+    let module_info = semantic_model
+        .get_db()
+        .get_module_index()
+        .find_module(&module_path)?;
+
+    let (from, deny) = find_denied_layer(semantic_model.get_db(), rules, module_info.file_id)?;
+    context.add_diagnostic(
+        DiagnosticCode::LayerViolation,
+        arg_expr.get_range(),
+        t!(
+            "Module '%{module}' is in layer '%{deny}', which '%{from}' must not require",
+            module = module_info.full_module_name,
+            deny = deny,
+            from = from
+        )
+        .to_string(),
+        None,
+    );
+    Some(())
+}
+
+/// A global is reported when every file defining it lies in a denied layer.
+fn check_global_name_expr(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    rules: &[&LuaLayerRule],
+    name_expr: LuaNameExpr,
+) -> Option<()> {
+    let name = name_expr.get_name_text()?;
+    let db = semantic_model.get_db();
+    let decl_ids = db.get_global_index().get_global_decl_ids(&name)?;
+    let mut denied = None;
+    for decl_id in decl_ids {
+        denied = Some(find_denied_layer(db, rules, decl_id.file_id)?);
+    }
+
+    let (from, deny) = denied?;
+    context.add_diagnostic(
+        DiagnosticCode::LayerViolation,
+        name_expr.get_range(),
+        t!(
+            "Global '%{name}' is only defined in layer '%{deny}', which '%{from}' must not use",
+            name = name,
+            deny = deny,
+            from = from
+        )
+        .to_string(),
+        None,
+    );
+    Some(())
+}
+
+/// The `from` and `deny` globs of the first rule forbidding a dependency on `file_id`.
+fn find_denied_layer(
+    db: &DbIndex,
+    rules: &[&LuaLayerRule],
+    file_id: FileId,
+) -> Option<(String, String)> {
+    let paths = get_workspace_relative_paths(db, file_id);
+    rules.iter().find_map(|rule| {
+        paths
+            .iter()
+            .find_map(|path| rule.find_deny(path))
+            .map(|deny| (rule.from.clone(), deny.to_string()))
+    })
+}
+
+/// The path of the file relative to each main workspace root containing it.
+fn get_workspace_relative_paths(db: &DbIndex, file_id: FileId) -> Vec<PathBuf> {
+    let Some(path) = db.get_vfs().get_file_path(&file_id) else {
+        return Vec::new();
+    };
+    db.get_module_index()
+        .get_workspaces()
+        .iter()
+        .filter(|workspace| workspace.id.is_main())
+        .filter_map(|workspace| path.strip_prefix(&workspace.root).ok())
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn get_local_ref_ranges(semantic_model: &SemanticModel) -> HashSet<TextRange> {
+    let file_id = semantic_model.get_file_id();
+    let mut ranges = HashSet::new();
+    if let Some(refs_index) = semantic_model
+        .get_db()
+        .get_reference_index()
+        .get_local_reference(&file_id)
+    {
+        for decl_refs in refs_index.get_decl_references_map().values() {
+            for decl_ref in &decl_refs.cells {
+                ranges.insert(decl_ref.range);
+            }
+        }
+    }
+    ranges
+}
@@ -2,7 +2,10 @@ use std::collections::{HashMap, HashSet};
 
 use emmylua_parser::{LuaAstNode, LuaTableExpr};
 
-use crate::{DiagnosticCode, LuaMemberOwner, LuaType, LuaTypeCache, LuaTypeDeclId, SemanticModel};
+use crate::{
+    DiagnosticCode, LuaMemberOwner, LuaType, LuaTypeCache, LuaTypeDeclId, SemanticModel,
+    evaluate_alias_call,
+};
 
 use super::{Checker, DiagnosticContext, humanize_lint_type};
 use itertools::Itertools;
@@ -38,7 +41,8 @@ fn check_table_expr(
                     LuaType::Ref(_)
                     | LuaType::Object(_)
                     | LuaType::Generic(_)
-                    | LuaType::Intersection(_) => {
+                    | LuaType::Intersection(_)
+                    | LuaType::Call(_) => {
                         set.insert(ty.clone());
                     }
                     LuaType::Table | LuaType::Userdata => {
@@ -98,6 +102,11 @@ fn check_table_expr(
         LuaType::Object(_) => type_cache.entry(table_type.clone()).or_insert_with(|| {
             get_required_fields(context, &vec![table_type.clone()]).unwrap_or_default()
         }),
+        // std.Partial<T> and friends evaluate to an object type
+        LuaType::Call(alias_call) => type_cache.entry(table_type.clone()).or_insert_with(|| {
+            let types = evaluate_alias_call(db, alias_call).into_iter().collect();
+            get_required_fields(context, &types).unwrap_or_default()
+        }),
         LuaType::Intersection(intersections) => {
             type_cache.entry(table_type.clone()).or_insert_with(|| {
                 let mut computed_fields = HashSet::new();
@@ -10,6 +10,7 @@ mod check_return_count;
 mod circle_doc_class;
 mod code_style;
 mod code_style_check;
+mod cyclic_require;
 mod deprecated;
 mod discard_returns;
 mod duplicate_field;
@@ -20,6 +21,7 @@ mod enum_value_mismatch;
 mod generic;
 mod global_non_module;
 mod incomplete_signature_doc;
+mod layer_violation;
 mod local_const_reassign;
 mod missing_fields;
 mod need_check_nil;
@@ -32,10 +34,13 @@ mod syntax_error;
 mod unbalanced_assignments;
 mod undefined_doc_param;
 mod undefined_global;
+mod unhandled_variant;
 mod unknown_doc_tag;
 mod unnecessary_assert;
 mod unnecessary_if;
+mod unreachable_file;
 mod unused;
+mod unused_export;
 
 use emmylua_parser::{
     LuaAstNode, LuaClosureExpr, LuaComment, LuaReturnStat, LuaStat, LuaSyntaxKind,
@@ -78,6 +83,8 @@ pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticMode
     run_check::<syntax_error::SyntaxErrorChecker>(context, semantic_model);
     run_check::<analyze_error::AnalyzeErrorChecker>(context, semantic_model);
     run_check::<unused::UnusedChecker>(context, semantic_model);
+    run_check::<unused_export::UnusedExportChecker>(context, semantic_model);
+    run_check::<unreachable_file::UnreachableFileChecker>(context, semantic_model);
     run_check::<deprecated::DeprecatedChecker>(context, semantic_model);
     run_check::<undefined_global::UndefinedGlobalChecker>(context, semantic_model);
     run_check::<unnecessary_assert::UnnecessaryAssertChecker>(context, semantic_model);
@@ -98,6 +105,7 @@ pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticMode
     run_check::<incomplete_signature_doc::IncompleteSignatureDocChecker>(context, semantic_model);
     run_check::<assign_type_mismatch::AssignTypeMismatchChecker>(context, semantic_model);
     run_check::<duplicate_require::DuplicateRequireChecker>(context, semantic_model);
+    run_check::<cyclic_require::CyclicRequireChecker>(context, semantic_model);
     run_check::<duplicate_type::DuplicateTypeChecker>(context, semantic_model);
     run_check::<check_return_count::CheckReturnCount>(context, semantic_model);
     run_check::<unbalanced_assignments::UnbalancedAssignmentsChecker>(context, semantic_model);
@@ -110,6 +118,8 @@ pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticMode
     );
     run_check::<cast_type_mismatch::CastTypeMismatchChecker>(context, semantic_model);
     run_check::<require_module_visibility::RequireModuleVisibilityChecker>(context, semantic_model);
+    run_check::<layer_violation::LayerViolationChecker>(context, semantic_model);
+    run_check::<unhandled_variant::UnhandledVariantChecker>(context, semantic_model);
     run_check::<unknown_doc_tag::UnknownDocTag>(context, semantic_model);
     run_check::<enum_value_mismatch::EnumValueMismatchChecker>(context, semantic_model);
     run_check::<attribute_check::AttributeCheckChecker>(context, semantic_model);
@@ -206,9 +216,9 @@ impl<'a> DiagnosticContext<'a> {
 
     fn get_tags(&self, code: DiagnosticCode) -> Option<Vec<DiagnosticTag>> {
         match code {
-            DiagnosticCode::Unused | DiagnosticCode::UnreachableCode => {
-                Some(vec![DiagnosticTag::UNNECESSARY])
-            }
+            DiagnosticCode::Unused
+            | DiagnosticCode::UnusedExport
+            | DiagnosticCode::UnreachableCode => Some(vec![DiagnosticTag::UNNECESSARY]),
             DiagnosticCode::Deprecated => Some(vec![DiagnosticTag::DEPRECATED]),
             _ => None,
         }
@@ -76,6 +76,14 @@ fn check_call_expr(
         }
 
         if let Some(param_type) = param.1.clone() {
+            // `never` params are exhaustiveness checks, reported by `unhandled-variant` when it is
+            // enabled
+            if param_type.is_never()
+                && context.is_checker_enable_by_code(&DiagnosticCode::UnhandledVariant)
+            {
+                continue;
+            }
+
             let arg_type = arg_types.get(idx).unwrap_or(&LuaType::Any);
             let mut check_type = param_type.clone();
             // 对于第一个参数, 他有可能是`:`调用, 所以需要特殊处理
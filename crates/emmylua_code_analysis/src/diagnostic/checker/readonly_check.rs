@@ -2,8 +2,9 @@ use emmylua_parser::{LuaAssignStat, LuaAst, LuaAstNode, LuaExpr, LuaSyntaxId, Lu
 use rowan::{NodeOrToken, TextRange};
 
 use crate::{
-    DiagnosticCode, LuaDeclId, LuaMemberId, LuaSemanticDeclId, PropertyDeclFeature,
-    SemanticDeclLevel, SemanticModel,
+    DbIndex, DiagnosticCode, GenericTplId, LuaAliasCallKind, LuaDeclId, LuaGenericType,
+    LuaMemberId, LuaSemanticDeclId, LuaType, PropertyDeclFeature, SemanticDeclLevel, SemanticModel,
+    TypeSubstitutor, instantiate_type_generic,
 };
 
 use super::{Checker, DiagnosticContext};
@@ -79,6 +80,21 @@ fn check_assign_stat(
     let (vars, _) = assign_stat.get_var_and_expr_list();
     for var in vars {
         let mut var = LuaExpr::cast(var.syntax().clone())?;
+        // the fields of a `std.Readonly<T>` cannot be assigned to
+        if let LuaExpr::IndexExpr(index_expr) = &var
+            && let Some(prefix_expr) = index_expr.get_prefix_expr()
+            && let Ok(prefix_type) = semantic_model.infer_expr(prefix_expr)
+            && is_readonly_type(semantic_model.get_db(), &prefix_type)
+        {
+            context.add_diagnostic(
+                DiagnosticCode::ReadOnly,
+                var.get_range(),
+                t!("The variable is marked as readonly and cannot be assigned to.").to_string(),
+                None,
+            );
+            continue;
+        }
+
         loop {
             let node_or_token = NodeOrToken::Node(var.syntax().clone());
             let semantic_decl_id =
@@ -99,3 +115,61 @@ fn check_assign_stat(
 
     Some(())
 }
+
+/// `std.Readonly<T>`, written directly or through aliases like `---@alias Frozen<T> std.Readonly<T>`.
+fn is_readonly_type(db: &DbIndex, typ: &LuaType) -> bool {
+    is_readonly_alias(db, typ, 0)
+}
+
+fn is_readonly_alias(db: &DbIndex, typ: &LuaType, depth: usize) -> bool {
+    if depth > 10 {
+        return false;
+    }
+
+    match typ {
+        LuaType::Call(alias_call) => alias_call.get_call_kind() == LuaAliasCallKind::Readonly,
+        LuaType::Ref(type_decl_id) => db
+            .get_type_index()
+            .get_type_decl(type_decl_id)
+            .and_then(|type_decl| type_decl.get_alias_ref())
+            .is_some_and(|origin| is_readonly_alias(db, origin, depth + 1)),
+        LuaType::Generic(generic) => {
+            let base_id = generic.get_base_type_id();
+            let Some(origin) = db
+                .get_type_index()
+                .get_type_decl(&base_id)
+                .and_then(|type_decl| type_decl.get_alias_ref())
+            else {
+                return false;
+            };
+
+            // instantiating the origin would evaluate `std.Readonly<T>` to an object, only the
+            // params are substituted
+            let params = generic.get_params();
+            let origin = match origin {
+                LuaType::TplRef(tpl) => match tpl.get_tpl_id() {
+                    GenericTplId::Type(idx) => match params.get(idx as usize) {
+                        Some(param) => param.clone(),
+                        None => return false,
+                    },
+                    _ => return false,
+                },
+                LuaType::Generic(origin_generic) => {
+                    let substitutor = TypeSubstitutor::from_alias(params.clone(), base_id);
+                    let origin_params = origin_generic
+                        .get_params()
+                        .iter()
+                        .map(|param| instantiate_type_generic(db, param, &substitutor))
+                        .collect();
+                    LuaType::Generic(
+                        LuaGenericType::new(origin_generic.get_base_type_id(), origin_params)
+                            .into(),
+                    )
+                }
+                origin => origin.clone(),
+            };
+            is_readonly_alias(db, &origin, depth + 1)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,75 @@
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaExpr};
+
+use crate::{DiagnosticCode, LuaType, RenderLevel, SemanticModel, humanize_type};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct UnhandledVariantChecker;
+
+impl Checker for UnhandledVariantChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::UnhandledVariant];
+
+    /// `---@param value never` marks an exhaustiveness check, anything narrowed to less than
+    /// `never` before reaching it is a variant the branches above forgot.
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            check_call_expr(context, semantic_model, call_expr);
+        }
+    }
+}
+
+fn check_call_expr(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    call_expr: LuaCallExpr,
+) -> Option<()> {
+    let func = semantic_model.infer_call_expr_func(call_expr.clone(), None)?;
+    let params = func.get_params();
+    if !params
+        .iter()
+        .any(|(_, typ)| matches!(typ, Some(LuaType::Never)))
+    {
+        return Some(());
+    }
+
+    // the index of the param an arg is passed to, the implicit `self` shifts it by one
+    let param_offset: isize = match (call_expr.is_colon_call(), func.is_colon_define()) {
+        (true, false) => 1,
+        (false, true) => -1,
+        _ => 0,
+    };
+    for (idx, arg_expr) in call_expr.get_args_list()?.get_args().enumerate() {
+        let Ok(param_idx) = usize::try_from(idx as isize + param_offset) else {
+            continue;
+        };
+        if let Some((_, Some(LuaType::Never))) = params.get(param_idx) {
+            check_never_arg(context, semantic_model, arg_expr);
+        }
+    }
+
+    Some(())
+}
+
+fn check_never_arg(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    arg_expr: LuaExpr,
+) -> Option<()> {
+    let arg_type = semantic_model.infer_expr(arg_expr.clone()).ok()?;
+    if arg_type.is_never() || arg_type.is_unknown() || arg_type.is_any() {
+        return Some(());
+    }
+
+    context.add_diagnostic(
+        DiagnosticCode::UnhandledVariant,
+        arg_expr.get_range(),
+        t!(
+            "Unhandled variant: '%{typ}' can still reach this `never` parameter",
+            typ = humanize_type(semantic_model.get_db(), &arg_type, RenderLevel::Simple)
+        )
+        .to_string(),
+        None,
+    );
+    Some(())
+}
@@ -1,6 +1,7 @@
-use emmylua_parser::{LuaAstNode, LuaExpr, LuaIfStat};
+use emmylua_parser::{BinaryOperator, LuaAstNode, LuaExpr, LuaIfStat};
+use rowan::NodeOrToken;
 
-use crate::{DiagnosticCode, SemanticModel};
+use crate::{DiagnosticCode, LuaSemanticDeclId, SemanticDeclLevel, SemanticModel};
 
 use super::{Checker, DiagnosticContext};
 
@@ -30,6 +31,14 @@ fn check_condition(
     condition: LuaExpr,
 ) -> Option<()> {
     let expr_type = semantic_model.infer_expr(condition.clone()).ok()?;
+    if !expr_type.is_always_truthy() && !expr_type.is_always_falsy() {
+        return Some(());
+    }
+    // the last branches of `if s.kind == "circle" ... elseif s.kind == "square"` are explicit on
+    // purpose, they are only decided by the branches above
+    if is_narrowed_discriminant(semantic_model, &condition).unwrap_or(false) {
+        return Some(());
+    }
 
     if expr_type.is_always_truthy() {
         context.add_diagnostic(
@@ -48,3 +57,41 @@ fn check_condition(
     }
     Some(())
 }
+
+/// A field compared with `==` or `~=`, whose owner is narrowed from its declared type.
+fn is_narrowed_discriminant(semantic_model: &SemanticModel, condition: &LuaExpr) -> Option<bool> {
+    let binary_expr = match condition {
+        LuaExpr::BinaryExpr(binary_expr) => binary_expr.clone(),
+        LuaExpr::ParenExpr(paren_expr) => {
+            return is_narrowed_discriminant(semantic_model, &paren_expr.get_expr()?);
+        }
+        _ => return Some(false),
+    };
+    let op = binary_expr.get_op_token()?.get_op();
+    if !matches!(op, BinaryOperator::OpEq | BinaryOperator::OpNe) {
+        return Some(false);
+    }
+
+    let (left_expr, right_expr) = binary_expr.get_exprs()?;
+    let Some(index_expr) = [left_expr, right_expr]
+        .into_iter()
+        .find_map(|expr| match expr {
+            LuaExpr::IndexExpr(index_expr) => Some(index_expr),
+            _ => None,
+        })
+    else {
+        return Some(false);
+    };
+    let prefix_expr = index_expr.get_prefix_expr()?;
+    let type_owner = match semantic_model.find_decl(
+        NodeOrToken::Node(prefix_expr.syntax().clone()),
+        SemanticDeclLevel::default(),
+    )? {
+        LuaSemanticDeclId::LuaDecl(decl_id) => decl_id.into(),
+        LuaSemanticDeclId::Member(member_id) => member_id.into(),
+        _ => return Some(false),
+    };
+    let declared_type = semantic_model.get_type(type_owner);
+    let narrowed_type = semantic_model.infer_expr(prefix_expr).ok()?;
+    Some(declared_type != narrowed_type)
+}
@@ -0,0 +1,47 @@
+use emmylua_parser::{LuaAstNode, LuaTokenKind};
+use rowan::{TextRange, TextSize};
+
+use crate::{DiagnosticCode, SemanticModel, is_unreachable_file};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct UnreachableFileChecker;
+
+impl Checker for UnreachableFileChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::UnreachableFile];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        if !is_unreachable_file(semantic_model.get_db(), semantic_model.get_file_id()) {
+            return;
+        }
+
+        context.add_diagnostic(
+            DiagnosticCode::UnreachableFile,
+            get_report_range(semantic_model),
+            t!("This file is never required from the entry points").to_string(),
+            None,
+        );
+    }
+}
+
+/// The first line, the whole file would hide the other diagnostics. A file starting with a blank
+/// line is reported on its first token instead.
+fn get_report_range(semantic_model: &SemanticModel) -> TextRange {
+    let root = semantic_model.get_root().syntax();
+    let text = root.text().to_string();
+    let first_line = text.lines().next().unwrap_or_default();
+    if !first_line.trim().is_empty() {
+        return TextRange::up_to(TextSize::from(first_line.len() as u32));
+    }
+
+    root.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| {
+            !matches!(
+                token.kind().to_token(),
+                LuaTokenKind::TkWhitespace | LuaTokenKind::TkEndOfLine
+            )
+        })
+        .map(|token| token.text_range())
+        .unwrap_or_default()
+}
@@ -0,0 +1,51 @@
+use emmylua_parser::{LuaAst, LuaAstNode, LuaSyntaxNode};
+use rowan::TextRange;
+
+use crate::{DiagnosticCode, SemanticModel, UnusedExport, UnusedExportKind, find_unused_exports};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct UnusedExportChecker;
+
+impl Checker for UnusedExportChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::UnusedExport];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let file_id = semantic_model.get_file_id();
+        let root = semantic_model.get_root().syntax().clone();
+        for export in find_unused_exports(semantic_model.get_db(), file_id) {
+            let range = get_name_range(&root, &export).unwrap_or(export.syntax_id.get_range());
+            let name = export.name.as_str();
+            let message = match export.kind {
+                UnusedExportKind::ModuleMember => t!(
+                    "Module member '%{name}' is never used in the workspace",
+                    name = name
+                ),
+                UnusedExportKind::GlobalFunction => t!(
+                    "Global function '%{name}' is never used in the workspace",
+                    name = name
+                ),
+                UnusedExportKind::ClassField => t!(
+                    "Class field '%{name}' is never used in the workspace",
+                    name = name
+                ),
+            };
+            context.add_diagnostic(
+                DiagnosticCode::UnusedExport,
+                range,
+                message.to_string(),
+                None,
+            );
+        }
+    }
+}
+
+fn get_name_range(root: &LuaSyntaxNode, export: &UnusedExport) -> Option<TextRange> {
+    let node = export.syntax_id.to_node_from_root(root)?;
+    match LuaAst::cast(node)? {
+        LuaAst::LuaIndexExpr(index_expr) => index_expr.get_index_key()?.get_range(),
+        LuaAst::LuaTableField(field) => field.get_field_key()?.get_range(),
+        LuaAst::LuaDocTagField(tag) => tag.get_field_key_range(),
+        _ => None,
+    }
+}
@@ -0,0 +1,41 @@
+use lsp_types::Diagnostic;
+use rowan::{TextRange, TextSize};
+use serde::{Deserialize, Serialize};
+
+use crate::SemanticModel;
+
+use super::{DiagnosticFix, FixApplicability, FixEdit};
+
+/// Edit of the native formatter carried by a `code-style-check` diagnostic, in byte offsets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StyleFixData {
+    pub start: u32,
+    pub end: u32,
+    pub new_text: String,
+}
+
+pub fn build_fixes(
+    semantic_model: &SemanticModel,
+    fixes: &mut Vec<DiagnosticFix>,
+    diagnostic: &Diagnostic,
+) -> Option<()> {
+    let data: StyleFixData = serde_json::from_value(diagnostic.data.clone()?).ok()?;
+    let range = TextRange::new(TextSize::from(data.start), TextSize::from(data.end));
+    // the diagnostic may be older than the text
+    semantic_model
+        .get_document()
+        .get_text()
+        .get(data.start as usize..data.end as usize)?;
+
+    fixes.push(DiagnosticFix {
+        title: t!("Fix code style").to_string(),
+        edits: vec![FixEdit {
+            range,
+            new_text: data.new_text,
+        }],
+        applicability: FixApplicability::MachineApplicable,
+    });
+
+    Some(())
+}
@@ -0,0 +1,118 @@
+mod code_style_check;
+mod need_check_nil;
+mod preferred_local_alias;
+
+use std::str::FromStr;
+
+use lsp_types::{Diagnostic, NumberOrString};
+use rowan::{TextRange, TextSize};
+
+use crate::{DiagnosticCode, SemanticModel};
+pub(crate) use code_style_check::StyleFixData;
+
+/// Whether a fix can be applied without a human looking at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixApplicability {
+    /// The fix keeps the behavior of the code, `emmylua_check --fix` applies it.
+    MachineApplicable,
+    /// The fix changes what the code means or only silences the diagnostic, it is only offered as
+    /// a quick fix in the editor.
+    MaybeIncorrect,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixEdit {
+    pub range: TextRange,
+    pub new_text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticFix {
+    pub title: String,
+    pub edits: Vec<FixEdit>,
+    pub applicability: FixApplicability,
+}
+
+/// Fixes for an EmmyLua diagnostic of the file of `semantic_model`.
+pub fn get_diagnostic_fixes(
+    semantic_model: &SemanticModel,
+    diagnostic: &Diagnostic,
+) -> Vec<DiagnosticFix> {
+    let mut fixes = Vec::new();
+    if diagnostic.source.as_deref() != Some("EmmyLua") {
+        return fixes;
+    }
+    let Some(NumberOrString::String(code)) = &diagnostic.code else {
+        return fixes;
+    };
+    let Ok(code) = DiagnosticCode::from_str(code) else {
+        return fixes;
+    };
+
+    match code {
+        DiagnosticCode::NeedCheckNil => {
+            need_check_nil::build_fixes(semantic_model, &mut fixes, diagnostic);
+        }
+        DiagnosticCode::PreferredLocalAlias => {
+            preferred_local_alias::build_fixes(semantic_model, &mut fixes, diagnostic);
+        }
+        DiagnosticCode::CodeStyleCheck => {
+            code_style_check::build_fixes(semantic_model, &mut fixes, diagnostic);
+        }
+        _ => {}
+    }
+
+    fixes
+}
+
+/// Edits of the machine applicable fixes of `diagnostics`, sorted by position. When two fixes
+/// overlap the later one is dropped, running the fix again applies it on the updated text.
+pub fn collect_machine_applicable_edits(
+    semantic_model: &SemanticModel,
+    diagnostics: &[Diagnostic],
+) -> Vec<FixEdit> {
+    let mut fixes: Vec<Vec<FixEdit>> = diagnostics
+        .iter()
+        .flat_map(|diagnostic| get_diagnostic_fixes(semantic_model, diagnostic))
+        .filter(|fix| fix.applicability == FixApplicability::MachineApplicable)
+        .map(|mut fix| {
+            fix.edits.sort_by_key(|edit| edit.range.start());
+            fix.edits
+        })
+        .filter(|edits| !edits.is_empty())
+        .collect();
+    fixes.sort_by_key(|edits| edits[0].range.start());
+
+    let mut result: Vec<FixEdit> = Vec::new();
+    for edits in fixes {
+        // the edits of one fix are applied together or not at all
+        let overlaps = edits.iter().any(|edit| {
+            result
+                .iter()
+                .any(|applied| is_overlapping(applied.range, edit.range))
+        });
+        if !overlaps {
+            result.extend(edits);
+        }
+    }
+    result.sort_by_key(|edit| edit.range.start());
+    result
+}
+
+fn is_overlapping(a: TextRange, b: TextRange) -> bool {
+    // two insertions at the same offset would depend on their order
+    a.start() < b.end() && b.start() < a.end() || a.start() == b.start()
+}
+
+/// Apply `edits`, which must be sorted and must not overlap, to `text`.
+pub fn apply_fix_edits(text: &str, edits: &[FixEdit]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = TextSize::from(0);
+    for edit in edits {
+        result.push_str(&text[TextRange::new(last, edit.range.start())]);
+        result.push_str(&edit.new_text);
+        last = edit.range.end();
+    }
+    result.push_str(&text[usize::from(last)..]);
+    result
+}
@@ -0,0 +1,43 @@
+use emmylua_parser::{LuaAstNode, LuaExpr};
+use lsp_types::Diagnostic;
+use rowan::{NodeOrToken, TextRange, TokenAtOffset};
+
+use crate::SemanticModel;
+
+use super::{DiagnosticFix, FixApplicability, FixEdit};
+
+pub fn build_fixes(
+    semantic_model: &SemanticModel,
+    fixes: &mut Vec<DiagnosticFix>,
+    diagnostic: &Diagnostic,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let range = diagnostic.range;
+    let offset = document.get_offset(range.end.line as usize, range.end.character as usize)?;
+    let root = semantic_model.get_root();
+    let token = match root.syntax().token_at_offset(offset) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(_, token) => token,
+        _ => return None,
+    };
+    // 取上一个token的父节点
+    let node_or_token = token.prev_sibling_or_token()?;
+    if let NodeOrToken::Node(expr_node) = node_or_token
+        && LuaExpr::can_cast(expr_node.kind().into())
+    {
+        let expr = LuaExpr::cast(expr_node)?;
+        // 插入到表达式的尾部
+        let end = expr.syntax().text_range().end();
+        fixes.push(DiagnosticFix {
+            title: t!("use cast to remove nil").to_string(),
+            edits: vec![FixEdit {
+                range: TextRange::empty(end),
+                new_text: "--[[@cast -?]]".to_string(),
+            }],
+            // the cast only silences the check, the value may still be nil at runtime
+            applicability: FixApplicability::MaybeIncorrect,
+        });
+    }
+
+    Some(())
+}
@@ -0,0 +1,64 @@
+use emmylua_parser::{LuaAstNode, LuaIndexExpr};
+use lsp_types::Diagnostic;
+
+use crate::SemanticModel;
+
+use super::{DiagnosticFix, FixApplicability, FixEdit};
+
+pub fn build_fixes(
+    semantic_model: &SemanticModel,
+    fixes: &mut Vec<DiagnosticFix>,
+    diagnostic: &Diagnostic,
+) -> Option<()> {
+    let alias_name = diagnostic.data.as_ref()?.as_str()?;
+    let document = semantic_model.get_document();
+    let range = document.to_rowan_range(diagnostic.range)?;
+    let index_expr = semantic_model
+        .get_root()
+        .syntax()
+        .covering_element(range)
+        .into_node()?
+        .ancestors()
+        .find_map(LuaIndexExpr::cast)?;
+    if index_expr.get_range() != range {
+        return None;
+    }
+
+    // the alias may be shadowed by another local with the same name at this position
+    let file_id = semantic_model.get_file_id();
+    let db = semantic_model.get_db();
+    let decl = db
+        .get_decl_index()
+        .get_decl_tree(&file_id)?
+        .find_local_decl(alias_name, range.start())?;
+    let value_range = decl.get_value_syntax_id()?.get_range();
+    let alias_value = &document.get_text()[value_range];
+    let expr_text = index_expr.syntax().text().to_string();
+    if !is_same_expr_text(alias_value, &expr_text) {
+        return None;
+    }
+    if db
+        .get_reference_index()
+        .get_decl_references(&file_id, &decl.get_id())
+        .is_some_and(|decl_refs| decl_refs.mutable)
+    {
+        return None;
+    }
+
+    fixes.push(DiagnosticFix {
+        title: t!("Replace with local alias '%{name}'", name = alias_name).to_string(),
+        edits: vec![FixEdit {
+            range,
+            new_text: alias_name.to_string(),
+        }],
+        applicability: FixApplicability::MachineApplicable,
+    });
+
+    Some(())
+}
+
+fn is_same_expr_text(a: &str, b: &str) -> bool {
+    a.chars()
+        .filter(|c| !c.is_whitespace())
+        .eq(b.chars().filter(|c| !c.is_whitespace()))
+}
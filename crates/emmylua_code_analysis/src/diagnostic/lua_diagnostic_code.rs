@@ -113,6 +113,16 @@ pub enum DiagnosticCode {
     AttributeMissingParameter,
     /// attribute-redundant-parameter
     AttributeRedundantParameter,
+    /// Exported member never referenced in the workspace
+    UnusedExport,
+    /// File never required from the configured entry points
+    UnreachableFile,
+    /// Files requiring each other
+    CyclicRequire,
+    /// Require or global use across a denied `layerRules` boundary
+    LayerViolation,
+    /// A value other than `never` passed to a `never` parameter, a variant left unhandled
+    UnhandledVariant,
 
     #[serde(other)]
     None,
@@ -141,6 +151,8 @@ pub fn get_default_severity(code: DiagnosticCode) -> DiagnosticSeverity {
         DiagnosticCode::DuplicateRequire => DiagnosticSeverity::HINT,
         DiagnosticCode::IterVariableReassign => DiagnosticSeverity::ERROR,
         DiagnosticCode::PreferredLocalAlias => DiagnosticSeverity::HINT,
+        DiagnosticCode::UnusedExport => DiagnosticSeverity::HINT,
+        DiagnosticCode::UnreachableFile => DiagnosticSeverity::HINT,
         _ => DiagnosticSeverity::WARNING,
     }
 }
@@ -152,6 +164,8 @@ pub fn is_code_default_enable(code: &DiagnosticCode, level: LuaLanguageLevel) ->
         DiagnosticCode::IncompleteSignatureDoc => false,
         DiagnosticCode::MissingGlobalDoc => false,
         DiagnosticCode::UnknownDocTag => false,
+        DiagnosticCode::UnusedExport => false,
+        DiagnosticCode::UnreachableFile => false,
         // ... handle other variants
 
         // neovim-code-style
@@ -1,11 +1,15 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 
 use emmylua_parser::LuaLanguageLevel;
 use lsp_types::DiagnosticSeverity;
 use regex::Regex;
 use smol_str::SmolStr;
+use wax::{Glob, Pattern};
 
-use crate::Emmyrc;
+use crate::{Emmyrc, EmmyrcLayerRule};
 
 use super::DiagnosticCode;
 
@@ -17,6 +21,7 @@ pub struct LuaDiagnosticConfig {
     pub global_disable_glob: Vec<Regex>,
     pub severity: HashMap<DiagnosticCode, DiagnosticSeverity>,
     pub level: LuaLanguageLevel,
+    pub layer_rules: Vec<LuaLayerRule>,
 }
 
 impl LuaDiagnosticConfig {
@@ -43,6 +48,13 @@ impl LuaDiagnosticConfig {
             })
             .collect();
 
+        let layer_rules = emmyrc
+            .diagnostics
+            .layer_rules
+            .iter()
+            .filter_map(LuaLayerRule::new)
+            .collect();
+
         let mut severity = HashMap::new();
         for (code, sev) in &emmyrc.diagnostics.severity {
             severity.insert(*code, (*sev).into());
@@ -54,6 +66,54 @@ impl LuaDiagnosticConfig {
             global_disable_glob,
             severity,
             level: emmyrc.get_language_level(),
+            layer_rules,
+        }
+    }
+}
+
+/// A compiled `diagnostics.layerRules` entry.
+#[derive(Debug, Clone)]
+pub struct LuaLayerRule {
+    pub from: String,
+    from_glob: Glob<'static>,
+    deny_globs: Vec<(String, Glob<'static>)>,
+}
+
+impl LuaLayerRule {
+    fn new(rule: &EmmyrcLayerRule) -> Option<Self> {
+        let from_glob = compile_glob(&rule.from)?;
+        let deny_globs = rule
+            .deny
+            .iter()
+            .filter_map(|deny| Some((deny.clone(), compile_glob(deny)?)))
+            .collect();
+        Some(Self {
+            from: rule.from.clone(),
+            from_glob,
+            deny_globs,
+        })
+    }
+
+    /// `path` is relative to the workspace root.
+    pub fn is_from(&self, path: &Path) -> bool {
+        self.from_glob.is_match(path)
+    }
+
+    /// The first `deny` glob matching `path`, relative to the workspace root.
+    pub fn find_deny(&self, path: &Path) -> Option<&str> {
+        self.deny_globs
+            .iter()
+            .find(|(_, glob)| glob.is_match(path))
+            .map(|(deny, _)| deny.as_str())
+    }
+}
+
+fn compile_glob(pattern: &str) -> Option<Glob<'static>> {
+    match Glob::new(pattern) {
+        Ok(glob) => Some(glob.into_owned()),
+        Err(e) => {
+            log::error!("Invalid layer glob: {}, error: {}", pattern, e);
+            None
         }
     }
 }
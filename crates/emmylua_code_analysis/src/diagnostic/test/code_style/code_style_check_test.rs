@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod test {
+    use emmylua_code_style::{LuaCodeStyle, QuoteStyle};
+
+    use crate::{DiagnosticCode, Emmyrc, EmmyrcFormatter};
+
+    #[test]
+    fn test_native_code_style() {
+        let mut ws = crate::VirtualWorkspace::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.format.formatter = EmmyrcFormatter::Native;
+        ws.update_emmyrc(emmyrc);
+        ws.enable_check(DiagnosticCode::CodeStyleCheck);
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::CodeStyleCheck,
+            "local a = 1\nif a then\n    print(a)\nend\n",
+        ));
+        assert!(!ws.check_code_for(DiagnosticCode::CodeStyleCheck, "local b = 1   \n"));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::CodeStyleCheck,
+            "if b then\n  print(b)\nend\n",
+        ));
+        assert!(!ws.check_code_for(DiagnosticCode::CodeStyleCheck, "local c  =  1\n"));
+    }
+
+    #[test]
+    fn test_native_code_style_options() {
+        let mut ws = crate::VirtualWorkspace::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.format.formatter = EmmyrcFormatter::Native;
+        emmyrc.format.max_line_width = 20;
+        emmyrc.format.style = Some(LuaCodeStyle {
+            quote_style: QuoteStyle::Single,
+            ..Default::default()
+        });
+        ws.update_emmyrc(emmyrc);
+        ws.enable_check(DiagnosticCode::CodeStyleCheck);
+
+        assert!(ws.check_code_for(DiagnosticCode::CodeStyleCheck, "local a = 'a'\n"));
+        assert!(!ws.check_code_for(DiagnosticCode::CodeStyleCheck, "local b = \"b\"\n"));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::CodeStyleCheck,
+            "local c = 'a long string value'\n",
+        ));
+    }
+
+    #[test]
+    fn test_native_code_style_syntax_error() {
+        let mut ws = crate::VirtualWorkspace::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.format.formatter = EmmyrcFormatter::Native;
+        ws.update_emmyrc(emmyrc);
+        ws.enable_check(DiagnosticCode::CodeStyleCheck);
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::CodeStyleCheck,
+            "local a  =  1   \nif a then\n  print( a\nend\n",
+        ));
+    }
+
+    #[test]
+    fn test_invalid_style_option() {
+        let emmyrc = serde_json::from_value::<Emmyrc>(serde_json::json!({
+            "format": { "style": { "quote_style": "Single", "indent": { "Space": 2 } } }
+        }))
+        .unwrap();
+        let style = emmyrc.format.get_native_style();
+        assert_eq!(style.quote_style, QuoteStyle::Single);
+        assert_eq!(style.indent_width(), 2);
+
+        let result = serde_json::from_value::<Emmyrc>(serde_json::json!({
+            "format": { "style": { "quote_style": "Backtick" } }
+        }));
+        assert!(result.is_err());
+    }
+}
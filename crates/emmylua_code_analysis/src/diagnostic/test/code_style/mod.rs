@@ -1,2 +1,3 @@
+mod code_style_check_test;
 mod non_literal_expressions_in_assert_test;
 mod preferred_local_alias_test;
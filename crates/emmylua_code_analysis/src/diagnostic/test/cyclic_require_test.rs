@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use lsp_types::NumberOrString;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_cycle() {
+        let mut ws = VirtualWorkspace::new();
+        let file_ids = ws.def_files(vec![
+            ("a.lua", r#"local b = require("b")"#),
+            ("b.lua", r#"local c = require("c")"#),
+            ("c.lua", r#"local a = require("a")"#),
+        ]);
+
+        let code = Some(NumberOrString::String(
+            DiagnosticCode::CyclicRequire.get_name().to_string(),
+        ));
+        let mut messages = Vec::new();
+        for file_id in file_ids {
+            let diagnostics = ws
+                .analysis
+                .diagnose_file(file_id, CancellationToken::new())
+                .unwrap_or_default();
+            messages.extend(
+                diagnostics
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.code == code)
+                    .map(|diagnostic| diagnostic.message),
+            );
+        }
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "Require cycle: a -> b -> c -> a",
+                "Require cycle: b -> c -> a -> b",
+                "Require cycle: c -> a -> b -> c",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file("lib.lua", r#"return {}"#);
+        assert!(ws.check_code_for(
+            DiagnosticCode::CyclicRequire,
+            r#"
+            local lib = require("lib")
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_self_require() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file("self.lua", r#"local s = require("self")"#);
+        let file_id = ws
+            .analysis
+            .compilation
+            .get_db()
+            .get_vfs()
+            .get_file_id(&ws.virtual_url_generator.new_uri("self.lua"))
+            .expect("file should exist");
+        let diagnostics = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default();
+        let diagnostic = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.message == "Require cycle: self -> self")
+            .expect("cycle should be reported");
+        let self_path = ws.virtual_url_generator.new_path("self.lua");
+        let self_path = self_path.to_string_lossy();
+        assert_eq!(
+            diagnostic.data,
+            Some(serde_json::json!({ "cycle": [self_path, self_path] }))
+        );
+    }
+
+    #[test]
+    fn test_lazy_require() {
+        let mut ws = VirtualWorkspace::new();
+        let file_ids = ws.def_files(vec![
+            (
+                "a.lua",
+                r#"
+                local M = {}
+                function M.f()
+                    local b = require("b")
+                end
+                return M
+                "#,
+            ),
+            ("b.lua", r#"local a = require("a")"#),
+        ]);
+
+        let code = Some(NumberOrString::String(
+            DiagnosticCode::CyclicRequire.get_name().to_string(),
+        ));
+        for file_id in file_ids {
+            let diagnostics = ws
+                .analysis
+                .diagnose_file(file_id, CancellationToken::new())
+                .unwrap_or_default();
+            assert!(diagnostics.iter().all(|diagnostic| diagnostic.code != code));
+        }
+    }
+}
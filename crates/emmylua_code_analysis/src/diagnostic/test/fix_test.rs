@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod test {
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{
+        DiagnosticCode, Emmyrc, EmmyrcFormatter, FixApplicability, VirtualWorkspace,
+        apply_fix_edits, get_diagnostic_fixes,
+    };
+
+    fn fix(ws: &mut VirtualWorkspace, code: &str) -> String {
+        let file_id = ws.def(code);
+        let edits = ws
+            .analysis
+            .get_file_fix_edits(file_id, CancellationToken::new())
+            .unwrap();
+        apply_fix_edits(code, &edits)
+    }
+
+    #[test]
+    fn test_fix_preferred_local_alias() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        assert_eq!(
+            fix(
+                &mut ws,
+                r#"
+                local gsub = string.gsub
+                print(string.gsub("hello", "l", "0"))
+                return string.gsub
+                "#
+            ),
+            r#"
+                local gsub = string.gsub
+                print(gsub("hello", "l", "0"))
+                return gsub
+                "#
+        );
+    }
+
+    #[test]
+    fn test_fix_preferred_local_alias_shadowed() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let code = r#"
+            local gsub = string.gsub
+            do
+                local gsub = 1
+                print(string.gsub("hello", "l", "0"), gsub)
+            end
+            "#;
+        assert_eq!(fix(&mut ws, code), code);
+    }
+
+    #[test]
+    fn test_need_check_nil_fix_is_not_applied() {
+        let mut ws = VirtualWorkspace::new();
+        let code = r#"
+            ---@type { a: integer }?
+            local t
+            local a = t.a
+            "#;
+        let file_id = ws.def(code);
+        let diagnostics = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap();
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let fixes: Vec<_> = diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic.code
+                    == Some(lsp_types::NumberOrString::String(
+                        DiagnosticCode::NeedCheckNil.get_name().to_string(),
+                    ))
+            })
+            .flat_map(|diagnostic| get_diagnostic_fixes(&semantic_model, diagnostic))
+            .collect();
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].applicability, FixApplicability::MaybeIncorrect);
+
+        assert_eq!(fix(&mut ws, code), code);
+    }
+
+    #[test]
+    fn test_fix_code_style() {
+        let mut ws = VirtualWorkspace::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.format.formatter = EmmyrcFormatter::Native;
+        ws.update_emmyrc(emmyrc);
+        ws.enable_check(DiagnosticCode::CodeStyleCheck);
+        assert_eq!(
+            fix(
+                &mut ws,
+                "local a  =  1   \nif a then\n  print( a )\nend\n\n\n\nreturn a\n"
+            ),
+            "local a = 1\nif a then\n    print(a)\nend\n\nreturn a\n"
+        );
+        // a file with syntax errors is never rewritten
+        let broken = "local b  =  1   \nif b then\n  print( b\nend\n";
+        assert_eq!(fix(&mut ws, broken), broken);
+    }
+}
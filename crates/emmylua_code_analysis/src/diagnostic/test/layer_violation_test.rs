@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use lsp_types::NumberOrString;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{DiagnosticCode, EmmyrcLayerRule, FileId, VirtualWorkspace};
+
+    fn set_layer_rules(ws: &mut VirtualWorkspace, rules: &[(&str, &[&str])]) {
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.diagnostics.layer_rules = rules
+            .iter()
+            .map(|(from, deny)| EmmyrcLayerRule {
+                from: from.to_string(),
+                deny: deny.iter().map(|deny| deny.to_string()).collect(),
+            })
+            .collect();
+        ws.update_emmyrc(emmyrc);
+    }
+
+    fn get_messages(ws: &VirtualWorkspace, file_id: FileId) -> Vec<String> {
+        let code = Some(NumberOrString::String(
+            DiagnosticCode::LayerViolation.get_name().to_string(),
+        ));
+        ws.analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|diagnostic| diagnostic.code == code)
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_require_denied_layer() {
+        let mut ws = VirtualWorkspace::new();
+        set_layer_rules(&mut ws, &[("client/**", &["server/**"])]);
+        ws.def_file("server/db.lua", "return {}");
+        ws.def_file("shared/util.lua", "return {}");
+        let file_id = ws.def_file(
+            "client/ui.lua",
+            r#"
+            local db = require("server.db")
+            local util = require("shared.util")
+            "#,
+        );
+
+        assert_eq!(
+            get_messages(&ws, file_id),
+            vec!["Module 'server.db' is in layer 'server/**', which 'client/**' must not require"]
+        );
+    }
+
+    #[test]
+    fn test_rule_only_applies_to_from() {
+        let mut ws = VirtualWorkspace::new();
+        set_layer_rules(&mut ws, &[("client/**", &["server/**"])]);
+        ws.def_file("client/ui.lua", "return {}");
+        let file_id = ws.def_file(
+            "server/main.lua",
+            r#"
+            local ui = require("client.ui")
+            "#,
+        );
+
+        assert!(get_messages(&ws, file_id).is_empty());
+    }
+
+    #[test]
+    fn test_global_of_denied_layer() {
+        let mut ws = VirtualWorkspace::new();
+        set_layer_rules(&mut ws, &[("client/**", &["server/**"])]);
+        ws.def_file(
+            "server/db.lua",
+            r#"
+            ServerDb = {}
+            SharedConfig = {}
+            "#,
+        );
+        ws.def_file("shared/config.lua", "SharedConfig = {}");
+        let file_id = ws.def_file(
+            "client/ui.lua",
+            r#"
+            local db = ServerDb
+            local config = SharedConfig
+            "#,
+        );
+
+        assert_eq!(
+            get_messages(&ws, file_id),
+            vec![
+                "Global 'ServerDb' is only defined in layer 'server/**', which 'client/**' must not use"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_rules() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file("server/db.lua", "return {}");
+        let file_id = ws.def_file("client/ui.lua", r#"local db = require("server.db")"#);
+
+        assert!(get_messages(&ws, file_id).is_empty());
+    }
+}
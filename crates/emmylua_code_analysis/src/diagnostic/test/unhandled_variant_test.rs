@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    const SHAPE: &str = r#"
+        ---@class Circle
+        ---@field kind "circle"
+
+        ---@class Square
+        ---@field kind "square"
+
+        ---@class Triangle
+        ---@field kind "triangle"
+
+        ---@alias Shape Circle | Square | Triangle
+
+        ---@param value never
+        function assertNever(value)
+            error("unhandled variant")
+        end
+    "#;
+
+    #[test]
+    fn test_exhaustive_chain() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(SHAPE);
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnhandledVariant,
+            r#"
+            ---@param s Shape
+            local function area(s)
+                if s.kind == "circle" then
+                    return 1
+                elseif s.kind == "square" then
+                    return 2
+                elseif s.kind == "triangle" then
+                    return 3
+                else
+                    assertNever(s)
+                end
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_missing_variant() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(SHAPE);
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnhandledVariant,
+            r#"
+            ---@param s Shape
+            local function area(s)
+                if s.kind == "circle" then
+                    return 1
+                elseif s.kind == "square" then
+                    return 2
+                else
+                    assertNever(s)
+                end
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_early_return() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(SHAPE);
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnhandledVariant,
+            r#"
+            ---@param s Shape
+            local function area(s)
+                if s.kind == "circle" then
+                    return 1
+                end
+                if s.kind == "square" or s.kind == "triangle" then
+                    return 2
+                end
+                assertNever(s)
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_not_param_type_mismatch() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(SHAPE);
+        assert!(ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            ---@param s Shape
+            local function area(s)
+                if s.kind == "circle" then
+                    return 1
+                end
+                assertNever(s)
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_param_type_mismatch_without_unhandled_variant() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc
+            .diagnostics
+            .disable
+            .push(DiagnosticCode::UnhandledVariant);
+        ws.update_emmyrc(emmyrc);
+        ws.def(SHAPE);
+        assert!(!ws.check_code_for(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            ---@param s Shape
+            local function area(s)
+                if s.kind == "circle" then
+                    return 1
+                end
+                assertNever(s)
+            end
+            "#
+        ));
+    }
+}
@@ -26,4 +26,47 @@ mod test {
         "#
         ));
     }
+
+    #[test]
+    fn test_discriminant_narrowing() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Circle
+            ---@field kind "circle"
+
+            ---@class Square
+            ---@field kind "square"
+
+            ---@alias Shape Circle | Square
+
+            ---@param value never
+            function assertNever(value) end
+            "#,
+        );
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnnecessaryIf,
+            r#"
+            ---@param s Shape
+            local function area(s)
+                if s.kind == "circle" then
+                    return 1
+                elseif s.kind == "square" then
+                    return 2
+                else
+                    assertNever(s)
+                end
+            end
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnnecessaryIf,
+            r#"
+            ---@type Circle
+            local c
+            if c.kind == "circle" then
+            end
+            "#
+        ));
+    }
 }
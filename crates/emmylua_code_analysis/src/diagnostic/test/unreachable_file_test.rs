@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use lsp_types::{NumberOrString, Position};
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{DiagnosticCode, VirtualWorkspace, collect_unreachable_files};
+
+    fn set_entry_points(ws: &mut VirtualWorkspace, entry_points: &[&str]) {
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.workspace.entry_points = entry_points
+            .iter()
+            .map(|path| {
+                ws.virtual_url_generator
+                    .new_path(path)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        ws.update_emmyrc(emmyrc);
+        ws.enable_check(DiagnosticCode::UnreachableFile);
+    }
+
+    #[test]
+    fn test_unreachable_file() {
+        let mut ws = VirtualWorkspace::new();
+        set_entry_points(&mut ws, &["main.lua"]);
+        ws.def_files(vec![
+            ("main.lua", r#"require("game")"#),
+            ("game.lua", r#"require("util")"#),
+            ("util.lua", "return {}"),
+        ]);
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnreachableFile,
+            r#"
+            local orphan = require("util")
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_reachable_files() {
+        let mut ws = VirtualWorkspace::new();
+        set_entry_points(&mut ws, &["main.lua", "conf.lua"]);
+        ws.def_files(vec![
+            ("main.lua", r#"require("game")"#),
+            ("conf.lua", "return {}"),
+            ("game.lua", r#"require("util")"#),
+            ("util.lua", "return {}"),
+            ("old.lua", "return {}"),
+        ]);
+
+        let db = ws.analysis.compilation.get_db();
+        let unreachable = collect_unreachable_files(db);
+        let old_id = db
+            .get_vfs()
+            .get_file_id(&ws.virtual_url_generator.new_uri("old.lua"));
+        assert_eq!(unreachable.len(), 1);
+        assert_eq!(Some(unreachable[0]), old_id);
+    }
+
+    #[test]
+    fn test_no_entry_points() {
+        let mut ws = VirtualWorkspace::new();
+        ws.enable_check(DiagnosticCode::UnreachableFile);
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnreachableFile,
+            r#"
+            local a = 1
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_blank_first_line() {
+        let mut ws = VirtualWorkspace::new();
+        set_entry_points(&mut ws, &["main.lua"]);
+        ws.def_file("main.lua", "return {}");
+        let file_id = ws.def_file("orphan.lua", "\n  local orphan = 1\n");
+
+        let diagnostics = ws
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap();
+        let code = Some(NumberOrString::String(
+            DiagnosticCode::UnreachableFile.get_name().to_string(),
+        ));
+        let diagnostic = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.code == code)
+            .unwrap();
+        assert_eq!(diagnostic.range.start, Position::new(1, 2));
+        assert_eq!(diagnostic.range.end, Position::new(1, 7));
+    }
+
+    #[test]
+    fn test_reachable_after_change() {
+        let mut ws = VirtualWorkspace::new();
+        set_entry_points(&mut ws, &["main.lua"]);
+        ws.def_file("main.lua", "return {}");
+        let util = ws.def_file("util.lua", "return {}");
+        let db = ws.analysis.compilation.get_db();
+        assert_eq!(collect_unreachable_files(db), vec![util]);
+
+        ws.def_file("main.lua", r#"require("util")"#);
+        let db = ws.analysis.compilation.get_db();
+        assert!(collect_unreachable_files(db).is_empty());
+
+        ws.def_file("main.lua", "return {}");
+        let db = ws.analysis.compilation.get_db();
+        assert_eq!(collect_unreachable_files(db), vec![util]);
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_module_member() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UnusedExport);
+        ws.def_file(
+            "used.lua",
+            r#"
+            local M = {}
+
+            function M.used() end
+
+            return M
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            require("used").used()
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            local M = {}
+
+            function M.never_used() end
+
+            return M
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_global_function() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UnusedExport);
+        ws.def(
+            r#"
+            global_used()
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            function global_used() end
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            function global_never_used() end
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_class_field() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UnusedExport);
+        ws.def(
+            r#"
+            ---@type Point
+            local p
+            print(p.x)
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            ---@class Point
+            ---@field x number
+            "#,
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            ---@class Size
+            ---@field width number
+            "#,
+        ));
+        // metamethods are called by the runtime
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            ---@class Vector
+            local Vector = {}
+
+            function Vector.__add(a, b) end
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_entry_point() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.enable_check(DiagnosticCode::UnusedExport);
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            local M = {}
+
+            ---@[entry_point]
+            function M.main() end
+
+            return M
+            "#,
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            ---@[entry_point]
+            local M = {}
+
+            function M.a() end
+
+            M.b = 1
+
+            return M
+            "#,
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            ---@[entry_point]
+            function on_load() end
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnusedExport,
+            r#"
+            function global_never_used() end
+            "#,
+        ));
+    }
+}
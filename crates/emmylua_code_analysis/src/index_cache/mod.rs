@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    hash::Hasher,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use fnv::FnvHasher;
+use serde::{Deserialize, Serialize};
+
+use crate::{DbIndex, DbIndexSnapshot, Emmyrc, FileId, FileIdRemap, WorkspaceId};
+
+/// Bump when the layout of any cached index changes.
+const INDEX_CACHE_VERSION: u32 = 7;
+const INDEX_CACHE_DIR_NAME: &str = "emmylua_analyzer";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexCacheHeader {
+    version: u32,
+    analyzer_version: String,
+    config_hash: u64,
+    files: Vec<CachedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedFile {
+    path: PathBuf,
+    file_id: FileId,
+    content_hash: u64,
+}
+
+/// Default location of the index cache for a workspace: one file per main workspace root, inside
+/// `index_cache_dir` or the system cache directory.
+pub fn get_index_cache_path(emmyrc: &Emmyrc, main_root: &Path) -> Option<PathBuf> {
+    let cache_dir = match &emmyrc.workspace.index_cache_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::cache_dir()?.join(INDEX_CACHE_DIR_NAME),
+    };
+
+    let mut hasher = FnvHasher::default();
+    hash_path(&mut hasher, main_root);
+    Some(cache_dir.join(format!("{:016x}.idx", hasher.finish())))
+}
+
+pub(crate) fn write_index_cache(db: &DbIndex, emmyrc: &Emmyrc, path: &Path) -> io::Result<()> {
+    let header = IndexCacheHeader {
+        version: INDEX_CACHE_VERSION,
+        analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+        config_hash: config_hash(db, emmyrc),
+        files: collect_cached_files(db),
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // write to a temporary file first, a half written cache must never be picked up
+    let tmp_path = path.with_extension("tmp");
+    {
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        bincode::serialize_into(&mut writer, &header).map_err(io::Error::other)?;
+        bincode::serialize_into(&mut writer, &db.snapshot()).map_err(io::Error::other)?;
+        writer.flush()?;
+    }
+    fs::rename(&tmp_path, path)
+}
+
+/// Restore the cached index into `db`.
+///
+/// Returns `None` when the cache is missing or can not be used, `db` is left untouched in that
+/// case. Otherwise returns the files whose content differs from the cache, including removed
+/// files. They and the files depending on them still need to be analyzed again.
+pub(crate) fn restore_index_cache(
+    db: &mut DbIndex,
+    emmyrc: &Emmyrc,
+    path: &Path,
+) -> Option<Vec<FileId>> {
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+    let header: IndexCacheHeader = match bincode::deserialize_from(&mut reader) {
+        Ok(header) => header,
+        Err(e) => {
+            log::warn!("invalid index cache {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    if header.version != INDEX_CACHE_VERSION
+        || header.analyzer_version != env!("CARGO_PKG_VERSION")
+        || header.config_hash != config_hash(db, emmyrc)
+    {
+        log::info!("index cache {:?} is outdated", path);
+        return None;
+    }
+
+    let CachedFilesDiff {
+        mut changed_files,
+        file_id_remap,
+    } = diff_cached_files(db, &header.files);
+    let (snapshot, file_id_remap) =
+        file_id_remap.apply(|| bincode::deserialize_from::<_, DbIndexSnapshot>(&mut reader));
+    let snapshot = match snapshot {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            log::warn!("invalid index cache {:?}: {}", path, e);
+            return None;
+        }
+    };
+    db.restore_snapshot(snapshot);
+
+    // the cached data of removed files got ids after the current files
+    let vfs = db.get_vfs_mut();
+    let removed_start = vfs.get_next_file_id().id;
+    let next_id = file_id_remap.get_next_id();
+    vfs.reserve_file_ids(FileId::new(next_id));
+    changed_files.extend((removed_start..next_id).map(FileId::new));
+    changed_files.sort();
+    log::info!(
+        "index cache restored, {} of {} files changed",
+        changed_files.len(),
+        header.files.len()
+    );
+    Some(changed_files)
+}
+
+struct CachedFilesDiff {
+    /// New files and files whose content differs from the cache
+    changed_files: Vec<FileId>,
+    /// Cached ids of the files which still exist to their current ids
+    file_id_remap: FileIdRemap,
+}
+
+/// Match the cached files with the vfs by path. The cached index is read with the ids of the
+/// current files, so adding a file which shifts the ids of later files only reanalyzes new or
+/// changed files.
+fn diff_cached_files(db: &DbIndex, cached_files: &[CachedFile]) -> CachedFilesDiff {
+    let cached_map: HashMap<&PathBuf, &CachedFile> =
+        cached_files.iter().map(|file| (&file.path, file)).collect();
+    let mut changed_files = Vec::new();
+    let mut ids = HashMap::new();
+    for file in collect_cached_files(db) {
+        match cached_map.get(&file.path) {
+            Some(cached) => {
+                ids.insert(cached.file_id.id, file.file_id.id);
+                if cached.content_hash != file.content_hash {
+                    changed_files.push(file.file_id);
+                }
+            }
+            None => changed_files.push(file.file_id),
+        }
+    }
+
+    CachedFilesDiff {
+        changed_files,
+        file_id_remap: FileIdRemap::new(ids, db.get_vfs().get_next_file_id().id),
+    }
+}
+
+fn collect_cached_files(db: &DbIndex) -> Vec<CachedFile> {
+    let vfs = db.get_vfs();
+    let mut files = Vec::new();
+    for file_id in vfs.get_all_file_ids() {
+        let (Some(path), Some(content)) =
+            (vfs.get_file_path(&file_id), vfs.get_file_content(&file_id))
+        else {
+            continue;
+        };
+
+        let mut hasher = FnvHasher::default();
+        hasher.write(content.as_bytes());
+        files.push(CachedFile {
+            path: path.clone(),
+            file_id,
+            content_hash: hasher.finish(),
+        });
+    }
+
+    files
+}
+
+/// Module paths and workspace ids depend on the config and the workspace roots.
+pub(crate) fn config_hash(db: &DbIndex, emmyrc: &Emmyrc) -> u64 {
+    let mut hasher = FnvHasher::default();
+    // the config holds hash maps, their order changes from one process to the next
+    let config = serde_json::to_value(emmyrc)
+        .map(sort_json_keys)
+        .unwrap_or_default();
+    hasher.write(config.to_string().as_bytes());
+    let mut roots: Vec<(&PathBuf, WorkspaceId)> = db
+        .get_module_index()
+        .get_workspaces()
+        .iter()
+        .map(|workspace| (&workspace.root, workspace.id))
+        .collect();
+    roots.sort_by_key(|(root, _)| *root);
+    for (root, id) in roots {
+        hash_path(&mut hasher, root);
+        hasher.write_u32(id.id);
+    }
+    hasher.finish()
+}
+
+fn sort_json_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<(String, serde_json::Value)> = map
+                .into_iter()
+                .map(|(key, value)| (key, sort_json_keys(value)))
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            serde_json::Value::Object(entries.into_iter().collect())
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_json_keys).collect())
+        }
+        value => value,
+    }
+}
+
+/// Hashes go through FNV over raw bytes, the output of `DefaultHasher` and of the std `Hash`
+/// impls may change with the toolchain.
+fn hash_path(hasher: &mut FnvHasher, path: &Path) {
+    hasher.write(path.as_os_str().as_encoded_bytes());
+    hasher.write_u8(0xff);
+}
@@ -12,6 +12,7 @@ mod compilation;
 mod config;
 mod db_index;
 mod diagnostic;
+mod index_cache;
 mod locale;
 mod profile;
 mod resources;
@@ -24,12 +25,18 @@ pub use config::*;
 pub use db_index::*;
 pub use diagnostic::*;
 pub use emmylua_codestyle::*;
+pub use index_cache::get_index_cache_path;
 pub use locale::get_locale_code;
 use lsp_types::Uri;
 pub use profile::Profile;
 use resources::load_resource_std;
 pub use semantic::*;
-use std::{collections::HashSet, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashSet,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 pub use test_lib::VirtualWorkspace;
 use tokio_util::sync::CancellationToken;
 pub use vfs::*;
@@ -110,19 +117,15 @@ impl EmmyLuaAnalysis {
             .add_workspace_root(root, id);
     }
 
+    /// Update one file, the file and the files depending on it are analyzed again.
     pub fn update_file_by_uri(&mut self, uri: &Uri, text: Option<String>) -> Option<FileId> {
-        let is_removed = text.is_none();
         let file_id = self
             .compilation
             .get_db_mut()
             .get_vfs_mut()
             .set_file_content(uri, text);
 
-        self.compilation.remove_index(vec![file_id]);
-        if !is_removed {
-            self.compilation.update_index(vec![file_id]);
-        }
-
+        self.compilation.reanalyze_files(vec![file_id]);
         Some(file_id)
     }
 
@@ -188,7 +191,7 @@ impl EmmyLuaAnalysis {
 
     pub fn remove_file_by_uri(&mut self, uri: &Uri) -> Option<FileId> {
         if let Some(file_id) = self.compilation.get_db_mut().get_vfs_mut().remove_file(uri) {
-            self.compilation.remove_index(vec![file_id]);
+            self.compilation.reanalyze_files(vec![file_id]);
             return Some(file_id);
         }
 
@@ -206,6 +209,62 @@ impl EmmyLuaAnalysis {
         self.update_files_by_uri(files)
     }
 
+    /// Same as [`Self::update_files_by_path`], but files which are unchanged since the index cache
+    /// at `cache_path` was written are loaded from the cache instead of being analyzed again.
+    pub fn update_files_by_path_with_cache(
+        &mut self,
+        files: Vec<(PathBuf, Option<String>)>,
+        cache_path: &Path,
+    ) -> Vec<FileId> {
+        let mut removed_files = HashSet::new();
+        let mut updated_files = HashSet::new();
+        {
+            let _p = Profile::new("update files");
+            for (path, text) in files {
+                let Some(uri) = file_path_to_uri(&path) else {
+                    continue;
+                };
+                let is_new_text = text.is_some();
+                let file_id = self
+                    .compilation
+                    .get_db_mut()
+                    .get_vfs_mut()
+                    .set_file_content(&uri, text);
+                removed_files.insert(file_id);
+                if is_new_text {
+                    updated_files.insert(file_id);
+                }
+            }
+        }
+
+        let updated_files: Vec<FileId> = updated_files.into_iter().collect();
+        let cached = {
+            let _p = Profile::new("restore index cache");
+            index_cache::restore_index_cache(
+                self.compilation.get_db_mut(),
+                &self.emmyrc,
+                cache_path,
+            )
+        };
+        match cached {
+            Some(changed_files) => {
+                self.compilation.reanalyze_files(changed_files);
+            }
+            None => {
+                self.compilation
+                    .remove_index(removed_files.into_iter().collect());
+                self.compilation.update_index(updated_files.clone());
+            }
+        }
+        updated_files
+    }
+
+    /// Write the current index to `cache_path`, see [`Self::update_files_by_path_with_cache`].
+    pub fn save_index_cache(&self, cache_path: &Path) -> io::Result<()> {
+        let _p = Profile::new("save index cache");
+        index_cache::write_index_cache(self.compilation.get_db(), &self.emmyrc, cache_path)
+    }
+
     pub fn update_config(&mut self, config: Arc<Emmyrc>) {
         self.emmyrc = config.clone();
         self.compilation.update_config(config.clone());
@@ -225,6 +284,20 @@ impl EmmyLuaAnalysis {
             .diagnose_file(&self.compilation, file_id, cancel_token)
     }
 
+    /// Edits of the machine applicable fixes for the diagnostics of the file.
+    pub fn get_file_fix_edits(
+        &self,
+        file_id: FileId,
+        cancel_token: CancellationToken,
+    ) -> Option<Vec<FixEdit>> {
+        let diagnostics = self.diagnose_file(file_id, cancel_token)?;
+        let semantic_model = self.compilation.get_semantic_model(file_id)?;
+        Some(collect_machine_applicable_edits(
+            &semantic_model,
+            &diagnostics,
+        ))
+    }
+
     pub fn reindex(&mut self) {
         let module = self.compilation.get_db().get_module_index();
         let std_file_ids = module.get_std_file_ids();
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, ops::Deref};
+
+use crate::{
+    DbIndex, LuaMappedModifier, LuaMappedType, LuaMemberKey, LuaObjectType, LuaType, TypeOps,
+    TypeSubstitutor,
+};
+
+use super::{evaluate_alias_call, instantiate_type_generic};
+
+pub fn instantiate_mapped(
+    db: &DbIndex,
+    mapped: &LuaMappedType,
+    substitutor: &TypeSubstitutor,
+) -> LuaType {
+    let key_type = instantiate_type_generic(db, mapped.get_key_type(), substitutor);
+    let remap_type = mapped
+        .get_remap_type()
+        .map(|typ| instantiate_type_generic(db, typ, substitutor));
+    let value_type = instantiate_type_generic(db, mapped.get_value_type(), substitutor);
+    LuaType::Mapped(
+        LuaMappedType::new(
+            mapped.get_param().clone(),
+            key_type,
+            remap_type,
+            value_type,
+            mapped.get_modifier(),
+        )
+        .into(),
+    )
+}
+
+/// The keys a mapped type iterates, `keyof T` is evaluated but no value is instantiated.
+pub fn get_mapped_keys(db: &DbIndex, mapped: &LuaMappedType) -> Vec<LuaType> {
+    let key_type = match mapped.get_key_type() {
+        LuaType::Call(alias_call) => match evaluate_alias_call(db, alias_call) {
+            Some(typ) => typ,
+            None => return Vec::new(),
+        },
+        LuaType::Ref(type_decl_id) => {
+            match db
+                .get_type_index()
+                .get_type_decl(type_decl_id)
+                .and_then(|decl| decl.get_alias_origin(db, None))
+            {
+                Some(typ) => typ,
+                None => return vec![mapped.get_key_type().clone()],
+            }
+        }
+        typ => typ.clone(),
+    };
+
+    match key_type {
+        LuaType::Union(union) => union.into_vec(),
+        LuaType::MultiLineUnion(multi_union) => match multi_union.to_union() {
+            LuaType::Union(union) => union.into_vec(),
+            typ => vec![typ],
+        },
+        LuaType::Unknown | LuaType::Never => Vec::new(),
+        typ => vec![typ],
+    }
+}
+
+/// The object a mapped type expands to, `None` while its keys still depend on a generic.
+pub fn evaluate_mapped(db: &DbIndex, mapped: &LuaMappedType) -> Option<LuaType> {
+    if mapped.get_key_type().contain_tpl() {
+        return None;
+    }
+
+    let mut fields = HashMap::new();
+    let mut index_access = Vec::new();
+    for key in get_mapped_keys(db, mapped) {
+        let Some(field_key) = get_mapped_field_key(db, mapped, &key) else {
+            continue;
+        };
+        let typ = instantiate_mapped_value(db, mapped, &key);
+        match field_key {
+            LuaMemberKey::ExprType(key_type) => index_access.push((key_type, typ)),
+            LuaMemberKey::None => {}
+            field_key => {
+                fields.entry(field_key).or_insert(typ);
+            }
+        }
+    }
+
+    Some(LuaType::Object(
+        LuaObjectType::new_with_fields(fields, index_access).into(),
+    ))
+}
+
+/// The key of the field produced for `key`, `None` when `as` maps the key to `nil` or `never`.
+pub fn get_mapped_field_key(
+    db: &DbIndex,
+    mapped: &LuaMappedType,
+    key: &LuaType,
+) -> Option<LuaMemberKey> {
+    let field_key = match mapped.get_remap_type() {
+        Some(remap_type) => {
+            instantiate_type_generic(db, remap_type, &get_key_substitutor(mapped, key))
+        }
+        None => key.clone(),
+    };
+    match field_key {
+        LuaType::DocStringConst(s) | LuaType::StringConst(s) => {
+            Some(LuaMemberKey::Name(s.deref().clone()))
+        }
+        LuaType::DocIntegerConst(i) | LuaType::IntegerConst(i) => Some(LuaMemberKey::Integer(i)),
+        LuaType::Nil | LuaType::Never | LuaType::Unknown => None,
+        typ => Some(LuaMemberKey::ExprType(typ)),
+    }
+}
+
+/// The value of the field produced for `key`.
+pub fn instantiate_mapped_value(db: &DbIndex, mapped: &LuaMappedType, key: &LuaType) -> LuaType {
+    let value_type = instantiate_type_generic(
+        db,
+        mapped.get_value_type(),
+        &get_key_substitutor(mapped, key),
+    );
+    match mapped.get_modifier() {
+        LuaMappedModifier::None => value_type,
+        LuaMappedModifier::Optional => TypeOps::Union.apply(db, &value_type, &LuaType::Nil),
+        LuaMappedModifier::Required => TypeOps::Remove.apply(db, &value_type, &LuaType::Nil),
+    }
+}
+
+fn get_key_substitutor(mapped: &LuaMappedType, key: &LuaType) -> TypeSubstitutor {
+    let mut substitutor = TypeSubstitutor::new();
+    substitutor.insert_type(mapped.get_param().get_tpl_id(), key.clone());
+    substitutor
+}
@@ -1,10 +1,10 @@
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref, sync::Arc};
 
 use crate::{
-    DbIndex, LuaAliasCallKind, LuaAliasCallType, LuaMemberKey, LuaType, TypeOps, VariadicType,
-    get_member_map,
+    AsyncState, DbIndex, LuaAliasCallKind, LuaAliasCallType, LuaFunctionType, LuaMemberKey,
+    LuaObjectType, LuaTupleStatus, LuaTupleType, LuaType, TypeOps, VariadicType, get_member_map,
     semantic::{
-        member::{find_members, infer_raw_member_type},
+        member::{find_members, find_members_with_key, infer_raw_member_type},
         type_check,
     },
 };
@@ -62,6 +62,11 @@ pub fn instantiate_alias_call(
             let compact = type_check::check_type_compact(db, &operands[0], &operands[1]).is_ok();
             return LuaType::BooleanConst(compact);
         }
+        LuaAliasCallKind::Index => {
+            if operands.len() == 2 {
+                return instantiate_index_call(db, &operands[0], &operands[1]);
+            }
+        }
         LuaAliasCallKind::Select => {
             if operands.len() != 2 {
                 return LuaType::Unknown;
@@ -79,12 +84,73 @@ pub fn instantiate_alias_call(
 
             return instantiate_rawget_call(db, &operands[0], &operands[1]);
         }
-        _ => {}
+        LuaAliasCallKind::Partial | LuaAliasCallKind::Required | LuaAliasCallKind::Readonly => {
+            if operands.len() != 1 {
+                return LuaType::Unknown;
+            }
+
+            return instantiate_mapped_call(db, alias_call.get_call_kind(), &operands[0], None);
+        }
+        LuaAliasCallKind::Pick | LuaAliasCallKind::Omit => {
+            if operands.len() != 2 {
+                return LuaType::Unknown;
+            }
+
+            return instantiate_mapped_call(
+                db,
+                alias_call.get_call_kind(),
+                &operands[0],
+                Some(&operands[1]),
+            );
+        }
+        LuaAliasCallKind::Record => {
+            if operands.len() != 2 {
+                return LuaType::Unknown;
+            }
+
+            return instantiate_record_call(&operands[0], &operands[1]);
+        }
+        LuaAliasCallKind::Parameters => {
+            if operands.len() != 1 {
+                return LuaType::Unknown;
+            }
+
+            return match get_function_type(db, &operands[0]) {
+                Some(func) => instantiate_parameters_call(&func),
+                None => LuaType::Unknown,
+            };
+        }
+        LuaAliasCallKind::ReturnType => {
+            if operands.len() != 1 {
+                return LuaType::Unknown;
+            }
+
+            return instantiate_return_type_call(db, &operands[0], false);
+        }
+        LuaAliasCallKind::Awaited => {
+            if operands.len() != 1 {
+                return LuaType::Unknown;
+            }
+
+            return instantiate_return_type_call(db, &operands[0], true);
+        }
     }
 
     LuaType::Unknown
 }
 
+/// Evaluate an alias call written outside of a generic, like `---@type std.Partial<Config>`.
+pub fn evaluate_alias_call(db: &DbIndex, alias_call: &LuaAliasCallType) -> Option<LuaType> {
+    if alias_call.contain_tpl() {
+        return None;
+    }
+
+    match instantiate_alias_call(db, alias_call, &TypeSubstitutor::new()) {
+        LuaType::Unknown | LuaType::Call(_) => None,
+        typ => Some(typ),
+    }
+}
+
 enum NumOrLen {
     Num(i64),
     Len,
@@ -250,3 +316,149 @@ fn instantiate_rawget_call(db: &DbIndex, owner: &LuaType, key: &LuaType) -> LuaT
 
     infer_raw_member_type(db, owner, &member_key).unwrap_or(LuaType::Unknown)
 }
+
+/// `T[K]` is the union of the fields of `T` named by the literal keys in `K`, it stays a call
+/// while `K` is the key parameter of a mapped type.
+fn instantiate_index_call(db: &DbIndex, owner: &LuaType, key: &LuaType) -> LuaType {
+    if matches!(key, LuaType::TplRef(_)) || owner.contain_tpl() || key.contain_tpl() {
+        return LuaType::Call(
+            LuaAliasCallType::new(LuaAliasCallKind::Index, vec![owner.clone(), key.clone()]).into(),
+        );
+    }
+
+    let mut result = LuaType::Unknown;
+    for member_key in get_literal_member_keys(key) {
+        for member in find_members_with_key(db, owner, member_key, false).unwrap_or_default() {
+            result = TypeOps::Union.apply(db, &result, &member.typ);
+        }
+    }
+    result
+}
+
+/// `std.Partial`, `std.Required`, `std.Readonly`, `std.Pick` and `std.Omit` copy the fields of
+/// `source` into an object type, the first member of a key wins like in `find_members`.
+fn instantiate_mapped_call(
+    db: &DbIndex,
+    call_kind: LuaAliasCallKind,
+    source: &LuaType,
+    keys: Option<&LuaType>,
+) -> LuaType {
+    let Some(members) = find_members(db, source) else {
+        return LuaType::Unknown;
+    };
+    let keys = keys.map(get_literal_member_keys).unwrap_or_default();
+
+    let mut fields = HashMap::new();
+    let mut index_access = Vec::new();
+    for member in members {
+        let selected = match call_kind {
+            LuaAliasCallKind::Pick => keys.contains(&member.key),
+            LuaAliasCallKind::Omit => !keys.contains(&member.key),
+            _ => true,
+        };
+        if !selected {
+            continue;
+        }
+
+        let typ = match call_kind {
+            LuaAliasCallKind::Partial => TypeOps::Union.apply(db, &member.typ, &LuaType::Nil),
+            LuaAliasCallKind::Required => TypeOps::Remove.apply(db, &member.typ, &LuaType::Nil),
+            _ => member.typ,
+        };
+        match member.key {
+            LuaMemberKey::ExprType(key_type) => index_access.push((key_type, typ)),
+            LuaMemberKey::None => {}
+            key => {
+                fields.entry(key).or_insert(typ);
+            }
+        }
+    }
+
+    LuaType::Object(LuaObjectType::new_with_fields(fields, index_access).into())
+}
+
+/// `std.Record<K, V>` is an object when every key is a literal, otherwise `table<K, V>`.
+fn instantiate_record_call(key: &LuaType, value: &LuaType) -> LuaType {
+    let keys = get_literal_member_keys(key);
+    let key_count = match key {
+        LuaType::Union(union) => union.into_vec().len(),
+        _ => 1,
+    };
+    if keys.is_empty() || keys.len() != key_count {
+        return LuaType::TableGeneric(vec![key.clone(), value.clone()].into());
+    }
+
+    let fields = keys.into_iter().map(|key| (key, value.clone())).collect();
+    LuaType::Object(LuaObjectType::new_with_fields(fields, Vec::new()).into())
+}
+
+fn get_literal_member_keys(keys: &LuaType) -> Vec<LuaMemberKey> {
+    let types = match keys {
+        LuaType::Union(union) => union.into_vec(),
+        _ => vec![keys.clone()],
+    };
+    types
+        .into_iter()
+        .filter_map(|typ| match typ {
+            LuaType::DocStringConst(s) | LuaType::StringConst(s) => {
+                Some(LuaMemberKey::Name(s.deref().clone()))
+            }
+            LuaType::DocIntegerConst(i) | LuaType::IntegerConst(i) => {
+                Some(LuaMemberKey::Integer(i))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The doc function of a function type or of a signature.
+fn get_function_type(db: &DbIndex, typ: &LuaType) -> Option<Arc<LuaFunctionType>> {
+    match typ {
+        LuaType::DocFunction(func) => Some(func.clone()),
+        LuaType::Signature(signature_id) => Some(
+            db.get_signature_index()
+                .get(signature_id)?
+                .to_doc_func_type(),
+        ),
+        _ => None,
+    }
+}
+
+/// `std.Parameters<F>` is the tuple of the parameters of `F`.
+fn instantiate_parameters_call(func: &LuaFunctionType) -> LuaType {
+    let types = func
+        .get_params()
+        .iter()
+        .map(|(name, typ)| {
+            let typ = typ.clone().unwrap_or(LuaType::Any);
+            if name == "..." {
+                LuaType::Variadic(VariadicType::Base(typ).into())
+            } else {
+                typ
+            }
+        })
+        .collect();
+    LuaType::Tuple(LuaTupleType::new(types, LuaTupleStatus::DocResolve).into())
+}
+
+/// `std.ReturnType<F>` is what `F` returns, each function of a union contributes its returns.
+///
+/// `std.Awaited<T>` is what an async function returns, any other type is kept.
+fn instantiate_return_type_call(db: &DbIndex, typ: &LuaType, awaited: bool) -> LuaType {
+    if let LuaType::Union(union) = typ {
+        let types = union
+            .into_vec()
+            .iter()
+            .map(|typ| instantiate_return_type_call(db, typ, awaited))
+            .collect::<Vec<_>>();
+        return LuaType::from_vec(types);
+    }
+
+    match get_function_type(db, typ) {
+        Some(func) if !awaited || func.get_async_state() == AsyncState::Async => {
+            func.get_ret().clone()
+        }
+        _ if awaited => typ.clone(),
+        _ => LuaType::Unknown,
+    }
+}
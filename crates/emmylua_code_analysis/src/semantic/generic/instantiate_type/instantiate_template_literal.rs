@@ -0,0 +1,167 @@
+use smol_str::SmolStr;
+
+use crate::{DbIndex, LuaTemplateLiteralType, LuaType, TypeSubstitutor};
+
+use super::instantiate_type_generic;
+
+/// A template literal standing for more strings than this is only checked by pattern.
+const MAX_TEMPLATE_LITERAL_STRINGS: usize = 256;
+
+/// What a placeholder of a template literal may stand for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateLiteralPiece {
+    Literal(String),
+    String,
+    Integer,
+    Number,
+}
+
+pub fn instantiate_template_literal(
+    db: &DbIndex,
+    template: &LuaTemplateLiteralType,
+    substitutor: &TypeSubstitutor,
+) -> LuaType {
+    let parts = template
+        .get_parts()
+        .iter()
+        .map(|part| instantiate_type_generic(db, part, substitutor))
+        .collect();
+    let template = LuaTemplateLiteralType::new(parts);
+    match evaluate_template_literal(db, &template) {
+        Some(typ) => typ,
+        None => LuaType::TemplateLiteral(template.into()),
+    }
+}
+
+/// The union of the strings a template literal stands for, `None` while a placeholder is not a
+/// small set of literals.
+pub fn evaluate_template_literal(
+    db: &DbIndex,
+    template: &LuaTemplateLiteralType,
+) -> Option<LuaType> {
+    evaluate_template_literal_at(db, template, 0)
+}
+
+fn evaluate_template_literal_at(
+    db: &DbIndex,
+    template: &LuaTemplateLiteralType,
+    depth: usize,
+) -> Option<LuaType> {
+    if template.contain_tpl() {
+        return None;
+    }
+
+    let mut strings = vec![String::new()];
+    for pieces in get_pieces_at(db, template, depth)? {
+        let mut literals = Vec::new();
+        for piece in pieces {
+            match piece {
+                TemplateLiteralPiece::Literal(literal) => literals.push(literal),
+                _ => return None,
+            }
+        }
+        if strings.len() * literals.len() > MAX_TEMPLATE_LITERAL_STRINGS {
+            return None;
+        }
+
+        strings = strings
+            .iter()
+            .flat_map(|prefix| {
+                literals
+                    .iter()
+                    .map(move |literal| format!("{prefix}{literal}"))
+            })
+            .collect();
+    }
+
+    if strings.is_empty() {
+        return Some(LuaType::Never);
+    }
+
+    Some(LuaType::from_vec(
+        strings
+            .into_iter()
+            .map(|s| LuaType::DocStringConst(SmolStr::new(s).into()))
+            .collect(),
+    ))
+}
+
+/// The pieces each part of a template literal may match, `None` when a part is not string-like.
+pub fn get_template_literal_pieces(
+    db: &DbIndex,
+    template: &LuaTemplateLiteralType,
+) -> Option<Vec<Vec<TemplateLiteralPiece>>> {
+    get_pieces_at(db, template, 0)
+}
+
+fn get_pieces_at(
+    db: &DbIndex,
+    template: &LuaTemplateLiteralType,
+    depth: usize,
+) -> Option<Vec<Vec<TemplateLiteralPiece>>> {
+    template
+        .get_parts()
+        .iter()
+        .map(|part| {
+            let mut pieces = Vec::new();
+            collect_template_literal_pieces(db, part, &mut pieces, depth)?;
+            Some(pieces)
+        })
+        .collect()
+}
+
+fn collect_template_literal_pieces(
+    db: &DbIndex,
+    typ: &LuaType,
+    pieces: &mut Vec<TemplateLiteralPiece>,
+    depth: usize,
+) -> Option<()> {
+    if depth > 10 {
+        return None;
+    }
+
+    match typ {
+        LuaType::DocStringConst(s) | LuaType::StringConst(s) => {
+            pieces.push(TemplateLiteralPiece::Literal(s.to_string()));
+        }
+        LuaType::DocIntegerConst(i) | LuaType::IntegerConst(i) => {
+            pieces.push(TemplateLiteralPiece::Literal(i.to_string()));
+        }
+        LuaType::DocBooleanConst(b) | LuaType::BooleanConst(b) => {
+            pieces.push(TemplateLiteralPiece::Literal(b.to_string()));
+        }
+        LuaType::Boolean => {
+            pieces.push(TemplateLiteralPiece::Literal("true".to_string()));
+            pieces.push(TemplateLiteralPiece::Literal("false".to_string()));
+        }
+        LuaType::String => pieces.push(TemplateLiteralPiece::String),
+        LuaType::Integer => pieces.push(TemplateLiteralPiece::Integer),
+        LuaType::Number => pieces.push(TemplateLiteralPiece::Number),
+        LuaType::Ref(type_decl_id) => {
+            let origin = db
+                .get_type_index()
+                .get_type_decl(type_decl_id)?
+                .get_alias_origin(db, None)?;
+            collect_template_literal_pieces(db, &origin, pieces, depth + 1)?;
+        }
+        LuaType::Union(union) => {
+            for typ in union.into_vec() {
+                collect_template_literal_pieces(db, &typ, pieces, depth + 1)?;
+            }
+        }
+        LuaType::MultiLineUnion(multi_union) => {
+            collect_template_literal_pieces(db, &multi_union.to_union(), pieces, depth + 1)?;
+        }
+        // a nested template that is not a set of literals matches like `string`
+        LuaType::TemplateLiteral(template) => {
+            match evaluate_template_literal_at(db, template, depth + 1) {
+                Some(typ) => collect_template_literal_pieces(db, &typ, pieces, depth + 1)?,
+                None => pieces.push(TemplateLiteralPiece::String),
+            }
+        }
+        LuaType::Never => {}
+        _ => return None,
+    }
+
+    Some(())
+}
@@ -1,5 +1,7 @@
 mod instantiate_func_generic;
+mod instantiate_mapped;
 mod instantiate_special_generic;
+mod instantiate_template_literal;
 
 use std::{collections::HashMap, ops::Deref};
 
@@ -13,7 +15,13 @@ use crate::{
 
 use super::type_substitutor::{SubstitutorValue, TypeSubstitutor};
 pub use instantiate_func_generic::{build_self_type, infer_self_type, instantiate_func_generic};
-pub use instantiate_special_generic::instantiate_alias_call;
+pub use instantiate_mapped::{
+    evaluate_mapped, get_mapped_field_key, get_mapped_keys, instantiate_mapped_value,
+};
+pub use instantiate_special_generic::{evaluate_alias_call, instantiate_alias_call};
+pub use instantiate_template_literal::{
+    TemplateLiteralPiece, evaluate_template_literal, get_template_literal_pieces,
+};
 
 pub fn instantiate_type_generic(
     db: &DbIndex,
@@ -36,6 +44,10 @@ pub fn instantiate_type_generic(
         LuaType::TplRef(tpl) => instantiate_tpl_ref(db, tpl, substitutor),
         LuaType::Signature(sig_id) => instantiate_signature(db, sig_id, substitutor),
         LuaType::Call(alias_call) => instantiate_alias_call(db, alias_call, substitutor),
+        LuaType::Mapped(mapped) => instantiate_mapped::instantiate_mapped(db, mapped, substitutor),
+        LuaType::TemplateLiteral(template) => {
+            instantiate_template_literal::instantiate_template_literal(db, template, substitutor)
+        }
         LuaType::Variadic(variadic) => instantiate_variadic_type(db, variadic, substitutor),
         LuaType::SelfInfer => {
             if let Some(typ) = substitutor.get_self_type() {
@@ -88,6 +88,7 @@ pub fn get_tpl_ref_extend_type(
                     }
                     None
                 }
+                GenericTplId::Mapped(_) => None,
             }
         }
         LuaType::Union(union_type) => {
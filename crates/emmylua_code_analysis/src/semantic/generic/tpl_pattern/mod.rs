@@ -8,9 +8,9 @@ use rowan::NodeOrToken;
 use smol_str::SmolStr;
 
 use crate::{
-    InferFailReason, LuaFunctionType, LuaMemberInfo, LuaMemberKey, LuaMemberOwner, LuaObjectType,
-    LuaSemanticDeclId, LuaTupleType, LuaUnionType, SemanticDeclLevel, VariadicType,
-    check_type_compact,
+    InferFailReason, LuaAliasCallKind, LuaAliasCallType, LuaFunctionType, LuaMemberInfo,
+    LuaMemberKey, LuaMemberOwner, LuaObjectType, LuaSemanticDeclId, LuaTemplateLiteralType,
+    LuaTupleType, LuaUnionType, SemanticDeclLevel, VariadicType, check_type_compact,
     db_index::{DbIndex, LuaGenericType, LuaType},
     infer_node_semantic_decl,
     semantic::{
@@ -159,12 +159,76 @@ pub fn tpl_pattern_match(
         LuaType::Object(obj) => {
             object_tpl_pattern_match(context, obj, &target)?;
         }
+        LuaType::Call(alias_call) => {
+            alias_call_tpl_pattern_match(context, alias_call, &target)?;
+        }
+        LuaType::TemplateLiteral(template) => {
+            if let LuaType::StringConst(s) | LuaType::DocStringConst(s) = &target {
+                template_literal_tpl_pattern_match(context, template, s);
+            }
+        }
         _ => {}
     }
 
     Ok(())
 }
 
+/// `` `on_${T}` `` captures the text between the literal prefix and suffix for `T`.
+fn template_literal_tpl_pattern_match(
+    context: &mut TplContext,
+    template: &LuaTemplateLiteralType,
+    text: &str,
+) -> Option<()> {
+    let parts = template.get_parts();
+    let index = parts.iter().position(|part| part.contain_tpl())?;
+    let LuaType::TplRef(tpl) = &parts[index] else {
+        return None;
+    };
+    if !tpl.get_tpl_id().is_func() {
+        return None;
+    }
+
+    let get_text = |parts: &[LuaType]| {
+        parts
+            .iter()
+            .map(|part| match part {
+                LuaType::DocStringConst(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect::<Option<String>>()
+    };
+    let prefix = get_text(&parts[..index])?;
+    let suffix = get_text(&parts[index + 1..])?;
+    let captured = text.strip_prefix(&prefix)?.strip_suffix(&suffix)?;
+    context.substitutor.insert_type(
+        tpl.get_tpl_id(),
+        LuaType::DocStringConst(SmolStr::new(captured).into()),
+    );
+    Some(())
+}
+
+/// `std.Awaited<T>`, `std.Partial<T>`, `std.Required<T>` and `std.Readonly<T>` keep the shape of
+/// `T`, so `T` is matched against the target itself.
+fn alias_call_tpl_pattern_match(
+    context: &mut TplContext,
+    alias_call: &LuaAliasCallType,
+    target: &LuaType,
+) -> TplPatternMatchResult {
+    match (
+        alias_call.get_call_kind(),
+        alias_call.get_operands().as_slice(),
+    ) {
+        (
+            LuaAliasCallKind::Awaited
+            | LuaAliasCallKind::Partial
+            | LuaAliasCallKind::Required
+            | LuaAliasCallKind::Readonly,
+            [operand],
+        ) => tpl_pattern_match(context, operand, target),
+        _ => Ok(()),
+    }
+}
+
 fn constant_decay(typ: LuaType) -> LuaType {
     match &typ {
         LuaType::FloatConst(_) => LuaType::Number,
@@ -10,7 +10,7 @@ use smol_str::SmolStr;
 
 use crate::{
     CacheEntry, GenericTpl, InFiled, InferGuardRef, LuaArrayLen, LuaArrayType, LuaDeclOrMemberId,
-    LuaInferCache, LuaInstanceType, LuaMemberOwner, LuaOperatorOwner, TypeOps,
+    LuaInferCache, LuaInstanceType, LuaMappedType, LuaMemberOwner, LuaOperatorOwner, TypeOps,
     db_index::{
         DbIndex, LuaGenericType, LuaIntersectionType, LuaMemberKey, LuaObjectType,
         LuaOperatorMetaMethod, LuaTupleType, LuaType, LuaTypeDeclId, LuaUnionType,
@@ -18,13 +18,16 @@ use crate::{
     enum_variable_is_param, get_tpl_ref_extend_type,
     semantic::{
         InferGuard,
-        generic::{TypeSubstitutor, instantiate_type_generic},
+        generic::{
+            TypeSubstitutor, evaluate_alias_call, get_mapped_field_key, get_mapped_keys,
+            instantiate_mapped_value, instantiate_type_generic,
+        },
         infer::{
             VarRefId,
             infer_name::get_name_expr_var_ref_id,
             narrow::{get_var_expr_var_ref_id, infer_expr_narrow_type},
         },
-        member::get_buildin_type_map_type_id,
+        member::{get_alias_call_origin, get_buildin_type_map_type_id},
         type_check::{self, check_type_compact},
     },
 };
@@ -189,6 +192,11 @@ pub fn infer_member_by_member_key(
         LuaType::Namespace(ns) => infer_namespace_member(db, cache, ns, index_expr),
         LuaType::Array(array_type) => infer_array_member(db, cache, array_type, index_expr),
         LuaType::TplRef(tpl) => infer_tpl_ref_member(db, cache, tpl, index_expr, infer_guard),
+        LuaType::Call(alias_call) => {
+            let typ = evaluate_alias_call(db, alias_call).ok_or(InferFailReason::FieldNotFound)?;
+            infer_member_by_member_key(db, cache, &typ, index_expr, infer_guard)
+        }
+        LuaType::Mapped(mapped) => infer_mapped_member(db, cache, mapped, index_expr),
         _ => Err(InferFailReason::FieldNotFound),
     }
 }
@@ -619,6 +627,39 @@ fn infer_object_member(
     Err(InferFailReason::FieldNotFound)
 }
 
+/// Only the value of the matching key is instantiated, keys that are not literals act like the
+/// index access of an object.
+fn infer_mapped_member(
+    db: &DbIndex,
+    cache: &mut LuaInferCache,
+    mapped: &LuaMappedType,
+    index_expr: LuaIndexMemberExpr,
+) -> InferResult {
+    let index_key = index_expr.get_index_key().ok_or(InferFailReason::None)?;
+    let member_key = LuaMemberKey::from_index_key(db, cache, &index_key)?;
+    let mut index_accesses = Vec::new();
+    for key in get_mapped_keys(db, mapped) {
+        match get_mapped_field_key(db, mapped, &key) {
+            Some(field_key) if field_key == member_key => {
+                return Ok(instantiate_mapped_value(db, mapped, &key));
+            }
+            Some(LuaMemberKey::ExprType(key_type)) => index_accesses.push((key_type, key)),
+            _ => {}
+        }
+    }
+
+    for (key_type, key) in index_accesses {
+        let value_type = instantiate_mapped_value(db, mapped, &key);
+        match infer_index_metamethod(db, cache, &index_key, &key_type, &value_type) {
+            Ok(typ) => return Ok(typ),
+            Err(InferFailReason::FieldNotFound) => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(InferFailReason::FieldNotFound)
+}
+
 fn infer_index_metamethod(
     db: &DbIndex,
     cache: &mut LuaInferCache,
@@ -737,6 +778,9 @@ fn infer_generic_member(
 
     let generic_params = generic_type.get_params();
     let substitutor = TypeSubstitutor::from_type_array(generic_params.clone());
+    if let Some(origin) = get_alias_call_origin(db, &base_type, &substitutor) {
+        return infer_member_by_member_key(db, cache, &origin, index_expr, infer_guard);
+    }
 
     if let LuaType::Ref(base_type_decl_id) = &base_type {
         let result = infer_generic_members_from_super_generics(
@@ -818,6 +862,10 @@ pub fn infer_member_by_operator(
             let base = inst.get_base();
             infer_member_by_operator(db, cache, base, index_expr, infer_guard)
         }
+        LuaType::Call(alias_call) => {
+            let typ = evaluate_alias_call(db, alias_call).ok_or(InferFailReason::FieldNotFound)?;
+            infer_member_by_operator(db, cache, &typ, index_expr, infer_guard)
+        }
         _ => Err(InferFailReason::FieldNotFound),
     }
 }
@@ -534,18 +534,17 @@ fn maybe_field_literal_eq_narrow(
 
     let antecedent_flow_id = get_single_antecedent(tree, flow_node)?;
     let left_type = get_type_at_flow(db, tree, cache, root, var_ref_id, antecedent_flow_id)?;
-    let LuaType::Union(union_type) = left_type else {
+    let mut union_types = Vec::new();
+    collect_union_variants(db, &left_type, &mut union_types, 0);
+    if union_types.is_empty() {
         return Ok(ResultTypeOrContinue::Continue);
-    };
-
-    cache
-        .narrow_by_literal_stop_position_cache
-        .insert(syntax_id);
+    }
 
     let right_type = infer_expr(db, cache, LuaExpr::LiteralExpr(literal_expr))?;
     let index = LuaIndexMemberExpr::IndexExpr(index_expr);
-    let mut opt_result = None;
-    let mut union_types = union_type.into_vec();
+    // variants whose field is exactly the literal, and variants whose field may be it
+    let mut matched = Vec::new();
+    let mut maybe_matched = Vec::new();
     for (i, sub_type) in union_types.iter().enumerate() {
         let member_type = match infer_member_by_member_key(
             db,
@@ -558,26 +557,91 @@ fn maybe_field_literal_eq_narrow(
             Err(_) => continue, // If we cannot infer the member type, skip this type
         };
         if const_type_eq(&member_type, &right_type) {
-            // If the right type matches the member type, we can narrow it
-            opt_result = Some(i);
+            matched.push(i);
+        } else if let LuaType::Union(member_union) = &member_type
+            && member_union
+                .into_vec()
+                .iter()
+                .any(|member| const_type_eq(member, &right_type))
+        {
+            maybe_matched.push(i);
         }
     }
 
-    match condition_flow {
-        InferConditionFlow::TrueCondition => {
-            if let Some(i) = opt_result {
-                return Ok(ResultTypeOrContinue::Result(union_types[i].clone()));
-            }
-        }
+    // a single variant is only narrowed once its tag is known, down to `never` in the last else
+    if union_types.len() > 1 || !matched.is_empty() {
+        cache
+            .narrow_by_literal_stop_position_cache
+            .insert(syntax_id);
+    }
+    if matched.is_empty() && (maybe_matched.is_empty() || union_types.len() == 1) {
+        return Ok(ResultTypeOrContinue::Continue);
+    }
+
+    let result_type = match condition_flow {
+        InferConditionFlow::TrueCondition => LuaType::from_vec(
+            union_types
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| matched.contains(i) || maybe_matched.contains(i))
+                .map(|(_, typ)| typ)
+                .collect(),
+        ),
         InferConditionFlow::FalseCondition => {
-            if let Some(i) = opt_result {
-                union_types.remove(i);
-                return Ok(ResultTypeOrContinue::Result(LuaType::from_vec(union_types)));
+            let rest: Vec<_> = union_types
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| !matched.contains(i))
+                .map(|(_, typ)| typ)
+                .collect();
+            // every variant was handled, like the final `else` of an exhaustive chain
+            if rest.is_empty() {
+                LuaType::Never
+            } else {
+                LuaType::from_vec(rest)
             }
         }
+    };
+
+    Ok(ResultTypeOrContinue::Result(result_type))
+}
+
+/// The variants of a union, aliases of unions are expanded so `Shape` narrows like
+/// `Circle | Square`.
+fn collect_union_variants(db: &DbIndex, typ: &LuaType, variants: &mut Vec<LuaType>, depth: usize) {
+    if depth > 10 {
+        variants.push(typ.clone());
+        return;
     }
 
-    Ok(ResultTypeOrContinue::Continue)
+    match typ {
+        LuaType::Union(union) => {
+            for sub_type in union.into_vec() {
+                collect_union_variants(db, &sub_type, variants, depth + 1);
+            }
+        }
+        LuaType::MultiLineUnion(multi_union) => {
+            collect_union_variants(db, &multi_union.to_union(), variants, depth + 1);
+        }
+        LuaType::Ref(type_decl_id) => {
+            if let Some(origin) = db
+                .get_type_index()
+                .get_type_decl(type_decl_id)
+                .filter(|decl| decl.is_alias())
+                .and_then(|decl| decl.get_alias_origin(db, None))
+                && matches!(origin, LuaType::Union(_) | LuaType::MultiLineUnion(_))
+            {
+                collect_union_variants(db, &origin, variants, depth + 1);
+            } else if !variants.contains(typ) {
+                variants.push(typ.clone());
+            }
+        }
+        _ => {
+            if !variants.contains(typ) {
+                variants.push(typ.clone());
+            }
+        }
+    }
 }
 
 fn const_type_eq(left_type: &LuaType, right_type: &LuaType) -> bool {
@@ -4,11 +4,14 @@ use smol_str::SmolStr;
 
 use crate::{
     DbIndex, FileId, InferGuardRef, LuaGenericType, LuaInstanceType, LuaIntersectionType,
-    LuaMemberKey, LuaMemberOwner, LuaObjectType, LuaSemanticDeclId, LuaTupleType, LuaType,
-    LuaTypeDeclId, LuaUnionType,
+    LuaMappedType, LuaMemberKey, LuaMemberOwner, LuaObjectType, LuaSemanticDeclId, LuaTupleType,
+    LuaType, LuaTypeDeclId, LuaUnionType,
     semantic::{
         InferGuard,
-        generic::{TypeSubstitutor, instantiate_type_generic},
+        generic::{
+            TypeSubstitutor, evaluate_alias_call, get_mapped_field_key, get_mapped_keys,
+            instantiate_mapped_value, instantiate_type_generic,
+        },
     },
 };
 
@@ -95,6 +98,11 @@ fn find_members_guard(
         LuaType::Global => find_global_members(db, filter),
         LuaType::Instance(inst) => find_instance_members(db, inst, infer_guard, filter),
         LuaType::Namespace(ns) => find_namespace_members(db, ns, filter),
+        LuaType::Call(alias_call) => {
+            let typ = evaluate_alias_call(db, alias_call)?;
+            find_members_guard(db, &typ, infer_guard, filter)
+        }
+        LuaType::Mapped(mapped) => find_mapped_members(db, mapped, filter),
         _ => None,
     }
 }
@@ -283,6 +291,35 @@ fn find_object_members(
     Some(members)
 }
 
+/// The value of a field is only instantiated once its key passes the filter.
+fn find_mapped_members(
+    db: &DbIndex,
+    mapped: &LuaMappedType,
+    filter: &FindMemberFilter,
+) -> FindMembersResult {
+    let mut members = Vec::new();
+    for key in get_mapped_keys(db, mapped) {
+        let Some(field_key) = get_mapped_field_key(db, mapped, &key) else {
+            continue;
+        };
+        if should_include_member(&field_key, filter) {
+            members.push(LuaMemberInfo {
+                property_owner_id: None,
+                key: field_key,
+                typ: instantiate_mapped_value(db, mapped, &key),
+                feature: None,
+                overload_index: None,
+            });
+
+            if should_stop_collecting(members.len(), filter) {
+                break;
+            }
+        }
+    }
+
+    Some(members)
+}
+
 fn find_union_members(
     db: &DbIndex,
     union_type: &LuaUnionType,
@@ -398,6 +435,26 @@ fn find_generic_members_from_super_generics(
     }
 }
 
+/// The instantiated origin of a generic alias whose origin is an alias call or a mapped type.
+pub(crate) fn get_alias_call_origin(
+    db: &DbIndex,
+    base_type: &LuaType,
+    substitutor: &TypeSubstitutor,
+) -> Option<LuaType> {
+    let LuaType::Ref(type_decl_id) = base_type else {
+        return None;
+    };
+    let type_decl = db.get_type_index().get_type_decl(type_decl_id)?;
+    if !matches!(
+        type_decl.get_alias_ref(),
+        Some(LuaType::Call(_) | LuaType::Mapped(_))
+    ) {
+        return None;
+    }
+
+    type_decl.get_alias_origin(db, Some(substitutor))
+}
+
 fn find_generic_members(
     db: &DbIndex,
     generic_type: &LuaGenericType,
@@ -405,10 +462,14 @@ fn find_generic_members(
     filter: &FindMemberFilter,
 ) -> FindMembersResult {
     let base_type = generic_type.get_base_type();
-    let mut members = find_members_guard(db, &base_type, infer_guard, filter)?;
-
     let generic_params = generic_type.get_params();
     let substitutor = TypeSubstitutor::from_type_array(generic_params.clone());
+    // `---@alias Patch<T> std.Partial<T>`, the call is evaluated after the instantiation
+    if let Some(origin) = get_alias_call_origin(db, &base_type, &substitutor) {
+        return find_members_guard(db, &origin, infer_guard, filter);
+    }
+
+    let mut members = find_members_guard(db, &base_type, infer_guard, filter)?;
     for info in members.iter_mut() {
         info.typ = instantiate_type_generic(db, &info.typ, &substitutor);
     }
@@ -11,6 +11,7 @@ use crate::{
 };
 use emmylua_parser::{LuaAssignStat, LuaAstNode, LuaSyntaxKind, LuaTableExpr, LuaTableField};
 pub use find_index::find_index_operations;
+pub(crate) use find_members::get_alias_call_origin;
 pub use find_members::{find_members, find_members_with_key};
 pub use get_member_map::get_member_map;
 pub use infer_raw_member::infer_raw_member_type;
@@ -19,6 +19,7 @@ use type_check_guard::TypeCheckGuard;
 
 use crate::{
     db_index::{DbIndex, LuaType},
+    evaluate_alias_call, evaluate_mapped, evaluate_template_literal,
     semantic::type_check::type_check_context::TypeCheckContext,
 };
 pub use sub_type::is_sub_type_of;
@@ -92,6 +93,7 @@ fn check_general_type_compact(
         | LuaType::TplRef(_)
         | LuaType::StrTplRef(_)
         | LuaType::ConstTplRef(_)
+        | LuaType::TemplateLiteral(_)
         | LuaType::Namespace(_)
         | LuaType::Variadic(_)
         | LuaType::Language(_) => {
@@ -128,8 +130,20 @@ fn check_general_type_compact(
             check_complex_type_compact(context, source, compact_type, check_guard)
         }
 
-        // need think how to do that
-        LuaType::Call(_) => Ok(()),
+        // utility types like `std.Partial<T>` check as the type they evaluate to
+        LuaType::Call(alias_call) => match evaluate_alias_call(context.db, alias_call) {
+            Some(typ) => {
+                check_general_type_compact(context, &typ, compact_type, check_guard.next_level()?)
+            }
+            None => Ok(()),
+        },
+        // a mapped type checks as the object its keys expand to
+        LuaType::Mapped(mapped) => match evaluate_mapped(context.db, mapped) {
+            Some(typ) => {
+                check_general_type_compact(context, &typ, compact_type, check_guard.next_level()?)
+            }
+            None => Ok(()),
+        },
 
         // generic type
         LuaType::Generic(generic) => {
@@ -204,6 +218,9 @@ fn escape_type(db: &DbIndex, typ: &LuaType) -> Option<LuaType> {
             return Some(union);
         }
         LuaType::TypeGuard(_) => return Some(LuaType::Boolean),
+        LuaType::TemplateLiteral(template) => return evaluate_template_literal(db, template),
+        LuaType::Call(alias_call) => return evaluate_alias_call(db, alias_call),
+        LuaType::Mapped(mapped) => return evaluate_mapped(db, mapped),
         _ => {}
     }
 
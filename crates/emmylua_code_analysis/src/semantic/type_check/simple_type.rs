@@ -1,7 +1,8 @@
 use std::ops::Deref;
 
 use crate::{
-    DbIndex, LuaType, LuaTypeDeclId, VariadicType,
+    DbIndex, LuaType, LuaTypeDeclId, TemplateLiteralPiece, VariadicType, evaluate_template_literal,
+    get_template_literal_pieces,
     semantic::type_check::{is_sub_type_of, type_check_context::TypeCheckContext},
 };
 
@@ -74,6 +75,7 @@ pub fn check_simple_type_compact(
             | LuaType::StringConst(_)
             | LuaType::DocStringConst(_)
             | LuaType::StrTplRef(_)
+            | LuaType::TemplateLiteral(_)
             | LuaType::Language(_) => {
                 return Ok(());
             }
@@ -233,6 +235,35 @@ pub fn check_simple_type_compact(
                 return Ok(());
             }
         }
+        LuaType::TemplateLiteral(template) => {
+            if let Some(typ) = evaluate_template_literal(context.db, template) {
+                return check_general_type_compact(
+                    context,
+                    &typ,
+                    compact_type,
+                    check_guard.next_level()?,
+                );
+            }
+
+            match compact_type {
+                LuaType::StringConst(s) | LuaType::DocStringConst(s) => {
+                    let Some(pieces) = get_template_literal_pieces(context.db, template) else {
+                        return Ok(());
+                    };
+                    if is_template_literal_match(&pieces, s) {
+                        return Ok(());
+                    }
+
+                    return Err(TypeCheckFailReason::TypeNotMatch);
+                }
+                LuaType::TemplateLiteral(compact_template) => {
+                    if template == compact_template {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
         LuaType::TplRef(_) | LuaType::ConstTplRef(_) => return Ok(()),
         LuaType::Namespace(source_namespace) => {
             if let LuaType::Namespace(compact_namespace) = compact_type
@@ -278,6 +309,36 @@ pub fn check_simple_type_compact(
     Err(TypeCheckFailReason::TypeNotMatch)
 }
 
+/// Whether `text` can be split so each piece of the template literal matches in turn.
+fn is_template_literal_match(pieces: &[Vec<TemplateLiteralPiece>], text: &str) -> bool {
+    let Some((first, rest)) = pieces.split_first() else {
+        return text.is_empty();
+    };
+
+    first.iter().any(|piece| match piece {
+        TemplateLiteralPiece::Literal(literal) => text
+            .strip_prefix(literal.as_str())
+            .is_some_and(|tail| is_template_literal_match(rest, tail)),
+        _ => text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([text.len()])
+            .any(|end| {
+                is_template_piece_match(piece, &text[..end])
+                    && is_template_literal_match(rest, &text[end..])
+            }),
+    })
+}
+
+fn is_template_piece_match(piece: &TemplateLiteralPiece, text: &str) -> bool {
+    match piece {
+        TemplateLiteralPiece::Literal(literal) => literal == text,
+        TemplateLiteralPiece::String => true,
+        TemplateLiteralPiece::Integer => text.parse::<i64>().is_ok(),
+        TemplateLiteralPiece::Number => text.parse::<f64>().is_ok_and(|n| n.is_finite()),
+    }
+}
+
 fn get_alias_real_type<'a>(
     db: &'a DbIndex,
     compact_type: &'a LuaType,
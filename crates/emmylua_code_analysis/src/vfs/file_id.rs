@@ -1,7 +1,11 @@
-use std::cmp;
+use std::{cell::RefCell, cmp, collections::HashMap};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+thread_local! {
+    static FILE_ID_REMAP: RefCell<Option<FileIdRemap>> = const { RefCell::new(None) };
+}
+
 #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
 pub struct FileId {
     pub id: u32,
@@ -22,7 +26,7 @@ impl<'de> Deserialize<'de> for FileId {
         D: Deserializer<'de>,
     {
         let id = u32::deserialize(deserializer)?;
-        Ok(FileId { id })
+        Ok(FileId::from_cached(id))
     }
 }
 
@@ -32,6 +36,56 @@ impl FileId {
     }
 
     pub const VIRTUAL: FileId = FileId { id: u32::MAX };
+
+    /// The id of a deserialized file, remapped while a [`FileIdRemap`] is applied.
+    pub(crate) fn from_cached(id: u32) -> Self {
+        FILE_ID_REMAP.with(|remap| match remap.borrow_mut().as_mut() {
+            Some(remap) => FileId { id: remap.get(id) },
+            None => FileId { id },
+        })
+    }
+}
+
+/// Maps the file ids of an index written by another process to the ids the vfs gave the same
+/// paths in this one, the vfs hands out ids in the order it sees the files.
+#[derive(Debug)]
+pub(crate) struct FileIdRemap {
+    ids: HashMap<u32, u32>,
+    next_id: u32,
+}
+
+impl FileIdRemap {
+    /// Ids without a mapping, like those of removed files, get fresh ids from `next_id` on.
+    pub fn new(ids: HashMap<u32, u32>, next_id: u32) -> Self {
+        Self { ids, next_id }
+    }
+
+    /// Run `f` with every file id deserialized on this thread remapped.
+    pub fn apply<T>(self, f: impl FnOnce() -> T) -> (T, Self) {
+        FILE_ID_REMAP.with(|remap| *remap.borrow_mut() = Some(self));
+        let result = f();
+        let remap = FILE_ID_REMAP
+            .with(|remap| remap.borrow_mut().take())
+            .expect("file id remap applied");
+        (result, remap)
+    }
+
+    /// The first id not handed out yet.
+    pub fn get_next_id(&self) -> u32 {
+        self.next_id
+    }
+
+    fn get(&mut self, id: u32) -> u32 {
+        if id == FileId::VIRTUAL.id {
+            return id;
+        }
+
+        *self.ids.entry(id).or_insert_with(|| {
+            let new_id = self.next_id;
+            self.next_id += 1;
+            new_id
+        })
+    }
 }
 
 impl From<u32> for FileId {
@@ -52,7 +106,7 @@ impl cmp::Ord for FileId {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InFiled<N> {
     pub file_id: FileId,
     pub value: N,
@@ -96,6 +150,27 @@ mod tests {
         assert_eq!(infiled.value, "test_value");
     }
 
+    #[test]
+    fn test_file_id_remap() {
+        let remap = FileIdRemap::new([(1, 5), (2, 1)].into_iter().collect(), 10);
+        let (ids, remap) = remap.apply(|| {
+            serde_json::from_str::<Vec<FileId>>(&format!("[1, 2, 7, 7, {}]", u32::MAX)).unwrap()
+        });
+        assert_eq!(
+            ids,
+            vec![
+                FileId::new(5),
+                FileId::new(1),
+                FileId::new(10),
+                FileId::new(10),
+                FileId::VIRTUAL
+            ]
+        );
+        assert_eq!(remap.get_next_id(), 11);
+        let id: FileId = serde_json::from_str("1").unwrap();
+        assert_eq!(id, FileId::new(1));
+    }
+
     #[test]
     fn test_file_id_deserialization_error() {
         // Provide an invalid JSON value for FileId to trigger an error.
@@ -102,3 +102,48 @@ pub fn read_file_with_encoding(path: &Path, encoding: &str) -> Option<String> {
 
     Some(content.to_string())
 }
+
+/// Write `content` back in the encoding the workspace files are read with. Reading removes the
+/// byte order mark, so the one of the existing file is written again.
+pub fn write_file_with_encoding(path: &Path, content: &str, encoding: &str) -> std::io::Result<()> {
+    let encoding = Encoding::for_label(encoding.as_bytes()).unwrap_or(UTF_8);
+    let (bytes, output_encoding, _) = encoding.encode(content);
+    let mut output = Vec::with_capacity(bytes.len() + 3);
+    if let Ok(origin_content) = fs::read(path)
+        && let Some((bom_encoding, bom_len)) = Encoding::for_bom(&origin_content)
+        && bom_encoding == output_encoding
+    {
+        output.extend_from_slice(&origin_content[..bom_len]);
+    }
+    output.extend_from_slice(&bytes);
+    fs::write(path, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{read_file_with_encoding, write_file_with_encoding};
+
+    #[test]
+    fn test_write_keeps_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bom.lua");
+        fs::write(&path, b"\xEF\xBB\xBFlocal a = 1\n").unwrap();
+
+        let content = read_file_with_encoding(&path, "utf-8").unwrap();
+        assert_eq!(content, "local a = 1\n");
+        write_file_with_encoding(&path, "local a = 2\n", "utf-8").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"\xEF\xBB\xBFlocal a = 2\n");
+    }
+
+    #[test]
+    fn test_write_without_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.lua");
+        fs::write(&path, b"local a = 1\n").unwrap();
+
+        write_file_with_encoding(&path, "local a = 2\n", "utf-8").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"local a = 2\n");
+    }
+}
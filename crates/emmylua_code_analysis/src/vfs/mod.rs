@@ -6,9 +6,12 @@ mod virtual_url;
 
 pub use document::LuaDocument;
 use emmylua_parser::{LineIndex, LuaParseError, LuaParser, LuaSyntaxTree};
+pub(crate) use file_id::FileIdRemap;
 pub use file_id::{FileId, InFiled};
 pub use file_uri_handler::{file_path_to_uri, uri_to_file_path};
-pub use loader::{LuaFileInfo, load_workspace_files, read_file_with_encoding};
+pub use loader::{
+    LuaFileInfo, load_workspace_files, read_file_with_encoding, write_file_with_encoding,
+};
 use lsp_types::Uri;
 use rowan::NodeCache;
 use std::collections::HashMap;
@@ -69,6 +72,21 @@ impl Vfs {
         }
     }
 
+    /// The id the next new file gets.
+    pub(crate) fn get_next_file_id(&self) -> FileId {
+        FileId {
+            id: self.file_data.len() as u32,
+        }
+    }
+
+    /// Hand out ids up to `next_id` without files, the restored index cache may still hold data of
+    /// removed files under them until they are analyzed again.
+    pub(crate) fn reserve_file_ids(&mut self, next_id: FileId) {
+        while self.file_data.len() < next_id.id as usize {
+            self.file_data.push(None);
+        }
+    }
+
     pub fn get_file_id(&self, uri: &Uri) -> Option<FileId> {
         let path = uri_to_file_path(uri)?;
         self.file_id_map.get(&path).map(|&id| FileId { id })
@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+
+use emmylua_parser::LuaSyntaxId;
+
+use crate::{
+    format::{
+        TokenExpected,
+        token_stream::{AlignCandidate, FormatToken, IndentRule, TokenStream},
+    },
+    styles::LuaCodeStyle,
+};
+
+/// Formatted lines replacing the source lines `start_line..=end_line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedRange {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+}
+
+/// Formatted lines of a run of whole source lines which is formatted independently of the lines
+/// around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineGroup {
+    pub range: FormattedRange,
+    /// Output line of the first formatted line
+    pub formatted_line: usize,
+}
+
+/// Places the tokens of a `TokenStream` on lines. Groups are broken one line at a time until every
+/// line fits in `max_line_width` or nothing is left to break.
+pub struct Layout<'a> {
+    stream: &'a TokenStream,
+    style: &'a LuaCodeStyle,
+    broken: Vec<bool>,
+    padding: HashMap<usize, usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct Rendered {
+    pub text: String,
+    /// Output line of the start of every token
+    lines: Vec<usize>,
+    /// Output line of the end of every token
+    end_lines: Vec<usize>,
+    columns: Vec<usize>,
+    starts_line: Vec<bool>,
+    line_starts: Vec<usize>,
+    line_widths: Vec<usize>,
+}
+
+impl<'a> Layout<'a> {
+    pub fn new(stream: &'a TokenStream, style: &'a LuaCodeStyle) -> Self {
+        Self {
+            stream,
+            style,
+            broken: vec![false; stream.groups.len()],
+            padding: HashMap::new(),
+        }
+    }
+
+    pub fn finish(mut self) -> Rendered {
+        loop {
+            self.padding.clear();
+            let mut rendered = self.render();
+            if self.align(&rendered) {
+                rendered = self.render();
+            }
+            if !self.break_long_lines(&rendered) {
+                return rendered;
+            }
+        }
+    }
+
+    fn render(&self) -> Rendered {
+        let tokens = &self.stream.tokens;
+        let mut forced: Vec<bool> = tokens.iter().map(|token| token.line_break).collect();
+        for (group, broken) in self.stream.groups.iter().zip(&self.broken) {
+            if *broken {
+                for i in &group.breaks {
+                    forced[*i] = true;
+                }
+            }
+        }
+        let mut skipped = vec![false; tokens.len()];
+        let mut appended: HashMap<usize, &str> = HashMap::new();
+        for group in &self.stream.groups {
+            let Some(trailing) = &group.trailing else {
+                continue;
+            };
+            let is_multiline = forced[group.close] || tokens[group.close].newlines_before > 0;
+            match trailing.existing {
+                Some(existing) if !is_multiline => skipped[existing] = true,
+                None if is_multiline => {
+                    appended.insert(trailing.after, &trailing.separator);
+                }
+                _ => {}
+            }
+        }
+
+        let indent_text = self.style.indent_text();
+        let indent_width = self.style.indent_width();
+        let mut writer = Writer {
+            line_ending: self.stream.line_ending,
+            rendered: Rendered {
+                line_starts: vec![0],
+                ..Default::default()
+            },
+            line: 0,
+            column: 0,
+        };
+        let mut line_indent = vec![0; tokens.len()];
+        let mut level = 0;
+        let mut prev: Option<&FormatToken> = None;
+        for (i, token) in tokens.iter().enumerate() {
+            if skipped[i] {
+                let rendered = &mut writer.rendered;
+                rendered.lines.push(writer.line);
+                rendered.columns.push(writer.column);
+                rendered.starts_line.push(false);
+                rendered.end_lines.push(writer.line);
+                line_indent[i] = level;
+                continue;
+            }
+
+            let newlines = if i == 0 {
+                0
+            } else {
+                self.newlines_before(token, forced[i])
+            };
+            if newlines > 0 {
+                for _ in 0..newlines {
+                    writer.new_line();
+                }
+                level = indent_level(token.indent, &line_indent);
+                writer.push(&indent_text.repeat(level), level * indent_width);
+            } else if let Some(prev) = prev {
+                let spaces = self.spaces_before(prev, token) + self.padding.get(&i).unwrap_or(&0);
+                writer.push(&" ".repeat(spaces), spaces);
+            }
+            line_indent[i] = level;
+
+            let rendered = &mut writer.rendered;
+            rendered.lines.push(writer.line);
+            rendered.columns.push(writer.column);
+            rendered.starts_line.push(i == 0 || newlines > 0);
+            writer.push_token(token, &indent_text.repeat(level), level * indent_width);
+            if let Some(separator) = appended.get(&i) {
+                writer.push(separator, separator.chars().count());
+            }
+            writer.rendered.end_lines.push(writer.line);
+            prev = Some(token);
+        }
+
+        if !tokens.is_empty() && self.style.insert_final_newline {
+            writer.new_line();
+        } else {
+            writer.rendered.line_widths.push(writer.column);
+        }
+        writer.rendered
+    }
+
+    fn newlines_before(&self, token: &FormatToken, forced: bool) -> usize {
+        let newlines = if forced {
+            token.newlines_before.max(1)
+        } else {
+            token.newlines_before
+        };
+        if newlines == 0 || token.no_blank_before {
+            newlines.min(1)
+        } else {
+            newlines.min(self.style.max_blank_lines + 1)
+        }
+    }
+
+    fn spaces_before(&self, prev: &FormatToken, token: &FormatToken) -> usize {
+        let spaces = if token.is_comment {
+            // trailing comments keep their column
+            token.spaces_before
+        } else {
+            match token.expected {
+                Some(TokenExpected::Space(n)) => n,
+                Some(TokenExpected::MaxSpace(n)) => token.spaces_before.min(n),
+                None => token.spaces_before.min(1),
+            }
+        };
+        if spaces == 0 && needs_separation(&prev.text, &token.text) {
+            1
+        } else {
+            spaces
+        }
+    }
+
+    /// Pad the `=` of runs of consecutive single line assignments or fields, returns whether
+    /// anything was padded.
+    fn align(&mut self, rendered: &Rendered) -> bool {
+        let mut containers: Vec<LuaSyntaxId> = Vec::new();
+        let mut candidates: HashMap<LuaSyntaxId, Vec<&AlignCandidate>> = HashMap::new();
+        for candidate in &self.stream.aligns {
+            let enabled = if candidate.is_table_field {
+                self.style.align_table_field
+            } else {
+                self.style.align_continuous_assign_statement
+            };
+            let is_single_line = rendered.starts_line[candidate.first]
+                && rendered.lines[candidate.first] == rendered.end_lines[candidate.last];
+            if enabled && is_single_line {
+                candidates
+                    .entry(candidate.container)
+                    .or_insert_with(|| {
+                        containers.push(candidate.container);
+                        Vec::new()
+                    })
+                    .push(candidate);
+            }
+        }
+
+        let mut runs: Vec<Vec<&AlignCandidate>> = Vec::new();
+        for container in &containers {
+            let mut run: Vec<&AlignCandidate> = Vec::new();
+            for candidate in &candidates[container] {
+                let is_next_line = run.last().is_some_and(|last| {
+                    rendered.lines[candidate.first] == rendered.lines[last.first] + 1
+                });
+                if !is_next_line {
+                    runs.push(std::mem::take(&mut run));
+                }
+                run.push(candidate);
+            }
+            runs.push(run);
+        }
+
+        let tokens = &self.stream.tokens;
+        for run in runs {
+            // only keep an alignment the source already has
+            if run.len() < 2
+                || !run
+                    .iter()
+                    .any(|candidate| tokens[candidate.assign].spaces_before > 1)
+            {
+                continue;
+            }
+            let column = |candidate: &&AlignCandidate| rendered.columns[candidate.assign];
+            let target = run.iter().map(column).max().unwrap_or_default();
+            for candidate in &run {
+                let padding = target - column(candidate);
+                if padding > 0 {
+                    self.padding.insert(candidate.assign, padding);
+                }
+            }
+        }
+        !self.padding.is_empty()
+    }
+
+    /// Break the outermost group of every line longer than `max_line_width`, returns whether a
+    /// group was broken.
+    fn break_long_lines(&mut self, rendered: &Rendered) -> bool {
+        let max_line_width = self.style.max_line_width;
+        if max_line_width == 0 {
+            return false;
+        }
+
+        let groups = &self.stream.groups;
+        let mut outermost: HashMap<usize, usize> = HashMap::new();
+        for (i, group) in groups.iter().enumerate() {
+            let line = rendered.lines[group.open];
+            if self.broken[i]
+                || group.hug
+                || rendered.end_lines[group.close] != line
+                || rendered.line_widths[line] <= max_line_width
+            {
+                continue;
+            }
+            outermost
+                .entry(line)
+                .and_modify(|current| {
+                    if group.open < groups[*current].open {
+                        *current = i;
+                    }
+                })
+                .or_insert(i);
+        }
+
+        for i in outermost.values() {
+            self.broken[*i] = true;
+        }
+        !outermost.is_empty()
+    }
+}
+
+impl Rendered {
+    pub fn get_range(
+        &self,
+        stream: &TokenStream,
+        start_line: usize,
+        end_line: usize,
+    ) -> Option<FormattedRange> {
+        let tokens = &stream.tokens;
+        let mut first = tokens
+            .iter()
+            .position(|token| token.end_line >= start_line)?;
+        let mut last = tokens
+            .iter()
+            .rposition(|token| token.start_line <= end_line)?;
+        if first > last {
+            return None;
+        }
+        // extend the range to whole source lines, a multi-line token may share a line with others
+        loop {
+            let line = tokens[first].start_line;
+            let extended = tokens.iter().position(|token| token.end_line >= line)?;
+            if extended == first {
+                break;
+            }
+            first = extended;
+        }
+        loop {
+            let line = tokens[last].end_line;
+            let extended = tokens.iter().rposition(|token| token.start_line <= line)?;
+            if extended == last {
+                break;
+            }
+            last = extended;
+        }
+
+        let start = self.line_starts[self.lines[first]];
+        let end = self
+            .line_starts
+            .get(self.end_lines[last] + 1)
+            .copied()
+            .unwrap_or(self.text.len());
+        Some(FormattedRange {
+            start_line: tokens[first].start_line,
+            end_line: tokens[last].end_line,
+            text: self.text[start..end].to_string(),
+        })
+    }
+
+    /// Split the source lines holding tokens into the smallest groups whose formatted lines do not
+    /// share a line with another group, in order.
+    pub fn get_line_groups(&self, stream: &TokenStream) -> Vec<LineGroup> {
+        let tokens = &stream.tokens;
+        let mut groups = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let first = i;
+            let mut end_line = tokens[i].end_line;
+            let mut formatted_end_line = self.end_lines[i];
+            i += 1;
+            while i < tokens.len()
+                && (tokens[i].start_line <= end_line || self.lines[i] <= formatted_end_line)
+            {
+                end_line = end_line.max(tokens[i].end_line);
+                formatted_end_line = formatted_end_line.max(self.end_lines[i]);
+                i += 1;
+            }
+
+            let start = self.line_starts[self.lines[first]];
+            let end = self
+                .line_starts
+                .get(formatted_end_line + 1)
+                .copied()
+                .unwrap_or(self.text.len());
+            groups.push(LineGroup {
+                range: FormattedRange {
+                    start_line: tokens[first].start_line,
+                    end_line,
+                    text: self.text[start..end].to_string(),
+                },
+                formatted_line: self.lines[first],
+            });
+        }
+        groups
+    }
+}
+
+struct Writer {
+    line_ending: &'static str,
+    rendered: Rendered,
+    line: usize,
+    column: usize,
+}
+
+impl Writer {
+    fn push(&mut self, text: &str, width: usize) {
+        self.rendered.text.push_str(text);
+        self.column += width;
+    }
+
+    fn new_line(&mut self) {
+        self.rendered.line_widths.push(self.column);
+        self.rendered.text.push_str(self.line_ending);
+        self.rendered.line_starts.push(self.rendered.text.len());
+        self.line += 1;
+        self.column = 0;
+    }
+
+    fn push_token(&mut self, token: &FormatToken, indent_text: &str, indent_width: usize) {
+        if !token.text.contains('\n') {
+            self.push(&token.text, token.text.chars().count());
+            return;
+        }
+
+        for (i, part) in token.text.split('\n').enumerate() {
+            if token.is_comment && !token.is_long_comment {
+                // the lines of a comment block follow the indentation of its first line
+                let part = if i == 0 { part.trim_end() } else { part.trim() };
+                if i > 0 {
+                    self.new_line();
+                    if !part.is_empty() {
+                        self.push(indent_text, indent_width);
+                    }
+                }
+                self.push(part, part.chars().count());
+            } else {
+                // long strings and long comments are kept verbatim
+                if i > 0 {
+                    self.rendered.line_widths.push(self.column);
+                    self.rendered.text.push('\n');
+                    self.rendered.line_starts.push(self.rendered.text.len());
+                    self.line += 1;
+                    self.column = 0;
+                }
+                self.push(part, part.trim_end_matches('\r').chars().count());
+            }
+        }
+    }
+}
+
+fn indent_level(rule: IndentRule, line_indent: &[usize]) -> usize {
+    match rule {
+        IndentRule::Block { owner } => owner.map_or(0, |owner| line_indent[owner] + 1),
+        IndentRule::Close { owner } => owner.map_or(0, |owner| line_indent[owner]),
+        IndentRule::Item { open } => line_indent[open] + 1,
+        IndentRule::Continuation { unit } => unit.map_or(0, |unit| line_indent[unit]) + 1,
+    }
+}
+
+/// Whether two tokens written without space between them would be read as other tokens.
+fn needs_separation(prev: &str, next: &str) -> bool {
+    let (Some(last), Some(first)) = (prev.chars().last(), next.chars().next()) else {
+        return false;
+    };
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || !c.is_ascii();
+    if is_word(last) && is_word(first) {
+        return true;
+    }
+    matches!(
+        (last, first),
+        ('-', '-')
+            | ('.', '.')
+            | ('[', '[')
+            | ('[', '=')
+            | ('=', '=')
+            | ('~', '=')
+            | ('<', '<' | '=')
+            | ('>', '>' | '=')
+            | ('/', '/')
+            | (':', ':')
+    ) || (last == '.' && first.is_ascii_digit())
+        || (last.is_ascii_digit() && first == '.')
+}
@@ -1,21 +1,29 @@
-mod formatter_context;
+mod layout;
 mod syntax_node_change;
+mod token_stream;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use emmylua_parser::{LuaAst, LuaAstNode, LuaSyntaxId, LuaTokenKind};
-use rowan::NodeOrToken;
+use emmylua_parser::{LuaAst, LuaSyntaxId};
 
-use crate::format::formatter_context::FormatterContext;
-pub use crate::format::syntax_node_change::{TokenExpected, TokenNodeChange};
+pub use crate::format::{
+    layout::{FormattedRange, LineGroup},
+    syntax_node_change::{TokenExpected, TokenNodeChange},
+};
+use crate::{
+    format::{layout::Layout, token_stream::TokenStream},
+    styles::LuaCodeStyle,
+};
 
 #[allow(unused)]
 #[derive(Debug)]
 pub struct LuaFormatter {
     root: LuaAst,
-    token_changes: HashMap<LuaSyntaxId, TokenNodeChange>,
+    token_changes: HashMap<LuaSyntaxId, Vec<TokenNodeChange>>,
     token_left_expected: HashMap<LuaSyntaxId, TokenExpected>,
     token_right_expected: HashMap<LuaSyntaxId, TokenExpected>,
+    token_line_breaks: HashSet<LuaSyntaxId>,
+    multiline_trailing_separators: HashMap<LuaSyntaxId, String>,
 }
 
 #[allow(unused)]
@@ -26,11 +34,17 @@ impl LuaFormatter {
             token_changes: HashMap::new(),
             token_left_expected: HashMap::new(),
             token_right_expected: HashMap::new(),
+            token_line_breaks: HashSet::new(),
+            multiline_trailing_separators: HashMap::new(),
         }
     }
 
+    /// Changes of a token are applied in the order they are added.
     pub fn add_token_change(&mut self, syntax_id: LuaSyntaxId, change: TokenNodeChange) {
-        self.token_changes.insert(syntax_id, change);
+        self.token_changes
+            .entry(syntax_id)
+            .or_default()
+            .push(change);
     }
 
     pub fn add_token_left_expected(&mut self, syntax_id: LuaSyntaxId, expected: TokenExpected) {
@@ -41,97 +55,104 @@ impl LuaFormatter {
         self.token_right_expected.insert(syntax_id, expected);
     }
 
-    pub fn get_token_change(&self, syntax_id: &LuaSyntaxId) -> Option<&TokenNodeChange> {
-        self.token_changes.get(syntax_id)
+    pub fn get_token_changes(&self, syntax_id: &LuaSyntaxId) -> &[TokenNodeChange] {
+        self.token_changes
+            .get(syntax_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The token always starts a line.
+    pub fn add_token_line_break(&mut self, syntax_id: LuaSyntaxId) {
+        self.token_line_breaks.insert(syntax_id);
+    }
+
+    pub fn has_token_line_break(&self, syntax_id: &LuaSyntaxId) -> bool {
+        self.token_line_breaks.contains(syntax_id)
+    }
+
+    /// `separator` is added after the last field of the table when the table spans several
+    /// lines once formatted, and an existing trailing separator is removed otherwise.
+    pub fn add_multiline_trailing_separator(&mut self, table_id: LuaSyntaxId, separator: String) {
+        self.multiline_trailing_separators
+            .insert(table_id, separator);
+    }
+
+    pub fn get_multiline_trailing_separator(&self, table_id: &LuaSyntaxId) -> Option<&str> {
+        self.multiline_trailing_separators
+            .get(table_id)
+            .map(String::as_str)
+    }
+
+    pub fn get_token_left_expected(&self, syntax_id: &LuaSyntaxId) -> Option<&TokenExpected> {
+        self.token_left_expected.get(syntax_id)
+    }
+
+    pub fn get_token_right_expected(&self, syntax_id: &LuaSyntaxId) -> Option<&TokenExpected> {
+        self.token_right_expected.get(syntax_id)
     }
 
     pub fn get_root(&self) -> LuaAst {
         self.root.clone()
     }
 
-    pub fn get_formatted_text(&self) -> String {
-        let mut context = FormatterContext::new();
-        for node_or_token in self.root.syntax().descendants_with_tokens() {
-            if let NodeOrToken::Token(token) = node_or_token {
-                let token_kind = token.kind().to_token();
-                match (context.current_expected.take(), token_kind) {
-                    (Some(TokenExpected::Space(n)), LuaTokenKind::TkWhitespace) => {
-                        if !context.is_line_first_token {
-                            context.text.push_str(&" ".repeat(n));
-                            continue;
-                        }
-                    }
-                    (Some(TokenExpected::MaxSpace(n)), LuaTokenKind::TkWhitespace) => {
-                        if !context.is_line_first_token {
-                            let white_space_len = token.text().chars().count();
-                            if white_space_len > n {
-                                context.reset_whitespace_to(n);
-                                continue;
-                            }
-                        }
-                    }
-                    (_, LuaTokenKind::TkEndOfLine) => {
-                        // No space expected
-                        context.reset_whitespace();
-                        context.text.push('\n');
-                        context.is_line_first_token = true;
-                        continue;
-                    }
-                    (Some(TokenExpected::Space(n)), _) => {
-                        if !context.is_line_first_token {
-                            context.text.push_str(&" ".repeat(n));
-                        }
-                    }
-                    _ => {}
-                }
-
-                let syntax_id = LuaSyntaxId::from_token(&token);
-                if let Some(expected) = self.token_left_expected.get(&syntax_id) {
-                    match expected {
-                        TokenExpected::Space(n) => {
-                            if !context.is_line_first_token {
-                                context.reset_whitespace();
-                                context.text.push_str(&" ".repeat(*n));
-                            }
-                        }
-                        TokenExpected::MaxSpace(n) => {
-                            if !context.is_line_first_token {
-                                let current_spaces = context.get_last_whitespace_count();
-                                if current_spaces > *n {
-                                    context.reset_whitespace_to(*n);
-                                }
-                            }
-                        }
-                    }
-                }
-
-                if token_kind != LuaTokenKind::TkWhitespace {
-                    context.is_line_first_token = false;
-                }
-
-                if let Some(change) = self.token_changes.get(&syntax_id) {
-                    match change {
-                        TokenNodeChange::Remove => continue,
-                        TokenNodeChange::AddLeft(s) => {
-                            context.text.push_str(s);
-                            context.text.push_str(token.text());
-                        }
-                        TokenNodeChange::AddRight(s) => {
-                            context.text.push_str(token.text());
-                            context.text.push_str(s);
-                        }
-                        TokenNodeChange::ReplaceWith(s) => {
-                            context.text.push_str(s);
-                        }
-                    }
-                } else {
-                    context.text.push_str(token.text());
-                }
-
-                context.current_expected = self.token_right_expected.get(&syntax_id).cloned();
+    pub fn get_formatted_text(&self, style: &LuaCodeStyle) -> String {
+        let stream = TokenStream::new(self);
+        Layout::new(&stream, style).finish().text
+    }
+
+    /// Format the whole text and split it into groups of source lines, see
+    /// `Rendered::get_line_groups`.
+    pub fn get_formatted_line_groups(&self, style: &LuaCodeStyle) -> Vec<LineGroup> {
+        let stream = TokenStream::new(self);
+        Layout::new(&stream, style)
+            .finish()
+            .get_line_groups(&stream)
+    }
+
+    /// Format the whole text once and return the formatted lines of every `(start_line, end_line)`
+    /// source range. The ranges are sorted, and merged where they grow into each other.
+    pub fn get_formatted_ranges(
+        &self,
+        style: &LuaCodeStyle,
+        ranges: &[(usize, usize)],
+    ) -> Vec<FormattedRange> {
+        let stream = TokenStream::new(self);
+        let rendered = Layout::new(&stream, style).finish();
+        let mut ranges = ranges.to_vec();
+        ranges.sort();
+        let mut result: Vec<FormattedRange> = Vec::new();
+        for (start_line, end_line) in ranges {
+            let Some(mut range) = rendered.get_range(&stream, start_line, end_line) else {
+                continue;
+            };
+            if let Some(last) = result.last()
+                && range.start_line <= last.end_line + 1
+            {
+                let start_line = last.start_line;
+                let end_line = range.end_line.max(last.end_line);
+                let Some(merged) = rendered.get_range(&stream, start_line, end_line) else {
+                    continue;
+                };
+                result.pop();
+                range = merged;
             }
+            result.push(range);
         }
+        result
+    }
 
-        context.text
+    /// Format the whole text and return the formatted lines of the source lines
+    /// `start_line..=end_line`, indented as in the whole formatted text.
+    pub fn get_formatted_range(
+        &self,
+        style: &LuaCodeStyle,
+        start_line: usize,
+        end_line: usize,
+    ) -> Option<FormattedRange> {
+        let stream = TokenStream::new(self);
+        Layout::new(&stream, style)
+            .finish()
+            .get_range(&stream, start_line, end_line)
     }
 }
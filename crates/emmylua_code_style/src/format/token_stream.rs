@@ -0,0 +1,555 @@
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaBinaryExpr, LuaKind, LuaStat, LuaSyntaxElement, LuaSyntaxId,
+    LuaSyntaxKind, LuaSyntaxNode, LuaTokenKind,
+};
+use rowan::{NodeOrToken, TextRange, TextSize, WalkEvent};
+
+use crate::format::{LuaFormatter, TokenExpected, TokenNodeChange};
+
+/// How the indentation of a token is computed when it starts a line. The anchors are indexes of
+/// earlier tokens, the indentation is relative to the line they are on.
+#[derive(Debug, Clone, Copy)]
+pub enum IndentRule {
+    /// First token of a statement or comment of a block, one level deeper than the line of the
+    /// statement owning the block, or no indentation for the chunk.
+    Block { owner: Option<usize> },
+    /// `end`, `else`, `until` or a closing bracket, same level as the line of its opener.
+    Close { owner: Option<usize> },
+    /// First token of a table field, argument or parameter, one level deeper than the line of the
+    /// open bracket.
+    Item { open: usize },
+    /// Any other token, one level deeper than the line where its statement or item starts.
+    Continuation { unit: Option<usize> },
+}
+
+#[derive(Debug)]
+pub struct FormatToken {
+    pub text: String,
+    pub is_comment: bool,
+    /// Lines after the first line of a `--[[` comment are kept verbatim
+    pub is_long_comment: bool,
+    /// Line breaks between this token and the previous one in the source
+    pub newlines_before: usize,
+    /// Width of the whitespace between this token and the previous one on the same line
+    pub spaces_before: usize,
+    /// Space expected by the style rules between this token and the previous one
+    pub expected: Option<TokenExpected>,
+    /// The style rules put the token at the start of a line
+    pub line_break: bool,
+    pub indent: IndentRule,
+    /// Blank lines before the token are removed, it follows an opener or closes a block
+    pub no_blank_before: bool,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// Table, argument list or parameter list which is split into one item per line when it does
+/// not fit in the line, or a chain of binary operators of the same priority which is split before
+/// every operator.
+#[derive(Debug)]
+pub struct BreakGroup {
+    pub open: usize,
+    pub close: usize,
+    /// Tokens which start a line once the group is broken
+    pub breaks: Vec<usize>,
+    /// Argument list of a single table or closure, the argument is broken instead
+    pub hug: bool,
+    pub trailing: Option<MultilineTrailing>,
+}
+
+/// Separator after the last field of a table, only written when the table spans several lines.
+#[derive(Debug)]
+pub struct MultilineTrailing {
+    pub separator: String,
+    /// The trailing separator of the source
+    pub existing: Option<usize>,
+    /// Last token of the last field
+    pub after: usize,
+}
+
+/// An `=` which may be aligned with the `=` of the same kind of statement or field on the
+/// neighbouring lines.
+#[derive(Debug)]
+pub struct AlignCandidate {
+    pub container: LuaSyntaxId,
+    pub first: usize,
+    pub assign: usize,
+    pub last: usize,
+    pub is_table_field: bool,
+}
+
+#[derive(Debug)]
+pub struct TokenStream {
+    pub tokens: Vec<FormatToken>,
+    pub groups: Vec<BreakGroup>,
+    pub aligns: Vec<AlignCandidate>,
+    pub line_ending: &'static str,
+}
+
+impl TokenStream {
+    pub fn new(formatter: &LuaFormatter) -> Self {
+        let root = formatter.get_root();
+        let root = root.syntax();
+        let line_ending = if root.text().contains_char('\r') {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        let mut builder = TokenStreamBuilder {
+            formatter,
+            elements: Vec::new(),
+            tokens: Vec::new(),
+            starts: Vec::new(),
+        };
+        builder.collect_tokens(root);
+        let indents: Vec<IndentRule> = builder
+            .elements
+            .iter()
+            .map(|element| builder.indent_rule(element))
+            .collect();
+        for (i, indent) in indents.into_iter().enumerate() {
+            builder.tokens[i].no_blank_before = match indent {
+                IndentRule::Close { .. } => true,
+                IndentRule::Item { open } => open + 1 == i,
+                IndentRule::Block { owner: Some(_) } => builder.is_first_in_block(i),
+                _ => false,
+            };
+            builder.tokens[i].indent = indent;
+        }
+
+        let mut groups = Vec::new();
+        let mut aligns = Vec::new();
+        for node in root.descendants() {
+            match node.kind().to_syntax() {
+                LuaSyntaxKind::TableObjectExpr
+                | LuaSyntaxKind::TableArrayExpr
+                | LuaSyntaxKind::CallArgList
+                | LuaSyntaxKind::ParamList => {
+                    if let Some(group) = builder.break_group(&node) {
+                        groups.push(group);
+                    }
+                }
+                LuaSyntaxKind::BinaryExpr => {
+                    if let Some(group) = builder.binary_break_group(&node) {
+                        groups.push(group);
+                    }
+                }
+                LuaSyntaxKind::LocalStat | LuaSyntaxKind::AssignStat => {
+                    if let Some(parent) = node.parent()
+                        && parent.kind().to_syntax() == LuaSyntaxKind::Block
+                        && let Some(candidate) = builder.align_candidate(&node, &parent, false)
+                    {
+                        aligns.push(candidate);
+                    }
+                }
+                LuaSyntaxKind::TableFieldAssign => {
+                    if let Some(parent) = node.parent()
+                        && let Some(candidate) = builder.align_candidate(&node, &parent, true)
+                    {
+                        aligns.push(candidate);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        TokenStream {
+            tokens: builder.tokens,
+            groups,
+            aligns,
+            line_ending,
+        }
+    }
+}
+
+struct TokenStreamBuilder<'a> {
+    formatter: &'a LuaFormatter,
+    elements: Vec<LuaSyntaxElement>,
+    tokens: Vec<FormatToken>,
+    starts: Vec<TextSize>,
+}
+
+impl TokenStreamBuilder<'_> {
+    fn collect_tokens(&mut self, root: &LuaSyntaxNode) {
+        let mut newlines = 0;
+        let mut spaces = 0;
+        let mut line = 0;
+        let mut prev_id: Option<LuaSyntaxId> = None;
+        let mut preorder = root.preorder_with_tokens();
+        while let Some(event) = preorder.next() {
+            let WalkEvent::Enter(element) = event else {
+                continue;
+            };
+
+            let (text, is_comment, is_long_comment, syntax_id) = match &element {
+                NodeOrToken::Node(node) => {
+                    if node.kind().to_syntax() != LuaSyntaxKind::Comment {
+                        continue;
+                    }
+                    preorder.skip_subtree();
+                    let is_long_comment = node.first_token().is_some_and(|token| {
+                        token.kind() == LuaKind::Token(LuaTokenKind::TkLongCommentStart)
+                    });
+                    (node.text().to_string(), true, is_long_comment, None)
+                }
+                NodeOrToken::Token(token) => {
+                    match token.kind().to_token() {
+                        LuaTokenKind::TkWhitespace => {
+                            spaces += token.text().len();
+                            continue;
+                        }
+                        LuaTokenKind::TkEndOfLine => {
+                            newlines += 1;
+                            line += 1;
+                            spaces = 0;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    let syntax_id = LuaSyntaxId::from_token(token);
+                    let mut text = token.text().to_string();
+                    let mut removed = false;
+                    for change in self.formatter.get_token_changes(&syntax_id) {
+                        match change {
+                            TokenNodeChange::Remove => removed = true,
+                            TokenNodeChange::AddLeft(s) => text.insert_str(0, s),
+                            TokenNodeChange::AddRight(s) => text.push_str(s),
+                            TokenNodeChange::ReplaceWith(s) => text = s.clone(),
+                        }
+                    }
+                    if removed {
+                        continue;
+                    }
+                    (text, false, false, Some(syntax_id))
+                }
+            };
+
+            let expected = match &syntax_id {
+                Some(syntax_id) => self
+                    .formatter
+                    .get_token_left_expected(syntax_id)
+                    .or_else(|| {
+                        prev_id
+                            .as_ref()
+                            .and_then(|prev_id| self.formatter.get_token_right_expected(prev_id))
+                    })
+                    .copied(),
+                None => None,
+            };
+            let start_line = line;
+            line += element.to_string().matches('\n').count();
+            self.starts.push(element.text_range().start());
+            self.tokens.push(FormatToken {
+                text,
+                is_comment,
+                is_long_comment,
+                newlines_before: newlines,
+                spaces_before: spaces,
+                expected,
+                line_break: syntax_id
+                    .as_ref()
+                    .is_some_and(|syntax_id| self.formatter.has_token_line_break(syntax_id)),
+                indent: IndentRule::Continuation { unit: None },
+                no_blank_before: false,
+                start_line,
+                end_line: line,
+            });
+            self.elements.push(element);
+            prev_id = syntax_id;
+            newlines = 0;
+            spaces = 0;
+        }
+    }
+
+    /// Index of the first token at or after `offset`.
+    fn index_at(&self, offset: TextSize) -> Option<usize> {
+        let index = self.starts.partition_point(|start| *start < offset);
+        (index < self.starts.len()).then_some(index)
+    }
+
+    /// Index of the last token inside `range`.
+    fn last_index_in(&self, range: TextRange) -> Option<usize> {
+        let index = self.starts.partition_point(|start| *start < range.end());
+        index.checked_sub(1)
+    }
+
+    fn indent_rule(&self, element: &LuaSyntaxElement) -> IndentRule {
+        let start = element.text_range().start();
+        if let NodeOrToken::Token(token) = element {
+            let parent = token.parent();
+            match token.kind().to_token() {
+                LuaTokenKind::TkEnd | LuaTokenKind::TkUntil => {
+                    return IndentRule::Close {
+                        owner: parent.and_then(|parent| self.owner_index(&parent)),
+                    };
+                }
+                LuaTokenKind::TkElseIf | LuaTokenKind::TkElse => {
+                    return IndentRule::Close {
+                        owner: parent
+                            .and_then(|parent| parent.parent())
+                            .and_then(|if_stat| self.owner_index(&if_stat)),
+                    };
+                }
+                LuaTokenKind::TkRightParen
+                | LuaTokenKind::TkRightBracket
+                | LuaTokenKind::TkRightBrace => {
+                    let open = parent.and_then(|parent| {
+                        parent.children_with_tokens().find(|child| {
+                            matches!(
+                                child.kind().to_token(),
+                                LuaTokenKind::TkLeftParen
+                                    | LuaTokenKind::TkLeftBracket
+                                    | LuaTokenKind::TkLeftBrace
+                            )
+                        })
+                    });
+                    if let Some(open) = open {
+                        return IndentRule::Close {
+                            owner: self.index_at(open.text_range().start()),
+                        };
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut current = element.clone();
+        while let Some(parent) = current.parent() {
+            if parent.kind().to_syntax() == LuaSyntaxKind::Block {
+                let owner = parent.parent().and_then(|owner| {
+                    if owner.kind().to_syntax() == LuaSyntaxKind::Chunk {
+                        None
+                    } else {
+                        self.owner_index(&owner)
+                    }
+                });
+                return IndentRule::Block { owner };
+            }
+            if is_group_container(&parent)
+                && is_group_item(&current)
+                && let Some(NodeOrToken::Token(open)) = parent.first_child_or_token()
+                && matches!(
+                    open.kind().to_token(),
+                    LuaTokenKind::TkLeftParen | LuaTokenKind::TkLeftBrace
+                )
+                && let Some(open) = self.index_at(open.text_range().start())
+            {
+                return IndentRule::Item { open };
+            }
+            if parent.text_range().start() != start {
+                break;
+            }
+            current = NodeOrToken::Node(parent);
+        }
+
+        let unit = element.parent().and_then(|parent| {
+            parent.ancestors().find_map(|node| {
+                let is_unit = LuaStat::can_cast(node.kind().to_syntax())
+                    || node
+                        .parent()
+                        .is_some_and(|parent| is_group_container(&parent));
+                if is_unit && node.text_range().start() < start {
+                    self.index_at(node.text_range().start())
+                } else {
+                    None
+                }
+            })
+        });
+        IndentRule::Continuation { unit }
+    }
+
+    /// Index of the token a block or an `end` is indented after: the first token of the
+    /// statement, or of the function statement declaring a closure.
+    fn owner_index(&self, node: &LuaSyntaxNode) -> Option<usize> {
+        let mut owner = node.clone();
+        if owner.kind().to_syntax() == LuaSyntaxKind::ClosureExpr
+            && let Some(parent) = owner.parent()
+            && matches!(
+                parent.kind().to_syntax(),
+                LuaSyntaxKind::FuncStat | LuaSyntaxKind::LocalFuncStat
+            )
+        {
+            owner = parent;
+        }
+        let start = owner.text_range().start();
+        // the owner may start before the formatted node
+        if self.starts.first().is_none_or(|first| *first > start) {
+            return None;
+        }
+        self.index_at(start)
+    }
+
+    fn is_first_in_block(&self, index: usize) -> bool {
+        self.elements[index]
+            .parent()
+            .and_then(|parent| {
+                parent
+                    .ancestors()
+                    .find(|node| node.kind().to_syntax() == LuaSyntaxKind::Block)
+            })
+            .and_then(|block| self.index_at(block.text_range().start()))
+            .is_some_and(|mut first| {
+                // skip the comment on the line of the statement opening the block
+                while first < index
+                    && self.tokens[first].is_comment
+                    && self.tokens[first].newlines_before == 0
+                {
+                    first += 1;
+                }
+                first == index
+            })
+    }
+
+    fn break_group(&self, node: &LuaSyntaxNode) -> Option<BreakGroup> {
+        let mut open = None;
+        let mut close = None;
+        let mut separators = Vec::new();
+        let mut items = Vec::new();
+        for child in node.children_with_tokens() {
+            match child.kind().to_token() {
+                LuaTokenKind::TkLeftParen | LuaTokenKind::TkLeftBrace => {
+                    open = self.index_at(child.text_range().start())
+                }
+                LuaTokenKind::TkRightParen | LuaTokenKind::TkRightBrace => {
+                    close = self.index_at(child.text_range().start())
+                }
+                LuaTokenKind::TkComma | LuaTokenKind::TkSemicolon => {
+                    separators.extend(self.index_at(child.text_range().start()))
+                }
+                LuaTokenKind::TkWhitespace | LuaTokenKind::TkEndOfLine => {}
+                _ => {
+                    if child.kind().to_syntax() != LuaSyntaxKind::Comment {
+                        items.push(child);
+                    }
+                }
+            }
+        }
+        let (open, close) = (open?, close?);
+        if items.is_empty() || close >= self.tokens.len() {
+            return None;
+        }
+
+        let mut breaks = Vec::new();
+        for position in std::iter::once(open).chain(separators.iter().copied()) {
+            // a trailing comment stays on the line of the separator
+            let next = (position + 1..close)
+                .find(|i| !(self.tokens[*i].is_comment && self.tokens[*i].newlines_before == 0));
+            breaks.push(next.unwrap_or(close));
+        }
+        breaks.push(close);
+        breaks.dedup();
+
+        let hug = node.kind().to_syntax() == LuaSyntaxKind::CallArgList
+            && items.len() == 1
+            && matches!(
+                items[0].kind().to_syntax(),
+                LuaSyntaxKind::TableObjectExpr
+                    | LuaSyntaxKind::TableArrayExpr
+                    | LuaSyntaxKind::ClosureExpr
+            );
+        let trailing = self
+            .formatter
+            .get_multiline_trailing_separator(&LuaSyntaxId::from_node(node))
+            .and_then(|separator| {
+                let last_item = items.last()?.text_range();
+                Some(MultilineTrailing {
+                    separator: separator.to_string(),
+                    existing: separators
+                        .last()
+                        .copied()
+                        .filter(|last| self.starts[*last] >= last_item.end()),
+                    after: self.last_index_in(last_item)?,
+                })
+            });
+        Some(BreakGroup {
+            open,
+            close,
+            breaks,
+            hug,
+            trailing,
+        })
+    }
+
+    /// Group of the outermost binary expression of a chain like `a and b and c`, the operands
+    /// with another priority are groups of their own.
+    fn binary_break_group(&self, node: &LuaSyntaxNode) -> Option<BreakGroup> {
+        let binary_expr = LuaBinaryExpr::cast(node.clone())?;
+        let priority = binary_priority(&binary_expr)?;
+        if let Some(parent) = node.parent().and_then(LuaBinaryExpr::cast)
+            && binary_priority(&parent) == Some(priority)
+        {
+            return None;
+        }
+
+        let mut breaks = Vec::new();
+        let mut chain = vec![binary_expr];
+        while let Some(binary_expr) = chain.pop() {
+            let op_token = binary_expr.get_op_token()?;
+            breaks.extend(self.index_at(op_token.syntax().text_range().start()));
+            chain.extend(
+                binary_expr
+                    .children::<LuaBinaryExpr>()
+                    .filter(|child| binary_priority(child) == Some(priority)),
+            );
+        }
+        breaks.sort();
+
+        let open = self.index_at(node.text_range().start())?;
+        let close = self.last_index_in(node.text_range())?;
+        if close >= self.tokens.len() {
+            return None;
+        }
+        Some(BreakGroup {
+            open,
+            close,
+            breaks,
+            hug: false,
+            trailing: None,
+        })
+    }
+
+    fn align_candidate(
+        &self,
+        node: &LuaSyntaxNode,
+        container: &LuaSyntaxNode,
+        is_table_field: bool,
+    ) -> Option<AlignCandidate> {
+        let assign = node
+            .children_with_tokens()
+            .find(|child| child.kind() == LuaKind::Token(LuaTokenKind::TkAssign))?;
+        Some(AlignCandidate {
+            container: LuaSyntaxId::from_node(container),
+            first: self.index_at(node.text_range().start())?,
+            assign: self.index_at(assign.text_range().start())?,
+            last: self.last_index_in(node.text_range())?,
+            is_table_field,
+        })
+    }
+}
+
+fn binary_priority(binary_expr: &LuaBinaryExpr) -> Option<i32> {
+    Some(binary_expr.get_op_token()?.get_op().get_priority().left)
+}
+
+fn is_group_container(node: &LuaSyntaxNode) -> bool {
+    matches!(
+        node.kind().to_syntax(),
+        LuaSyntaxKind::TableObjectExpr
+            | LuaSyntaxKind::TableArrayExpr
+            | LuaSyntaxKind::CallArgList
+            | LuaSyntaxKind::ParamList
+    )
+}
+
+fn is_group_item(element: &LuaSyntaxElement) -> bool {
+    !matches!(
+        element.kind().to_token(),
+        LuaTokenKind::TkLeftParen
+            | LuaTokenKind::TkRightParen
+            | LuaTokenKind::TkLeftBrace
+            | LuaTokenKind::TkRightBrace
+            | LuaTokenKind::TkComma
+            | LuaTokenKind::TkSemicolon
+    )
+}
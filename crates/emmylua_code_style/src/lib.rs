@@ -1,26 +1,97 @@
+#[cfg(feature = "cli")]
 pub mod cmd_args;
 mod format;
+mod style_check;
 mod style_ruler;
 mod styles;
 mod test;
 
-use emmylua_parser::{LuaAst, LuaParser, ParserConfig};
+use emmylua_parser::{LuaAst, LuaAstNode, LuaParser, LuaSyntaxTree, ParserConfig};
 
 pub fn reformat_lua_code(code: &str, styles: &LuaCodeStyle) -> String {
     let tree = LuaParser::parse(code, ParserConfig::default());
+    // never rewrite code the parser does not understand
+    if tree.has_syntax_errors() {
+        return code.to_string();
+    }
 
     let mut formatter = format::LuaFormatter::new(LuaAst::LuaChunk(tree.get_chunk_node()));
     style_ruler::apply_styles(&mut formatter, styles);
 
-    formatter.get_formatted_text()
+    formatter.get_formatted_text(styles)
+}
+
+/// Format the lines `start_line..=end_line` (0-based) of `code`. The range grows to the whole
+/// lines of a long string or comment crossing its bounds, the result holds the lines it replaces.
+pub fn range_format_lua_code(
+    code: &str,
+    styles: &LuaCodeStyle,
+    start_line: usize,
+    end_line: usize,
+) -> Option<FormattedRange> {
+    let tree = LuaParser::parse(code, ParserConfig::default());
+    if tree.has_syntax_errors() {
+        return None;
+    }
+
+    let mut formatter = format::LuaFormatter::new(LuaAst::LuaChunk(tree.get_chunk_node()));
+    style_ruler::apply_styles(&mut formatter, styles);
+
+    formatter.get_formatted_range(styles, start_line, end_line)
+}
+
+/// Format several line ranges of `code` at once, see `range_format_lua_code`. Ranges growing into
+/// each other are merged, the result is sorted and does not overlap.
+pub fn range_format_lua_code_lines(
+    code: &str,
+    styles: &LuaCodeStyle,
+    ranges: &[(usize, usize)],
+) -> Option<Vec<FormattedRange>> {
+    let tree = LuaParser::parse(code, ParserConfig::default());
+    if tree.has_syntax_errors() {
+        return None;
+    }
+
+    let mut formatter = format::LuaFormatter::new(LuaAst::LuaChunk(tree.get_chunk_node()));
+    style_ruler::apply_styles(&mut formatter, styles);
+
+    Some(formatter.get_formatted_ranges(styles, ranges))
 }
 
 pub fn reformat_node(node: &LuaAst, styles: &LuaCodeStyle) -> String {
     let mut formatter = format::LuaFormatter::new(node.clone());
     style_ruler::apply_styles(&mut formatter, styles);
 
-    formatter.get_formatted_text()
+    formatter.get_formatted_text(styles)
+}
+
+/// Places where `code` differs from its formatted text, with the edits fixing them. Code with
+/// syntax errors has none.
+pub fn check_lua_code_style(code: &str, styles: &LuaCodeStyle) -> Vec<StyleDiagnostic> {
+    let tree = LuaParser::parse(code, ParserConfig::default());
+    if tree.has_syntax_errors() {
+        return Vec::new();
+    }
+
+    style_check::check_style(LuaAst::LuaChunk(tree.get_chunk_node()), code, styles)
+}
+
+/// Style diagnostics of a parsed file, see `check_lua_code_style`. A tree with syntax errors has
+/// none, so its fixes never rewrite broken code.
+pub fn check_tree_style(tree: &LuaSyntaxTree, styles: &LuaCodeStyle) -> Vec<StyleDiagnostic> {
+    if tree.has_syntax_errors() {
+        return Vec::new();
+    }
+
+    let chunk = tree.get_chunk_node();
+    let code = chunk.syntax().text().to_string();
+    style_check::check_style(LuaAst::LuaChunk(chunk), &code, styles)
 }
 
 // Re-export commonly used types for consumers/binaries
-pub use styles::LuaCodeStyle;
+pub use format::FormattedRange;
+pub use style_check::{StyleDiagnostic, StyleDiagnosticKind, StyleEdit};
+pub use styles::{
+    CallArgParentheses, EndAlignment, LuaCodeStyle, LuaIndent, QuoteStyle, TableSeparator,
+    TrailingSeparator,
+};
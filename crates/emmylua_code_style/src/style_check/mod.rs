@@ -0,0 +1,260 @@
+use std::ops::Range;
+
+use emmylua_parser::{LuaAst, LuaAstNode, LuaTokenKind};
+use rowan::{TextRange, TextSize};
+
+use crate::{
+    format::{LineGroup, LuaFormatter},
+    style_ruler::{self, convert_quotes},
+    styles::{LuaCodeStyle, QuoteStyle},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleDiagnosticKind {
+    /// The indentation of a line differs from the formatted line
+    Indentation,
+    /// A line ends with spaces or tabs
+    TrailingWhitespace,
+    /// A line is wider than `max_line_width`
+    LineTooLong,
+    /// Blank lines the formatter removes
+    BlankLines,
+    /// A string does not use the quotes of `quote_style`
+    QuoteStyle,
+    /// Any other difference with the formatted code, like the spaces between tokens
+    Format,
+}
+
+/// Replace `range` of the source with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleEdit {
+    pub range: TextRange,
+    pub new_text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleDiagnostic {
+    pub kind: StyleDiagnosticKind,
+    pub range: TextRange,
+    /// `None` when the formatter cannot fix it, like a long line without anything to break
+    pub fix: Option<StyleEdit>,
+}
+
+/// Compare `code`, the text of `root`, with its formatted text line by line.
+pub fn check_style(root: LuaAst, code: &str, styles: &LuaCodeStyle) -> Vec<StyleDiagnostic> {
+    let mut checker = StyleChecker {
+        code,
+        lines: source_lines(code),
+        styles,
+        diagnostics: Vec::new(),
+    };
+    checker.check_quotes(&root);
+
+    // strings are reported on their own, the lines are compared without changing their quotes
+    let layout_styles = LuaCodeStyle {
+        quote_style: QuoteStyle::Keep,
+        ..styles.clone()
+    };
+    let mut formatter = LuaFormatter::new(root);
+    style_ruler::apply_styles(&mut formatter, &layout_styles);
+    let mut next_line = 0;
+    let mut next_formatted_line = 0;
+    for group in formatter.get_formatted_line_groups(&layout_styles) {
+        checker.check_blank_lines(
+            next_line..group.range.start_line,
+            group.formatted_line.saturating_sub(next_formatted_line),
+        );
+        checker.check_group(&group);
+        next_line = group.range.end_line + 1;
+        next_formatted_line = group.formatted_line + group.range.text.lines().count();
+    }
+    checker.check_blank_lines(next_line..checker.lines.len(), 0);
+
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort_by_key(|diagnostic| diagnostic.range.start());
+    diagnostics
+}
+
+struct StyleChecker<'a> {
+    code: &'a str,
+    /// Byte range of every source line without its line ending
+    lines: Vec<Range<usize>>,
+    styles: &'a LuaCodeStyle,
+    diagnostics: Vec<StyleDiagnostic>,
+}
+
+impl StyleChecker<'_> {
+    fn add(&mut self, kind: StyleDiagnosticKind, range: Range<usize>, fix: Option<StyleEdit>) {
+        self.diagnostics.push(StyleDiagnostic {
+            kind,
+            range: to_text_range(range),
+            fix,
+        });
+    }
+
+    fn check_quotes(&mut self, root: &LuaAst) {
+        let quote = match self.styles.quote_style {
+            QuoteStyle::Keep => return,
+            QuoteStyle::Double => '"',
+            QuoteStyle::Single => '\'',
+        };
+
+        for token in root
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+        {
+            if token.kind().to_token() == LuaTokenKind::TkString
+                && let Some(text) = convert_quotes(token.text(), quote)
+            {
+                let range = token.text_range();
+                self.add(
+                    StyleDiagnosticKind::QuoteStyle,
+                    range.start().into()..range.end().into(),
+                    Some(StyleEdit {
+                        range,
+                        new_text: text,
+                    }),
+                );
+            }
+        }
+    }
+
+    /// `lines` hold no token, the first `kept` of them are kept by the formatter.
+    fn check_blank_lines(&mut self, lines: Range<usize>, kept: usize) {
+        for line in lines.clone().take(kept) {
+            self.check_trailing_whitespace(line);
+        }
+        if lines.len() > kept {
+            let start = self.lines[lines.start + kept].start;
+            let end = self
+                .lines
+                .get(lines.end)
+                .map_or(self.code.len(), |line| line.start);
+            self.add(
+                StyleDiagnosticKind::BlankLines,
+                start..end,
+                Some(delete(start..end)),
+            );
+        }
+    }
+
+    fn check_trailing_whitespace(&mut self, line: usize) {
+        let range = self.lines[line].clone();
+        let content = self.code[range.clone()].trim_end_matches([' ', '\t']);
+        let start = range.start + content.len();
+        if start < range.end {
+            self.add(
+                StyleDiagnosticKind::TrailingWhitespace,
+                start..range.end,
+                Some(delete(start..range.end)),
+            );
+        }
+    }
+
+    fn check_group(&mut self, group: &LineGroup) {
+        let range = &group.range;
+        let formatted: Vec<&str> = range.text.lines().collect();
+        if formatted.len() == range.end_line - range.start_line + 1 {
+            for (i, formatted_line) in formatted.into_iter().enumerate() {
+                self.check_line(range.start_line + i, formatted_line);
+            }
+            return;
+        }
+
+        // the formatter splits or joins lines, the whole group is replaced
+        let start = self.lines[range.start_line].start;
+        let end = self.lines[range.end_line].end;
+        let text = range.text.strip_suffix('\n').unwrap_or(&range.text);
+        let fix = Some(StyleEdit {
+            range: to_text_range(start..end),
+            new_text: text.strip_suffix('\r').unwrap_or(text).to_string(),
+        });
+        match (range.start_line..=range.end_line).find(|line| self.is_too_long(*line)) {
+            Some(line) => {
+                let line_range = self.lines[line].clone();
+                self.add(StyleDiagnosticKind::LineTooLong, line_range, fix);
+            }
+            None => self.add(StyleDiagnosticKind::Format, start..end, fix),
+        }
+    }
+
+    fn check_line(&mut self, line: usize, formatted: &str) {
+        let range = self.lines[line].clone();
+        let source = &self.code[range.clone()];
+        let replace_line = || StyleEdit {
+            range: to_text_range(range.clone()),
+            new_text: formatted.to_string(),
+        };
+        if self.is_too_long(line) {
+            let fix = (source != formatted).then(replace_line);
+            self.add(StyleDiagnosticKind::LineTooLong, range, fix);
+            return;
+        }
+        if source == formatted {
+            return;
+        }
+
+        let content = source.trim_end_matches([' ', '\t']);
+        let indent = content.len() - content.trim_start_matches([' ', '\t']).len();
+        let formatted_indent = formatted.len() - formatted.trim_start_matches([' ', '\t']).len();
+        if content[indent..] != formatted[formatted_indent..] {
+            let fix = Some(replace_line());
+            self.add(StyleDiagnosticKind::Format, range, fix);
+            return;
+        }
+        if content[..indent] != formatted[..formatted_indent] {
+            let indent_range = range.start..range.start + indent;
+            let fix = Some(StyleEdit {
+                range: to_text_range(indent_range.clone()),
+                new_text: formatted[..formatted_indent].to_string(),
+            });
+            self.add(StyleDiagnosticKind::Indentation, indent_range, fix);
+        }
+        self.check_trailing_whitespace(line);
+    }
+
+    fn is_too_long(&self, line: usize) -> bool {
+        let max_line_width = self.styles.max_line_width;
+        if max_line_width == 0 {
+            return false;
+        }
+        let width: usize = self.code[self.lines[line].clone()]
+            .chars()
+            .map(|c| {
+                if c == '\t' {
+                    self.styles.indent_width()
+                } else {
+                    1
+                }
+            })
+            .sum();
+        width > max_line_width
+    }
+}
+
+fn source_lines(code: &str) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for line in code.split_inclusive('\n') {
+        let content = line.strip_suffix('\n').unwrap_or(line);
+        let content = content.strip_suffix('\r').unwrap_or(content);
+        lines.push(start..start + content.len());
+        start += line.len();
+    }
+    lines
+}
+
+fn delete(range: Range<usize>) -> StyleEdit {
+    StyleEdit {
+        range: to_text_range(range),
+        new_text: String::new(),
+    }
+}
+
+fn to_text_range(range: Range<usize>) -> TextRange {
+    TextRange::new(
+        TextSize::from(range.start as u32),
+        TextSize::from(range.end as u32),
+    )
+}
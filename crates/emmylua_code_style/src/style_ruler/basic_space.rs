@@ -11,7 +11,8 @@ use super::StyleRuler;
 pub struct BasicSpaceRuler;
 
 impl StyleRuler for BasicSpaceRuler {
-    fn apply_style(f: &mut LuaFormatter, _: &LuaCodeStyle) {
+    fn apply_style(f: &mut LuaFormatter, styles: &LuaCodeStyle) {
+        let brace_space = if styles.space_inside_braces { 1 } else { 0 };
         let root = f.get_root();
         for node_or_token in root.syntax().descendants_with_tokens() {
             if let NodeOrToken::Token(token) = node_or_token {
@@ -30,6 +31,9 @@ impl StyleRuler for BasicSpaceRuler {
                                 | LuaTokenKind::TkLongString => {
                                     f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
                                 }
+                                LuaTokenKind::TkFunction => {
+                                    f.add_token_left_expected(syntax_id, TokenExpected::Space(0));
+                                }
                                 _ => {}
                             }
                         }
@@ -40,23 +44,35 @@ impl StyleRuler for BasicSpaceRuler {
                         f.add_token_left_expected(syntax_id, TokenExpected::Space(0));
                     }
                     LuaTokenKind::TkLeftBrace => {
-                        f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
+                        if is_parent_syntax(&token, LuaSyntaxKind::TableEmptyExpr) {
+                            continue;
+                        }
+                        f.add_token_right_expected(syntax_id, TokenExpected::Space(brace_space));
                     }
                     LuaTokenKind::TkRightBrace => {
-                        f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
+                        if is_parent_syntax(&token, LuaSyntaxKind::TableEmptyExpr) {
+                            f.add_token_left_expected(syntax_id, TokenExpected::Space(0));
+                            continue;
+                        }
+                        f.add_token_left_expected(syntax_id, TokenExpected::Space(brace_space));
                     }
                     LuaTokenKind::TkComma => {
                         f.add_token_left_expected(syntax_id, TokenExpected::Space(0));
                         f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
                     }
+                    LuaTokenKind::TkSemicolon => {
+                        f.add_token_left_expected(syntax_id, TokenExpected::Space(0));
+                        if is_table_separator(&token) {
+                            f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
+                        }
+                    }
                     LuaTokenKind::TkPlus | LuaTokenKind::TkMinus => {
                         if is_parent_syntax(&token, LuaSyntaxKind::UnaryExpr) {
                             f.add_token_right_expected(syntax_id, TokenExpected::Space(0));
                             continue;
                         }
 
-                        f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
-                        f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
+                        add_operator_space(f, styles, &token);
                     }
                     LuaTokenKind::TkLt => {
                         if is_parent_syntax(&token, LuaSyntaxKind::Attribute) {
@@ -65,8 +81,7 @@ impl StyleRuler for BasicSpaceRuler {
                             continue;
                         }
 
-                        f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
-                        f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
+                        add_operator_space(f, styles, &token);
                     }
                     LuaTokenKind::TkGt => {
                         if is_parent_syntax(&token, LuaSyntaxKind::Attribute) {
@@ -75,8 +90,7 @@ impl StyleRuler for BasicSpaceRuler {
                             continue;
                         }
 
-                        f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
-                        f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
+                        add_operator_space(f, styles, &token);
                     }
                     LuaTokenKind::TkMul
                     | LuaTokenKind::TkDiv
@@ -84,7 +98,6 @@ impl StyleRuler for BasicSpaceRuler {
                     | LuaTokenKind::TkMod
                     | LuaTokenKind::TkPow
                     | LuaTokenKind::TkConcat
-                    | LuaTokenKind::TkAssign
                     | LuaTokenKind::TkBitAnd
                     | LuaTokenKind::TkBitOr
                     | LuaTokenKind::TkBitXor
@@ -92,10 +105,11 @@ impl StyleRuler for BasicSpaceRuler {
                     | LuaTokenKind::TkGe
                     | LuaTokenKind::TkLe
                     | LuaTokenKind::TkNe
-                    | LuaTokenKind::TkAnd
-                    | LuaTokenKind::TkOr
                     | LuaTokenKind::TkShl
                     | LuaTokenKind::TkShr => {
+                        add_operator_space(f, styles, &token);
+                    }
+                    LuaTokenKind::TkAssign | LuaTokenKind::TkAnd | LuaTokenKind::TkOr => {
                         f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
                         f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
                     }
@@ -126,7 +140,17 @@ impl StyleRuler for BasicSpaceRuler {
                     | LuaTokenKind::TkUntil
                     | LuaTokenKind::TkIn
                     | LuaTokenKind::TkNot => {
-                        f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
+                        // `(function() end)()` and `(not a)` keep the bracket space
+                        let after_open_bracket = get_prev_sibling_token_without_space(&token)
+                            .is_some_and(|prev| {
+                                matches!(
+                                    prev.kind().to_token(),
+                                    LuaTokenKind::TkLeftParen | LuaTokenKind::TkLeftBracket
+                                )
+                            });
+                        if !after_open_bracket {
+                            f.add_token_left_expected(syntax_id, TokenExpected::Space(1));
+                        }
                         f.add_token_right_expected(syntax_id, TokenExpected::Space(1));
                     }
                     _ => {}
@@ -136,6 +160,14 @@ impl StyleRuler for BasicSpaceRuler {
     }
 }
 
+fn add_operator_space(f: &mut LuaFormatter, styles: &LuaCodeStyle, token: &LuaSyntaxToken) {
+    // the layout still keeps a space where removing it would change the tokens, like `a - -b`
+    let space = if styles.space_around_operators { 1 } else { 0 };
+    let syntax_id = LuaSyntaxId::from_token(token);
+    f.add_token_left_expected(syntax_id, TokenExpected::Space(space));
+    f.add_token_right_expected(syntax_id, TokenExpected::Space(space));
+}
+
 fn is_parent_syntax(token: &LuaSyntaxToken, kind: LuaSyntaxKind) -> bool {
     if let Some(parent) = token.parent() {
         return parent.kind().to_syntax() == kind;
@@ -143,6 +175,15 @@ fn is_parent_syntax(token: &LuaSyntaxToken, kind: LuaSyntaxKind) -> bool {
     false
 }
 
+fn is_table_separator(token: &LuaSyntaxToken) -> bool {
+    token.parent().is_some_and(|parent| {
+        matches!(
+            parent.kind().to_syntax(),
+            LuaSyntaxKind::TableObjectExpr | LuaSyntaxKind::TableArrayExpr
+        )
+    })
+}
+
 fn get_prev_sibling_token_without_space(token: &LuaSyntaxToken) -> Option<LuaSyntaxToken> {
     let mut current = token.clone();
     while let Some(prev) = current.prev_token() {
@@ -0,0 +1,87 @@
+use emmylua_parser::{LuaAstNode, LuaSyntaxId, LuaSyntaxKind, LuaSyntaxNode, LuaTokenKind};
+use rowan::NodeOrToken;
+
+use crate::{
+    format::{LuaFormatter, TokenExpected, TokenNodeChange},
+    styles::{CallArgParentheses, LuaCodeStyle},
+};
+
+use super::StyleRuler;
+
+pub struct CallArgParenthesesRuler;
+
+impl StyleRuler for CallArgParenthesesRuler {
+    fn apply_style(f: &mut LuaFormatter, styles: &LuaCodeStyle) {
+        if styles.call_arg_parentheses == CallArgParentheses::Keep {
+            return;
+        }
+
+        let root = f.get_root();
+        for node in root.syntax().descendants() {
+            if node.kind().to_syntax() != LuaSyntaxKind::CallArgList {
+                continue;
+            }
+            let mut parens = Vec::new();
+            let mut args = Vec::new();
+            for child in node.children_with_tokens() {
+                match child {
+                    NodeOrToken::Token(token) => match token.kind().to_token() {
+                        LuaTokenKind::TkLeftParen | LuaTokenKind::TkRightParen => {
+                            parens.push(token)
+                        }
+                        LuaTokenKind::TkWhitespace | LuaTokenKind::TkEndOfLine => {}
+                        // `f(a, b)`, keep it as is
+                        _ => args.push(NodeOrToken::Token(token)),
+                    },
+                    NodeOrToken::Node(node) => args.push(NodeOrToken::Node(node)),
+                }
+            }
+            let [NodeOrToken::Node(arg)] = args.as_slice() else {
+                continue;
+            };
+            if !is_string_or_table(arg) {
+                continue;
+            }
+            let (Some(first), Some(last)) = (arg.first_token(), arg.last_token()) else {
+                continue;
+            };
+
+            match styles.call_arg_parentheses {
+                CallArgParentheses::Omit if parens.len() == 2 => {
+                    for paren in &parens {
+                        f.add_token_change(LuaSyntaxId::from_token(paren), TokenNodeChange::Remove);
+                    }
+                    f.add_token_left_expected(
+                        LuaSyntaxId::from_token(&first),
+                        TokenExpected::Space(1),
+                    );
+                }
+                CallArgParentheses::Always if parens.is_empty() => {
+                    let first_id = LuaSyntaxId::from_token(&first);
+                    f.add_token_change(first_id, TokenNodeChange::AddLeft("(".to_string()));
+                    f.add_token_change(
+                        LuaSyntaxId::from_token(&last),
+                        TokenNodeChange::AddRight(")".to_string()),
+                    );
+                    f.add_token_left_expected(first_id, TokenExpected::Space(0));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn is_string_or_table(node: &LuaSyntaxNode) -> bool {
+    match node.kind().to_syntax() {
+        LuaSyntaxKind::TableObjectExpr
+        | LuaSyntaxKind::TableArrayExpr
+        | LuaSyntaxKind::TableEmptyExpr => true,
+        LuaSyntaxKind::LiteralExpr => node.first_token().is_some_and(|token| {
+            matches!(
+                token.kind().to_token(),
+                LuaTokenKind::TkString | LuaTokenKind::TkLongString
+            )
+        }),
+        _ => false,
+    }
+}
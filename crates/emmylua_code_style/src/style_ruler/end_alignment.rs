@@ -0,0 +1,69 @@
+use emmylua_parser::{LuaAstNode, LuaSyntaxId, LuaSyntaxKind, LuaTokenKind};
+use rowan::NodeOrToken;
+
+use crate::{
+    format::LuaFormatter,
+    styles::{EndAlignment, LuaCodeStyle},
+};
+
+use super::StyleRuler;
+
+pub struct EndAlignmentRuler;
+
+impl StyleRuler for EndAlignmentRuler {
+    fn apply_style(f: &mut LuaFormatter, styles: &LuaCodeStyle) {
+        if styles.end_alignment != EndAlignment::Statement {
+            return;
+        }
+
+        let root = f.get_root();
+        for block in root.syntax().descendants() {
+            if block.kind().to_syntax() != LuaSyntaxKind::Block
+                || block
+                    .parent()
+                    .is_none_or(|parent| parent.kind().to_syntax() == LuaSyntaxKind::Chunk)
+            {
+                continue;
+            }
+            // a block holding only comments stays where it is
+            let Some(first_stat) = block
+                .children()
+                .find(|child| child.kind().to_syntax() != LuaSyntaxKind::Comment)
+            else {
+                continue;
+            };
+            if let Some(first_token) = first_stat.first_token() {
+                f.add_token_line_break(LuaSyntaxId::from_token(&first_token));
+            }
+
+            // `end`, `until`, `elseif` or `else` closing the block
+            let mut next = block.next_sibling_or_token();
+            while let Some(element) = next {
+                match element {
+                    NodeOrToken::Token(token) => {
+                        if matches!(
+                            token.kind().to_token(),
+                            LuaTokenKind::TkEnd | LuaTokenKind::TkUntil
+                        ) {
+                            f.add_token_line_break(LuaSyntaxId::from_token(&token));
+                            break;
+                        }
+                        next = token.next_sibling_or_token();
+                    }
+                    NodeOrToken::Node(node) => {
+                        if let Some(first_token) = node.first_token()
+                            && matches!(
+                                first_token.kind().to_token(),
+                                LuaTokenKind::TkElseIf | LuaTokenKind::TkElse
+                            )
+                        {
+                            f.add_token_line_break(LuaSyntaxId::from_token(&first_token));
+                            break;
+                        }
+                        next = node.next_sibling_or_token();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,19 @@
 mod basic_space;
+mod call_arg_parentheses;
+mod end_alignment;
+mod quote_style;
+mod table_separator;
 
 use crate::{format::LuaFormatter, styles::LuaCodeStyle};
+pub(crate) use quote_style::convert_quotes;
 
 #[allow(unused)]
 pub fn apply_styles(formatter: &mut LuaFormatter, styles: &LuaCodeStyle) {
     apply_style::<basic_space::BasicSpaceRuler>(formatter, styles);
+    apply_style::<quote_style::QuoteStyleRuler>(formatter, styles);
+    apply_style::<table_separator::TableSeparatorRuler>(formatter, styles);
+    apply_style::<call_arg_parentheses::CallArgParenthesesRuler>(formatter, styles);
+    apply_style::<end_alignment::EndAlignmentRuler>(formatter, styles);
 }
 
 pub trait StyleRuler {
@@ -0,0 +1,67 @@
+use emmylua_parser::{LuaAstNode, LuaSyntaxId, LuaTokenKind};
+use rowan::NodeOrToken;
+
+use crate::{
+    format::{LuaFormatter, TokenNodeChange},
+    styles::{LuaCodeStyle, QuoteStyle},
+};
+
+use super::StyleRuler;
+
+pub struct QuoteStyleRuler;
+
+impl StyleRuler for QuoteStyleRuler {
+    fn apply_style(f: &mut LuaFormatter, styles: &LuaCodeStyle) {
+        let quote = match styles.quote_style {
+            QuoteStyle::Keep => return,
+            QuoteStyle::Double => '"',
+            QuoteStyle::Single => '\'',
+        };
+
+        let root = f.get_root();
+        for node_or_token in root.syntax().descendants_with_tokens() {
+            if let NodeOrToken::Token(token) = node_or_token
+                && token.kind().to_token() == LuaTokenKind::TkString
+                && let Some(text) = convert_quotes(token.text(), quote)
+            {
+                f.add_token_change(
+                    LuaSyntaxId::from_token(&token),
+                    TokenNodeChange::ReplaceWith(text),
+                );
+            }
+        }
+    }
+}
+
+/// `text` quoted with `quote`, or `None` when it already is or when it contains `quote`.
+pub(crate) fn convert_quotes(text: &str, quote: char) -> Option<String> {
+    let current = text.chars().next()?;
+    if current == quote || !matches!(current, '"' | '\'') || text.len() < 2 {
+        return None;
+    }
+    let body = text.strip_prefix(current)?.strip_suffix(current)?;
+    if body.contains(quote) {
+        return None;
+    }
+
+    let mut result = String::with_capacity(text.len());
+    result.push(quote);
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // the old quote does not need to be escaped anymore
+            Some(escaped) if escaped == current => result.push(escaped),
+            Some(escaped) => {
+                result.push('\\');
+                result.push(escaped);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result.push(quote);
+    Some(result)
+}
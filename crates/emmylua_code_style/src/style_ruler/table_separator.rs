@@ -0,0 +1,83 @@
+use emmylua_parser::{LuaAstNode, LuaSyntaxId, LuaSyntaxKind, LuaSyntaxNode, LuaTokenKind};
+
+use crate::{
+    format::{LuaFormatter, TokenNodeChange},
+    styles::{LuaCodeStyle, TableSeparator, TrailingSeparator},
+};
+
+use super::StyleRuler;
+
+pub struct TableSeparatorRuler;
+
+impl StyleRuler for TableSeparatorRuler {
+    fn apply_style(f: &mut LuaFormatter, styles: &LuaCodeStyle) {
+        let root = f.get_root();
+        for node in root.syntax().descendants() {
+            if matches!(
+                node.kind().to_syntax(),
+                LuaSyntaxKind::TableObjectExpr | LuaSyntaxKind::TableArrayExpr
+            ) {
+                apply_table_style(f, styles, &node);
+            }
+        }
+    }
+}
+
+fn apply_table_style(f: &mut LuaFormatter, styles: &LuaCodeStyle, table: &LuaSyntaxNode) {
+    let separators: Vec<_> = table
+        .children_with_tokens()
+        .filter_map(|child| child.into_token())
+        .filter(|token| {
+            matches!(
+                token.kind().to_token(),
+                LuaTokenKind::TkComma | LuaTokenKind::TkSemicolon
+            )
+        })
+        .collect();
+    let separator = match styles.table_separator {
+        TableSeparator::Keep => separators
+            .first()
+            .map_or(",", |separator| separator.text())
+            .to_string(),
+        TableSeparator::Comma => ",".to_string(),
+        TableSeparator::Semicolon => ";".to_string(),
+    };
+    if styles.table_separator != TableSeparator::Keep {
+        for token in &separators {
+            if token.text() != separator {
+                f.add_token_change(
+                    LuaSyntaxId::from_token(token),
+                    TokenNodeChange::ReplaceWith(separator.clone()),
+                );
+            }
+        }
+    }
+
+    let Some(last_field) = table
+        .children()
+        .filter(|child| child.kind().to_syntax() != LuaSyntaxKind::Comment)
+        .last()
+    else {
+        return;
+    };
+    let trailing = separators
+        .last()
+        .filter(|separator| separator.text_range().start() >= last_field.text_range().end());
+    match (styles.trailing_table_separator, trailing) {
+        (TrailingSeparator::Always, None) => {
+            if let Some(last_token) = last_field.last_token() {
+                f.add_token_change(
+                    LuaSyntaxId::from_token(&last_token),
+                    TokenNodeChange::AddRight(separator),
+                );
+            }
+        }
+        (TrailingSeparator::Never, Some(trailing)) => {
+            f.add_token_change(LuaSyntaxId::from_token(trailing), TokenNodeChange::Remove);
+        }
+        (TrailingSeparator::Multiline, _) => {
+            f.add_multiline_trailing_separator(LuaSyntaxId::from_node(table), separator);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum QuoteStyle {
+    /// Keep the quotes of every string
+    #[default]
+    Keep,
+    /// Use `"` unless the string contains one
+    Double,
+    /// Use `'` unless the string contains one
+    Single,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TableSeparator {
+    /// Keep the separators of every table
+    #[default]
+    Keep,
+    /// Separate table fields with `,`
+    Comma,
+    /// Separate table fields with `;`
+    Semicolon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TrailingSeparator {
+    /// Keep the separator after the last field as written
+    #[default]
+    Keep,
+    /// Always end the last field with a separator
+    Always,
+    /// Never end the last field with a separator
+    Never,
+    /// End the last field with a separator when the table spans several lines
+    Multiline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CallArgParentheses {
+    /// Keep the parentheses of every call
+    #[default]
+    Keep,
+    /// Add parentheses to calls with a single string or table argument
+    Always,
+    /// Remove the parentheses of calls with a single string or table argument
+    Omit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum EndAlignment {
+    /// Blocks written on one line, like `if a then return end`, stay on one line
+    #[default]
+    Keep,
+    /// The body of every non-empty block gets its own lines and `end` is aligned with the start
+    /// of the statement
+    Statement,
+}
@@ -1,12 +1,79 @@
 mod lua_indent;
+mod lua_style_options;
 
 pub use lua_indent::LuaIndent;
+pub use lua_style_options::{
+    CallArgParentheses, EndAlignment, QuoteStyle, TableSeparator, TrailingSeparator,
+};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
 pub struct LuaCodeStyle {
     /// The indentation style to use
     pub indent: LuaIndent,
-    /// The maximum width of a line before wrapping
+    /// The maximum width of a line before wrapping, 0 disables wrapping
     pub max_line_width: usize,
+    /// The maximum number of consecutive blank lines to keep
+    pub max_blank_lines: usize,
+    /// Whether to end the formatted text with a newline
+    pub insert_final_newline: bool,
+    /// Align the `=` of consecutive single line assignments when the source already aligns some
+    /// of them
+    pub align_continuous_assign_statement: bool,
+    /// Align the `=` of the fields of a multi-line table when the source already aligns some of
+    /// them
+    pub align_table_field: bool,
+    /// The quotes of short strings
+    pub quote_style: QuoteStyle,
+    /// The separator between table fields
+    pub table_separator: TableSeparator,
+    /// Whether the last field of a table ends with a separator
+    pub trailing_table_separator: TrailingSeparator,
+    /// Parentheses around a single string or table call argument
+    pub call_arg_parentheses: CallArgParentheses,
+    /// Whether to put a space after `{` and before `}` in tables
+    pub space_inside_braces: bool,
+    /// Whether to put spaces around arithmetic, bitwise, comparison and concat operators
+    pub space_around_operators: bool,
+    /// Where the `end` of a block goes
+    pub end_alignment: EndAlignment,
+}
+
+impl Default for LuaCodeStyle {
+    fn default() -> Self {
+        Self {
+            indent: LuaIndent::default(),
+            max_line_width: 120,
+            max_blank_lines: 1,
+            insert_final_newline: true,
+            align_continuous_assign_statement: true,
+            align_table_field: true,
+            quote_style: QuoteStyle::default(),
+            table_separator: TableSeparator::default(),
+            trailing_table_separator: TrailingSeparator::default(),
+            call_arg_parentheses: CallArgParentheses::default(),
+            space_inside_braces: true,
+            space_around_operators: true,
+            end_alignment: EndAlignment::default(),
+        }
+    }
+}
+
+impl LuaCodeStyle {
+    pub fn indent_text(&self) -> String {
+        match self.indent {
+            LuaIndent::Tab => "\t".to_string(),
+            LuaIndent::Space(n) => " ".repeat(n),
+        }
+    }
+
+    /// Width of one indentation level when measuring lines, a tab counts as 4 columns.
+    pub fn indent_width(&self) -> usize {
+        match self.indent {
+            LuaIndent::Tab => 4,
+            LuaIndent::Space(n) => n,
+        }
+    }
 }
@@ -1,7 +1,26 @@
 #[allow(clippy::module_inception)]
 #[cfg(test)]
 mod test {
-    use crate::{reformat_lua_code, styles::LuaCodeStyle};
+    use crate::{
+        StyleDiagnosticKind, check_lua_code_style, range_format_lua_code,
+        range_format_lua_code_lines, reformat_lua_code,
+        styles::{
+            CallArgParentheses, EndAlignment, LuaCodeStyle, LuaIndent, QuoteStyle, TableSeparator,
+            TrailingSeparator,
+        },
+    };
+
+    fn format(code: &str) -> String {
+        reformat_lua_code(code, &LuaCodeStyle::default())
+    }
+
+    fn format_with_width(code: &str, max_line_width: usize) -> String {
+        let styles = LuaCodeStyle {
+            max_line_width,
+            ..Default::default()
+        };
+        reformat_lua_code(code, &styles)
+    }
 
     #[test]
     fn test_reformat_lua_code() {
@@ -12,8 +31,639 @@ mod test {
             print  (c     )
         "#;
 
+        let formatted_code = format(code);
+        assert_eq!(
+            formatted_code,
+            "local a = 1\nlocal b = 2\nlocal c = a + b\nprint(c)\n"
+        );
+    }
+
+    #[test]
+    fn test_indent_blocks() {
+        let code = r#"
+function M.f(x)
+if x then
+return function(y)
+print(y)
+end
+elseif x == 2 then
+  for i = 1, 10 do
+        print(i)
+  end
+else
+repeat x = x - 1 until x < 0
+end
+end
+"#;
+        let expected = r#"function M.f(x)
+    if x then
+        return function(y)
+            print(y)
+        end
+    elseif x == 2 then
+        for i = 1, 10 do
+            print(i)
+        end
+    else
+        repeat x = x - 1 until x < 0
+    end
+end
+"#;
+        assert_eq!(format(code), expected);
+    }
+
+    #[test]
+    fn test_indent_tab() {
+        let styles = LuaCodeStyle {
+            indent: LuaIndent::Tab,
+            ..Default::default()
+        };
+        let code = "if a then\r\n  b()\r\nend\r\n";
+        assert_eq!(
+            reformat_lua_code(code, &styles),
+            "if a then\r\n\tb()\r\nend\r\n"
+        );
+    }
+
+    #[test]
+    fn test_closure_argument_and_continuation() {
+        let code = r#"
+list:each(function(item)
+print(item)
+end)
+local r = a
+and b
+    or c
+"#;
+        let expected = r#"list:each(function(item)
+    print(item)
+end)
+local r = a
+    and b
+    or c
+"#;
+        assert_eq!(format(code), expected);
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let code = r#"
+
+
+local a = 1
+
+
+
+local b = 2
+function f()
+
+    return a
+
+end
+"#;
+        let expected = r#"local a = 1
+
+local b = 2
+function f()
+    return a
+end
+"#;
+        assert_eq!(format(code), expected);
+    }
+
+    #[test]
+    fn test_keep_comments() {
+        let code = r#"
+    ---@class A
+      ---@field x number   # keep this spacing
+local A = {}   -- trailing
+
+function A:get()
+        --[[ long
+   comment ]]
+    local s = [[
+  raw ]]
+  -- end comment
+end
+"#;
+        let expected = r#"---@class A
+---@field x number   # keep this spacing
+local A = {}   -- trailing
+
+function A:get()
+    --[[ long
+   comment ]]
+    local s = [[
+  raw ]]
+    -- end comment
+end
+"#;
+        assert_eq!(format(code), expected);
+    }
+
+    #[test]
+    fn test_align_assign_statement() {
+        let code = r#"
+local a  = 1
+local bbb = 2
+ccc.d = 3
+
+local x = 1
+local yyy = 2
+"#;
+        let expected = r#"local a   = 1
+local bbb = 2
+ccc.d     = 3
+
+local x = 1
+local yyy = 2
+"#;
+        assert_eq!(format(code), expected);
+    }
+
+    #[test]
+    fn test_align_table_field() {
+        let code = r#"
+local t = {
+    a  = 1,
+    bbb = 2,
+    nested = {
+        x = 1,
+        yy = 2,
+    },
+}
+"#;
+        let expected = r#"local t = {
+    a   = 1,
+    bbb = 2,
+    nested = {
+        x = 1,
+        yy = 2,
+    },
+}
+"#;
+        assert_eq!(format(code), expected);
+    }
+
+    #[test]
+    fn test_wrap_call_arguments() {
+        let code = "call_function(first_argument, second_argument, third_argument)\n";
+        let expected = r#"call_function(
+    first_argument,
+    second_argument,
+    third_argument
+)
+"#;
+        assert_eq!(format_with_width(code, 40), expected);
+        assert_eq!(format_with_width(code, 80), code);
+    }
+
+    #[test]
+    fn test_wrap_nested_table() {
+        let code = r#"
+if ok then
+    setup({ name = "value", other = { 1, 2, 3 }, flag = true }) -- comment
+end
+"#;
+        let expected = r#"if ok then
+    setup({
+        name = "value",
+        other = { 1, 2, 3 },
+        flag = true
+    }) -- comment
+end
+"#;
+        assert_eq!(format_with_width(code, 50), expected);
+    }
+
+    #[test]
+    fn test_wrap_binary_expression() {
+        let code = "local message = first_part .. second_part .. third_part .. fourth_part\n";
+        let expected = r#"local message = first_part
+    .. second_part
+    .. third_part
+    .. fourth_part
+"#;
+        assert_eq!(format_with_width(code, 40), expected);
+        assert_eq!(format_with_width(expected, 40), expected);
+        assert_eq!(format_with_width(code, 80), code);
+    }
+
+    #[test]
+    fn test_wrap_if_condition() {
+        let code = r#"
+if is_enabled and player.health > minimum_health or force_update then
+    update(player)
+end
+"#;
+        // the `or` chain is split first, the `and` operand only when it is still too long
+        let expected = r#"if is_enabled and player.health > minimum_health
+    or force_update then
+    update(player)
+end
+"#;
+        assert_eq!(format_with_width(code, 60), expected);
+        let expected = r#"if is_enabled
+    and player.health > minimum_health
+    or force_update then
+    update(player)
+end
+"#;
+        assert_eq!(format_with_width(code, 40), expected);
+    }
+
+    #[test]
+    fn test_semicolon_space() {
+        assert_eq!(format("print(i)  ;\n"), "print(i);\n");
+        assert_eq!(
+            format("local a = 1 ;  local b = 2\n"),
+            "local a = 1; local b = 2\n"
+        );
+        assert_eq!(format("local t = { 1 ;2 }\n"), "local t = { 1; 2 }\n");
+    }
+
+    #[test]
+    fn test_idempotent() {
+        let code = r#"
+local config = { alpha = "first value", beta = "second value", gamma = { 1, 2, 3 } }
+local  short   = 1
+local longer = 2
+local function f(a,b) return a+b end
+"#;
+        let once = format_with_width(code, 50);
+        assert_eq!(format_with_width(&once, 50), once);
+    }
+
+    #[test]
+    fn test_syntax_error_unchanged() {
+        let code = "local a = = 1\n  print( a )\n";
+        assert_eq!(format(code), code);
+    }
+
+    #[test]
+    fn test_range_format() {
+        let code = r#"local a=1
+if a then
+print( a )
+local t = { x=1,
+y=2 }
+end
+local b=2
+"#;
+        let styles = LuaCodeStyle::default();
+        let range = range_format_lua_code(code, &styles, 3, 3).unwrap();
+        assert_eq!((range.start_line, range.end_line), (3, 3));
+        assert_eq!(range.text, "    local t = { x = 1,\n");
+
+        let range = range_format_lua_code(code, &styles, 3, 4).unwrap();
+        assert_eq!((range.start_line, range.end_line), (3, 4));
+        assert_eq!(range.text, "    local t = { x = 1,\n        y = 2 }\n");
+
+        let range = range_format_lua_code(code, &styles, 6, 6).unwrap();
+        assert_eq!((range.start_line, range.end_line), (6, 6));
+        assert_eq!(range.text, "local b = 2\n");
+    }
+
+    #[test]
+    fn test_quote_style() {
+        let code = r#"local a = 'it\'s'
+local b = "say \"hi\""
+local c = 'plain'
+local d = [[long]]
+"#;
+        let styles = LuaCodeStyle {
+            quote_style: QuoteStyle::Double,
+            ..Default::default()
+        };
+        let expected = r#"local a = "it's"
+local b = "say \"hi\""
+local c = "plain"
+local d = [[long]]
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+
+        let styles = LuaCodeStyle {
+            quote_style: QuoteStyle::Single,
+            ..Default::default()
+        };
+        let expected = r#"local a = 'it\'s'
+local b = 'say "hi"'
+local c = 'plain'
+local d = [[long]]
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+    }
+
+    #[test]
+    fn test_table_separator() {
+        let code = "local t = { a = 1; b = 2, 3 }\n";
+        let styles = LuaCodeStyle {
+            table_separator: TableSeparator::Comma,
+            ..Default::default()
+        };
+        assert_eq!(
+            reformat_lua_code(code, &styles),
+            "local t = { a = 1, b = 2, 3 }\n"
+        );
+
+        let styles = LuaCodeStyle {
+            table_separator: TableSeparator::Semicolon,
+            ..Default::default()
+        };
+        assert_eq!(
+            reformat_lua_code(code, &styles),
+            "local t = { a = 1; b = 2; 3 }\n"
+        );
+    }
+
+    #[test]
+    fn test_trailing_table_separator() {
+        let code = r#"local a = { 1, 2, }
+local b = {
+    x = 1,
+    y = 2
+}
+"#;
+        let styles = LuaCodeStyle {
+            trailing_table_separator: TrailingSeparator::Always,
+            ..Default::default()
+        };
+        let expected = r#"local a = { 1, 2, }
+local b = {
+    x = 1,
+    y = 2,
+}
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+
+        let styles = LuaCodeStyle {
+            trailing_table_separator: TrailingSeparator::Never,
+            ..Default::default()
+        };
+        let expected = r#"local a = { 1, 2 }
+local b = {
+    x = 1,
+    y = 2
+}
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+
+        let styles = LuaCodeStyle {
+            trailing_table_separator: TrailingSeparator::Multiline,
+            ..Default::default()
+        };
+        let expected = r#"local a = { 1, 2 }
+local b = {
+    x = 1,
+    y = 2,
+}
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+    }
+
+    #[test]
+    fn test_multiline_trailing_separator_on_wrap() {
+        let styles = LuaCodeStyle {
+            max_line_width: 30,
+            trailing_table_separator: TrailingSeparator::Multiline,
+            ..Default::default()
+        };
+        let code = "local t = { alpha = 1, beta = 2, gamma = 3 }\n";
+        let expected = r#"local t = {
+    alpha = 1,
+    beta = 2,
+    gamma = 3,
+}
+"#;
+        let formatted = reformat_lua_code(code, &styles);
+        assert_eq!(formatted, expected);
+        assert_eq!(reformat_lua_code(&formatted, &styles), expected);
+    }
+
+    #[test]
+    fn test_call_arg_parentheses() {
+        let code = r#"require("module")
+setup({ a = 1 })
+print "text"
+call(a, "b")
+"#;
+        let styles = LuaCodeStyle {
+            call_arg_parentheses: CallArgParentheses::Omit,
+            ..Default::default()
+        };
+        let expected = r#"require "module"
+setup { a = 1 }
+print "text"
+call(a, "b")
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+
+        let styles = LuaCodeStyle {
+            call_arg_parentheses: CallArgParentheses::Always,
+            ..Default::default()
+        };
+        let expected = r#"require("module")
+setup({ a = 1 })
+print("text")
+call(a, "b")
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+    }
+
+    #[test]
+    fn test_space_options() {
+        let code = "local t = { a = 1 + 2, b = x .. y, c = a == b and n * 2 or -n }\n";
+        let styles = LuaCodeStyle {
+            space_inside_braces: false,
+            space_around_operators: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            reformat_lua_code(code, &styles),
+            "local t = {a = 1+2, b = x..y, c = a==b and n*2 or -n}\n"
+        );
+
+        // removing the space would change the tokens
+        let code = "local a = b - -c .. 1 .. ...\nlocal s = 1 .. x\n";
+        assert_eq!(
+            reformat_lua_code(code, &styles),
+            "local a = b- -c.. 1 .. ...\nlocal s = 1 ..x\n"
+        );
+    }
+
+    #[test]
+    fn test_end_alignment() {
+        let code = r#"if a then return end
+local function f() return 1 end
+local g = function() end
+while x do x = x - 1 end
+"#;
+        assert_eq!(format(code), code);
+
+        let styles = LuaCodeStyle {
+            end_alignment: EndAlignment::Statement,
+            ..Default::default()
+        };
+        let expected = r#"if a then
+    return
+end
+local function f()
+    return 1
+end
+local g = function() end
+while x do
+    x = x - 1
+end
+"#;
+        assert_eq!(reformat_lua_code(code, &styles), expected);
+    }
+
+    #[test]
+    fn test_style_config() {
+        let json = r#"{
+    "quote_style": "Single",
+    "table_separator": "Semicolon",
+    "trailing_table_separator": "Multiline",
+    "call_arg_parentheses": "Omit",
+    "space_inside_braces": false,
+    "space_around_operators": false,
+    "end_alignment": "Statement"
+}"#;
+        let styles: LuaCodeStyle = serde_json::from_str(json).unwrap();
+        assert_eq!(styles.quote_style, QuoteStyle::Single);
+        assert_eq!(styles.table_separator, TableSeparator::Semicolon);
+        assert_eq!(
+            styles.trailing_table_separator,
+            TrailingSeparator::Multiline
+        );
+        assert_eq!(styles.call_arg_parentheses, CallArgParentheses::Omit);
+        assert!(!styles.space_inside_braces);
+        assert!(!styles.space_around_operators);
+        assert_eq!(styles.end_alignment, EndAlignment::Statement);
+        assert_eq!(styles.max_line_width, 120);
+
+        let yaml = "quote_style: Double\ntrailing_table_separator: Always\n";
+        let styles: LuaCodeStyle = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(styles.quote_style, QuoteStyle::Double);
+        assert_eq!(styles.trailing_table_separator, TrailingSeparator::Always);
+        assert_eq!(styles.table_separator, TableSeparator::Keep);
+        assert!(styles.space_inside_braces);
+    }
+
+    #[test]
+    fn test_range_format_lines() {
+        let code = r#"local a=1
+if a then
+print( a )
+local t = { x=1,
+y=2 }
+end
+local b=2
+"#;
         let styles = LuaCodeStyle::default();
-        let formatted_code = reformat_lua_code(code, &styles);
-        println!("Formatted code:\n{}", formatted_code);
+        let ranges =
+            range_format_lua_code_lines(code, &styles, &[(6, 6), (0, 0), (3, 3), (4, 4)]).unwrap();
+        let ranges: Vec<_> = ranges
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.text))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (0, 0, "local a = 1\n".to_string()),
+                (
+                    3,
+                    4,
+                    "    local t = { x = 1,\n        y = 2 }\n".to_string()
+                ),
+                (6, 6, "local b = 2\n".to_string()),
+            ]
+        );
+    }
+
+    fn check(code: &str, styles: &LuaCodeStyle) -> Vec<(StyleDiagnosticKind, String)> {
+        check_lua_code_style(code, styles)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.kind, code[diagnostic.range].to_string()))
+            .collect()
+    }
+
+    fn apply_style_fixes(code: &str, styles: &LuaCodeStyle) -> String {
+        let mut result = code.to_string();
+        for diagnostic in check_lua_code_style(code, styles).iter().rev() {
+            if let Some(fix) = &diagnostic.fix {
+                result.replace_range(std::ops::Range::<usize>::from(fix.range), &fix.new_text);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_check_style_lines() {
+        let code = "local a = 1  \nif a then\n  print(a)\nend\n\n\n\nlocal b  =  2\n";
+        let styles = LuaCodeStyle::default();
+        assert_eq!(
+            check(code, &styles),
+            vec![
+                (StyleDiagnosticKind::TrailingWhitespace, "  ".to_string()),
+                (StyleDiagnosticKind::Indentation, "  ".to_string()),
+                (StyleDiagnosticKind::BlankLines, "\n\n".to_string()),
+                (StyleDiagnosticKind::Format, "local b  =  2".to_string()),
+            ]
+        );
+        assert_eq!(apply_style_fixes(code, &styles), format(code));
+        assert!(check(&format(code), &styles).is_empty());
+    }
+
+    #[test]
+    fn test_check_style_line_too_long() {
+        let styles = LuaCodeStyle {
+            max_line_width: 30,
+            ..Default::default()
+        };
+        let code = "call_function(first_argument, second_argument)\nlocal s = \"a very long string value\"\n";
+        assert_eq!(
+            check(code, &styles),
+            vec![
+                (
+                    StyleDiagnosticKind::LineTooLong,
+                    "call_function(first_argument, second_argument)".to_string()
+                ),
+                (
+                    StyleDiagnosticKind::LineTooLong,
+                    "local s = \"a very long string value\"".to_string()
+                ),
+            ]
+        );
+        let diagnostics = check_lua_code_style(code, &styles);
+        assert!(diagnostics[0].fix.is_some());
+        assert!(diagnostics[1].fix.is_none());
+        assert_eq!(
+            apply_style_fixes(code, &styles),
+            reformat_lua_code(code, &styles)
+        );
+    }
+
+    #[test]
+    fn test_check_style_quotes() {
+        let code = "local a = 'x'\nlocal b = \"y\"\nlocal c = 'say \"hi\"'\n";
+        let styles = LuaCodeStyle {
+            quote_style: QuoteStyle::Double,
+            ..Default::default()
+        };
+        assert_eq!(
+            check(code, &styles),
+            vec![(StyleDiagnosticKind::QuoteStyle, "'x'".to_string())]
+        );
+        assert_eq!(
+            apply_style_fixes(code, &styles),
+            reformat_lua_code(code, &styles)
+        );
+        assert!(check(code, &LuaCodeStyle::default()).is_empty());
+    }
+
+    #[test]
+    fn test_check_style_syntax_error() {
+        assert!(check("local a = = 1  \n", &LuaCodeStyle::default()).is_empty());
     }
 }
@@ -43,7 +43,7 @@ pub struct CmdArgs {
     #[arg(long, value_enum, ignore_case = true)]
     pub format: Option<Format>,
 
-    /// Specify output destination (can be stdout when output_format is json)
+    /// Specify output destination (can be stdout when output_format is json or a dependency graph)
     #[arg(long, short, default_value = "./output")]
     pub output: OutputDestination,
 
@@ -60,6 +60,10 @@ pub struct CmdArgs {
     #[arg(long)]
     pub mixin: Option<PathBuf>,
 
+    /// Group the nodes of the dependency graph formats in clusters
+    #[arg(long, value_enum, ignore_case = true)]
+    pub group_by: Option<GraphGroup>,
+
     /// Verbose output
     #[arg(long)]
     pub verbose: bool,
@@ -69,6 +73,20 @@ pub struct CmdArgs {
 pub enum Format {
     Json,
     Markdown,
+    /// Require graph of the workspace modules as Graphviz DOT
+    Dot,
+    /// Require graph of the workspace modules as a Mermaid flowchart
+    Mermaid,
+    /// Require graph of the workspace modules as JSON nodes and edges
+    GraphJson,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum GraphGroup {
+    /// The directory of the file
+    Directory,
+    /// The workspace root containing the file
+    Workspace,
 }
 
 #[allow(unused)]
@@ -0,0 +1,194 @@
+use std::path::{Path, PathBuf};
+
+use emmylua_code_analysis::{DbIndex, FileId};
+
+use super::graph_types::{DependencyGraph, Edge, Node};
+use crate::GraphGroup;
+
+/// The require graph between the files of the main workspace, requires of std and library
+/// modules are left out.
+pub fn export(db: &DbIndex, main_path: &Path, group_by: Option<GraphGroup>) -> DependencyGraph {
+    let module_index = db.get_module_index();
+    let mut file_ids = module_index.get_main_workspace_file_ids();
+    file_ids.sort();
+
+    let mut graph = DependencyGraph::default();
+    for file_id in &file_ids {
+        let Some(module_info) = module_index.get_module(*file_id) else {
+            continue;
+        };
+        let path = get_file_path(db, *file_id);
+        let relative_path = get_relative_path(db, *file_id, main_path);
+        let group = match group_by {
+            Some(GraphGroup::Directory) => Some(get_directory(&relative_path)),
+            Some(GraphGroup::Workspace) => path
+                .as_deref()
+                .map(|path| get_workspace_root(db, path, main_path)),
+            None => None,
+        };
+        graph.nodes.push(Node {
+            path: relative_path.clone(),
+            name: module_info.full_module_name.clone(),
+            group,
+        });
+
+        let Some(required_files) = db.get_file_dependencies_index().get_required_files(file_id)
+        else {
+            continue;
+        };
+        for required in required_files {
+            if module_index.get_module(*required).is_some() && module_index.is_main(required) {
+                graph.edges.push(Edge {
+                    from: relative_path.clone(),
+                    to: get_relative_path(db, *required, main_path),
+                });
+            }
+        }
+    }
+
+    graph.nodes.sort_by(|a, b| a.path.cmp(&b.path));
+    graph.edges.sort();
+    graph
+}
+
+fn get_file_path(db: &DbIndex, file_id: FileId) -> Option<PathBuf> {
+    db.get_vfs().get_file_path(&file_id).cloned()
+}
+
+fn get_relative_path(db: &DbIndex, file_id: FileId, main_path: &Path) -> String {
+    get_file_path(db, file_id)
+        .map(|path| relative_to(&path, main_path))
+        .unwrap_or_default()
+}
+
+fn relative_to(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn get_directory(relative_path: &str) -> String {
+    match relative_path.rsplit_once('/') {
+        Some((directory, _)) => directory.to_string(),
+        None => ".".to_string(),
+    }
+}
+
+/// The innermost workspace root containing `path`, relative to the main workspace.
+fn get_workspace_root(db: &DbIndex, path: &Path, main_path: &Path) -> String {
+    let root = db
+        .get_module_index()
+        .get_workspaces()
+        .iter()
+        .filter(|workspace| workspace.id.is_main() && path.starts_with(&workspace.root))
+        .max_by_key(|workspace| workspace.root.components().count())
+        .map(|workspace| relative_to(&workspace.root, main_path))
+        .unwrap_or_default();
+    if root.is_empty() {
+        ".".to_string()
+    } else {
+        root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use emmylua_code_analysis::EmmyLuaAnalysis;
+
+    use super::*;
+
+    /// `client` and `server` are workspace roots under `main_path`, both with an `util` module.
+    fn export_workspace(group_by: Option<GraphGroup>) -> DependencyGraph {
+        let main_path = std::env::current_dir().unwrap().join("graph");
+        let mut analysis = EmmyLuaAnalysis::new();
+        analysis.add_main_workspace(main_path.join("client"));
+        analysis.add_main_workspace(main_path.join("server"));
+        analysis.update_files_by_path(vec![
+            (
+                main_path.join("client/main.lua"),
+                Some(r#"local util = require("util") local net = require("net.socket")"#.into()),
+            ),
+            (main_path.join("client/util.lua"), Some("return {}".into())),
+            (
+                main_path.join("client/net/socket.lua"),
+                Some("return {}".into()),
+            ),
+            (main_path.join("server/util.lua"), Some("return {}".into())),
+        ]);
+        export(analysis.compilation.get_db(), &main_path, group_by)
+    }
+
+    /// `path name group` of each node.
+    fn summarize(graph: &DependencyGraph) -> Vec<String> {
+        graph
+            .nodes
+            .iter()
+            .map(|node| {
+                format!(
+                    "{} {} {}",
+                    node.path,
+                    node.name,
+                    node.group.as_deref().unwrap_or("-")
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_same_module_name() {
+        let graph = export_workspace(None);
+        assert_eq!(
+            summarize(&graph),
+            vec![
+                "client/main.lua main -",
+                "client/net/socket.lua net.socket -",
+                "client/util.lua util -",
+                "server/util.lua util -",
+            ]
+        );
+        for edge in &graph.edges {
+            assert_eq!(edge.from, "client/main.lua");
+        }
+        assert!(
+            graph
+                .edges
+                .iter()
+                .any(|edge| edge.to == "client/net/socket.lua")
+        );
+    }
+
+    #[test]
+    fn test_group_by_directory() {
+        let graph = export_workspace(Some(GraphGroup::Directory));
+        assert_eq!(
+            summarize(&graph),
+            vec![
+                "client/main.lua main client",
+                "client/net/socket.lua net.socket client/net",
+                "client/util.lua util client",
+                "server/util.lua util server",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_workspace() {
+        let graph = export_workspace(Some(GraphGroup::Workspace));
+        assert_eq!(
+            summarize(&graph),
+            vec![
+                "client/main.lua main client",
+                "client/net/socket.lua net.socket client",
+                "client/util.lua util client",
+                "server/util.lua util server",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_directory() {
+        assert_eq!(get_directory("main.lua"), ".");
+        assert_eq!(get_directory("a/b/c.lua"), "a/b");
+    }
+}
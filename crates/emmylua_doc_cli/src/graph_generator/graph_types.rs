@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+/// A Lua file of the main workspace.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Node {
+    /// Path relative to the workspace root, it identifies the node
+    pub path: String,
+    /// The module name, like `game.player`, two workspace roots may hold the same one
+    pub name: String,
+    /// The directory or workspace root of the file, when grouped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// `from` requires `to`, both are node paths.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+}
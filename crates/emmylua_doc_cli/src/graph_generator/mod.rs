@@ -0,0 +1,66 @@
+use std::path::Path;
+
+use crate::{Format, GraphGroup, OutputDestination};
+use emmylua_code_analysis::EmmyLuaAnalysis;
+
+mod export;
+mod graph_types;
+mod render;
+
+/// Write the require graph of the workspace as Graphviz DOT, Mermaid or JSON.
+pub fn generate_graph(
+    analysis: &EmmyLuaAnalysis,
+    main_path: &Path,
+    format: Format,
+    group_by: Option<GraphGroup>,
+    output: OutputDestination,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db = analysis.compilation.get_db();
+    let extension = match format {
+        Format::Dot => "dot",
+        Format::Mermaid => "mmd",
+        _ => "json",
+    };
+
+    let output = match output {
+        OutputDestination::File(output) if output.extension().is_some() => {
+            if let Some(parent) = output.parent()
+                && !parent.exists()
+            {
+                log::info!("Creating output directory: {:?}", parent);
+                std::fs::create_dir_all(parent)?;
+            }
+
+            OutputDestination::File(output)
+        }
+        OutputDestination::File(output) => {
+            if !output.exists() {
+                log::info!("Creating output directory: {:?}", output);
+                std::fs::create_dir_all(&output)?;
+            }
+
+            OutputDestination::File(output.join(format!("dependencies.{}", extension)))
+        }
+        OutputDestination::Stdout => OutputDestination::Stdout,
+    };
+
+    let graph = export::export(db, main_path, group_by);
+    let text = match format {
+        Format::Dot => render::render_dot(&graph),
+        Format::Mermaid => render::render_mermaid(&graph),
+        _ => serde_json::to_string_pretty(&graph)?,
+    };
+
+    match output {
+        OutputDestination::Stdout => {
+            println!("{}", text);
+        }
+        OutputDestination::File(path) => {
+            log::info!("Writing dependency graph to: {:?}", path);
+            std::fs::write(&path, text)?;
+            eprintln!("Dependency graph exported to {:?}", path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use super::graph_types::{DependencyGraph, Node};
+
+pub fn render_dot(graph: &DependencyGraph) -> String {
+    let mut dot = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n");
+    for (index, (group, nodes)) in group_nodes(graph).into_iter().enumerate() {
+        let indent = match group {
+            Some(group) => {
+                let _ = writeln!(dot, "    subgraph \"cluster_{}\" {{", index);
+                let _ = writeln!(dot, "        label={};", quote_dot(group));
+                "        "
+            }
+            None => "    ",
+        };
+        for node in nodes {
+            let _ = writeln!(
+                dot,
+                "{}{} [label={}, tooltip={}];",
+                indent,
+                quote_dot(&node.path),
+                quote_dot(&node.name),
+                quote_dot(&node.path)
+            );
+        }
+        if group.is_some() {
+            dot.push_str("    }\n");
+        }
+    }
+    for edge in &graph.edges {
+        let _ = writeln!(
+            dot,
+            "    {} -> {};",
+            quote_dot(&edge.from),
+            quote_dot(&edge.to)
+        );
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+pub fn render_mermaid(graph: &DependencyGraph) -> String {
+    // mermaid ids cannot hold dots or slashes, the nodes are numbered by path
+    let ids: HashMap<&str, String> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.path.as_str(), format!("n{}", index)))
+        .collect();
+
+    let mut mermaid = String::from("flowchart LR\n");
+    for (index, (group, nodes)) in group_nodes(graph).into_iter().enumerate() {
+        let indent = match group {
+            Some(group) => {
+                let _ = writeln!(mermaid, "    subgraph g{}[{}]", index, quote_mermaid(group));
+                "        "
+            }
+            None => "    ",
+        };
+        for node in nodes {
+            let _ = writeln!(
+                mermaid,
+                "{}{}[{}]",
+                indent,
+                ids[node.path.as_str()],
+                quote_mermaid(&node.name)
+            );
+        }
+        if group.is_some() {
+            mermaid.push_str("    end\n");
+        }
+    }
+    for edge in &graph.edges {
+        if let (Some(from), Some(to)) = (ids.get(edge.from.as_str()), ids.get(edge.to.as_str())) {
+            let _ = writeln!(mermaid, "    {} --> {}", from, to);
+        }
+    }
+    mermaid
+}
+
+/// Nodes by group, in group name order, the ungrouped nodes come first.
+fn group_nodes(graph: &DependencyGraph) -> BTreeMap<Option<&str>, Vec<&Node>> {
+    let mut groups: BTreeMap<Option<&str>, Vec<&Node>> = BTreeMap::new();
+    for node in &graph.nodes {
+        groups.entry(node.group.as_deref()).or_default().push(node);
+    }
+    groups
+}
+
+fn quote_dot(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn quote_mermaid(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "#quot;"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_generator::graph_types::Edge;
+
+    fn node(path: &str, name: &str, group: Option<&str>) -> Node {
+        Node {
+            path: path.to_string(),
+            name: name.to_string(),
+            group: group.map(str::to_string),
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> Edge {
+        Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    /// Two workspace roots with an `util` module each.
+    fn same_name_graph(group: bool) -> DependencyGraph {
+        let group_of = |root| if group { Some(root) } else { None };
+        DependencyGraph {
+            nodes: vec![
+                node("client/main.lua", "main", group_of("client")),
+                node("client/util.lua", "util", group_of("client")),
+                node("server/util.lua", "util", group_of("server")),
+            ],
+            edges: vec![
+                edge("client/main.lua", "client/util.lua"),
+                edge("client/main.lua", "server/util.lua"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_dot() {
+        assert_eq!(
+            render_dot(&same_name_graph(false)),
+            r#"digraph dependencies {
+    rankdir=LR;
+    node [shape=box];
+    "client/main.lua" [label="main", tooltip="client/main.lua"];
+    "client/util.lua" [label="util", tooltip="client/util.lua"];
+    "server/util.lua" [label="util", tooltip="server/util.lua"];
+    "client/main.lua" -> "client/util.lua";
+    "client/main.lua" -> "server/util.lua";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        assert_eq!(
+            render_mermaid(&same_name_graph(false)),
+            r#"flowchart LR
+    n0["main"]
+    n1["util"]
+    n2["util"]
+    n0 --> n1
+    n0 --> n2
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_dot_group() {
+        assert_eq!(
+            render_dot(&same_name_graph(true)),
+            r#"digraph dependencies {
+    rankdir=LR;
+    node [shape=box];
+    subgraph "cluster_0" {
+        label="client";
+        "client/main.lua" [label="main", tooltip="client/main.lua"];
+        "client/util.lua" [label="util", tooltip="client/util.lua"];
+    }
+    subgraph "cluster_1" {
+        label="server";
+        "server/util.lua" [label="util", tooltip="server/util.lua"];
+    }
+    "client/main.lua" -> "client/util.lua";
+    "client/main.lua" -> "server/util.lua";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_mermaid_group() {
+        assert_eq!(
+            render_mermaid(&same_name_graph(true)),
+            r#"flowchart LR
+    subgraph g0["client"]
+        n0["main"]
+        n1["util"]
+    end
+    subgraph g1["server"]
+        n2["util"]
+    end
+    n0 --> n1
+    n0 --> n2
+"#
+        );
+    }
+
+    #[test]
+    fn test_quote() {
+        let graph = DependencyGraph {
+            nodes: vec![node(r#"a\"b".lua"#, r#"a\"b""#, Some(r#"say "hi""#))],
+            edges: vec![],
+        };
+        assert_eq!(
+            render_dot(&graph),
+            r#"digraph dependencies {
+    rankdir=LR;
+    node [shape=box];
+    subgraph "cluster_0" {
+        label="say \"hi\"";
+        "a\\\"b\".lua" [label="a\\\"b\"", tooltip="a\\\"b\".lua"];
+    }
+}
+"#
+        );
+        assert_eq!(
+            render_mermaid(&graph),
+            r#"flowchart LR
+    subgraph g0["say #quot;hi#quot;"]
+        n0["a\#quot;b#quot;"]
+    end
+"#
+        );
+    }
+}
@@ -5,6 +5,7 @@ pub use cmd_args::*;
 
 mod cmd_args;
 mod common;
+mod graph_generator;
 mod init;
 mod json_generator;
 mod markdown_generator;
@@ -62,5 +63,12 @@ pub fn run_doc_cli(mut cmd_args: CmdArgs) -> Result<(), Box<dyn std::error::Erro
             cmd_args.mixin,
         ),
         Format::Json => json_generator::generate_json(&analysis, cmd_args.output),
+        Format::Dot | Format::Mermaid | Format::GraphJson => graph_generator::generate_graph(
+            &analysis,
+            &main_path,
+            cmd_args.output_format,
+            cmd_args.group_by,
+            cmd_args.output,
+        ),
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU8, Ordering};
 use std::{path::PathBuf, sync::Arc, time::Duration};
@@ -9,7 +9,7 @@ use crate::handlers::{ClientConfig, init_analysis};
 use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, load_configs};
 use emmylua_code_analysis::{update_code_style, uri_to_file_path};
 use log::{debug, info};
-use lsp_types::Uri;
+use lsp_types::{SemanticTokens, Uri};
 use tokio::sync::{Mutex, RwLock};
 use tokio_util::sync::CancellationToken;
 use wax::Pattern;
@@ -25,6 +25,10 @@ pub struct WorkspaceManager {
     pub workspace_folders: Vec<PathBuf>,
     pub watcher: Option<notify::RecommendedWatcher>,
     pub current_open_files: HashSet<Uri>,
+    /// Text of the open files when they were opened or last saved
+    pub saved_texts: HashMap<Uri, String>,
+    /// Last semantic tokens sent for the open files, the base of the next delta request
+    pub semantic_tokens: HashMap<Uri, SemanticTokens>,
     pub match_file_pattern: WorkspaceFileMatcher,
     pub workspace_initialized: Arc<AtomicBool>,
     workspace_diagnostic_level: Arc<AtomicU8>,
@@ -50,6 +54,8 @@ impl WorkspaceManager {
             lsp_features,
             watcher: None,
             current_open_files: HashSet::new(),
+            saved_texts: HashMap::new(),
+            semantic_tokens: HashMap::new(),
             match_file_pattern: WorkspaceFileMatcher::default(),
             workspace_initialized: Arc::new(AtomicBool::new(false)),
             workspace_diagnostic_level: Arc::new(AtomicU8::new(
@@ -1,49 +1,35 @@
 use std::collections::HashMap;
 
 use crate::handlers::command::make_auto_doc_tag_command;
-use emmylua_code_analysis::SemanticModel;
-use emmylua_parser::{LuaAstNode, LuaExpr};
-use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
-use rowan::{NodeOrToken, TokenAtOffset};
+use emmylua_code_analysis::{FixApplicability, SemanticModel, get_diagnostic_fixes};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, Range, TextEdit, WorkspaceEdit,
+};
 
-pub fn build_need_check_nil(
+pub fn build_diagnostic_fixes(
     semantic_model: &SemanticModel,
     actions: &mut Vec<CodeActionOrCommand>,
-    range: Range,
-    _data: &Option<serde_json::Value>,
+    diagnostic: &Diagnostic,
 ) -> Option<()> {
     let document = semantic_model.get_document();
-    let offset = document.get_offset(range.end.line as usize, range.end.character as usize)?;
-    let root = semantic_model.get_root();
-    let token = match root.syntax().token_at_offset(offset) {
-        TokenAtOffset::Single(token) => token,
-        TokenAtOffset::Between(_, token) => token,
-        _ => return None,
-    };
-    // 取上一个token的父节点
-    let node_or_token = token.prev_sibling_or_token()?;
-    if let NodeOrToken::Node(expr_node) = node_or_token
-        && LuaExpr::can_cast(expr_node.kind().into())
-    {
-        let expr = LuaExpr::cast(expr_node)?;
-        let range = expr.syntax().text_range();
-        let mut lsp_range = document.to_lsp_range(range)?;
-        // 将范围缩小到最尾部的字符
-        lsp_range.start.line = lsp_range.end.line;
-        lsp_range.start.character = lsp_range.end.character;
-
-        let text_edit = TextEdit {
-            range: lsp_range,
-            new_text: "--[[@cast -?]]".to_string(),
-        };
+    for fix in get_diagnostic_fixes(semantic_model, diagnostic) {
+        let mut text_edits = Vec::new();
+        for edit in fix.edits {
+            text_edits.push(TextEdit {
+                range: document.to_lsp_range(edit.range)?,
+                new_text: edit.new_text,
+            });
+        }
 
         actions.push(CodeActionOrCommand::CodeAction(CodeAction {
-            title: t!("use cast to remove nil").to_string(),
+            title: fix.title,
             kind: Some(CodeActionKind::QUICKFIX),
             edit: Some(WorkspaceEdit {
-                changes: Some(HashMap::from([(document.get_uri(), vec![text_edit])])),
+                changes: Some(HashMap::from([(document.get_uri(), text_edits)])),
                 ..Default::default()
             }),
+            is_preferred: (fix.applicability == FixApplicability::MachineApplicable)
+                .then_some(true),
             ..Default::default()
         }));
     }
@@ -7,12 +7,10 @@ use lsp_types::{
 };
 
 use super::actions::{
-    build_add_doc_tag, build_disable_file_changes, build_disable_next_line_changes,
-};
-use crate::handlers::{
-    code_actions::actions::build_need_check_nil,
-    command::{DisableAction, make_disable_code_command},
+    build_add_doc_tag, build_diagnostic_fixes, build_disable_file_changes,
+    build_disable_next_line_changes,
 };
+use crate::handlers::command::{DisableAction, make_disable_code_command};
 
 pub fn build_actions(
     semantic_model: &SemanticModel,
@@ -21,27 +19,14 @@ pub fn build_actions(
     let mut actions = Vec::new();
     let file_id = semantic_model.get_file_id();
     for diagnostic in diagnostics {
-        if diagnostic.source.is_none() {
-            continue;
-        }
-
-        let source = diagnostic.source.unwrap();
-        if source != "EmmyLua" {
+        if diagnostic.source.as_deref() != Some("EmmyLua") {
             continue;
         }
 
-        if let Some(code) = diagnostic.code
-            && let NumberOrString::String(action_string) = code
-            && let Ok(diagnostic_code) = DiagnosticCode::from_str(&action_string)
+        if let Some(NumberOrString::String(action_string)) = &diagnostic.code
+            && let Ok(diagnostic_code) = DiagnosticCode::from_str(action_string)
         {
-            add_fix_code_action(
-                semantic_model,
-                &mut actions,
-                diagnostic_code,
-                file_id,
-                diagnostic.range,
-                &diagnostic.data,
-            );
+            add_fix_code_action(semantic_model, &mut actions, diagnostic_code, &diagnostic);
             add_disable_code_action(
                 semantic_model,
                 &mut actions,
@@ -59,19 +44,17 @@ pub fn build_actions(
     Some(actions)
 }
 
-#[allow(unused_variables)]
 fn add_fix_code_action(
     semantic_model: &SemanticModel,
     actions: &mut Vec<CodeActionOrCommand>,
     diagnostic_code: DiagnosticCode,
-    file_id: FileId,
-    range: Range,
-    data: &Option<serde_json::Value>,
+    diagnostic: &Diagnostic,
 ) -> Option<()> {
     match diagnostic_code {
-        DiagnosticCode::NeedCheckNil => build_need_check_nil(semantic_model, actions, range, data),
-        DiagnosticCode::UnknownDocTag => build_add_doc_tag(semantic_model, actions, range, data),
-        _ => Some(()),
+        DiagnosticCode::UnknownDocTag => {
+            build_add_doc_tag(semantic_model, actions, diagnostic.range, &diagnostic.data)
+        }
+        _ => build_diagnostic_fixes(semantic_model, actions, diagnostic),
     }
 }
 
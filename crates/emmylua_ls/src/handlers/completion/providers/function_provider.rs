@@ -2,7 +2,7 @@ use emmylua_code_analysis::{
     DbIndex, GenericTplId, InferGuard, InferGuardRef, LuaAliasCallKind, LuaAliasCallType,
     LuaDeclLocation, LuaFunctionType, LuaMember, LuaMemberKey, LuaMemberOwner, LuaMultiLineUnion,
     LuaSemanticDeclId, LuaStringTplType, LuaType, LuaTypeCache, LuaTypeDeclId, LuaUnionType,
-    RenderLevel, SemanticDeclLevel, get_real_type,
+    RenderLevel, SemanticDeclLevel, evaluate_template_literal, get_real_type,
 };
 use emmylua_parser::{
     LuaAssignStat, LuaAst, LuaAstNode, LuaAstToken, LuaCallArgList, LuaCallExpr, LuaClosureExpr,
@@ -117,6 +117,12 @@ pub fn dispatch_type(
         LuaType::Call(special_call) => {
             add_special_call_completion(builder, &special_call);
         }
+        LuaType::TemplateLiteral(template) => {
+            let db = builder.semantic_model.get_db();
+            if let Some(typ) = evaluate_template_literal(db, &template) {
+                dispatch_type(builder, typ, infer_guard);
+            }
+        }
         _ => {}
     }
 
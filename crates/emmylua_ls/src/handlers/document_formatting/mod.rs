@@ -1,14 +1,21 @@
 mod external_format;
 mod format_diff;
+mod modified_lines;
 
-use emmylua_code_analysis::{FormattingOptions, reformat_code};
+use emmylua_code_analysis::{EmmyrcFormatter, EmmyrcReformat, FormattingOptions, reformat_code};
+use emmylua_code_style::{LuaCodeStyle, LuaIndent, range_format_lua_code_lines, reformat_lua_code};
 use lsp_types::{
-    ClientCapabilities, DocumentFormattingParams, OneOf, ServerCapabilities, TextEdit,
+    ClientCapabilities, DocumentFormattingParams, OneOf, Position, Range, ServerCapabilities,
+    TextEdit,
 };
+use rowan::{TextRange, TextSize};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
-    context::ServerContextSnapshot, handlers::document_formatting::format_diff::format_diff,
+    context::ServerContextSnapshot,
+    handlers::document_formatting::{
+        format_diff::format_diff, modified_lines::get_modified_line_ranges,
+    },
 };
 pub use external_format::{FormattingRange, external_tool_format};
 
@@ -51,6 +58,44 @@ pub async fn on_formatting_handler(
         non_standard_symbol: !emmyrc.runtime.nonstandard_symbol.is_empty(),
     };
 
+    if emmyrc.format.external_tool.is_none()
+        && emmyrc.format.formatter == EmmyrcFormatter::Native
+        && emmyrc.format.modified_lines_only
+        && let Some(saved_text) = workspace_manager.saved_texts.get(&uri)
+    {
+        let style = native_code_style(&emmyrc.format, &formatting_options);
+        let ranges = get_modified_line_ranges(saved_text, text);
+        let mut text_edits = Vec::new();
+        for range in range_format_lua_code_lines(text, &style, &ranges)? {
+            let source_start = document.get_offset(range.start_line, 0)?;
+            let source_end = document
+                .get_offset(range.end_line + 1, 0)
+                .unwrap_or_else(|| TextSize::of(text));
+            if document.get_text_slice(TextRange::new(source_start, source_end)) == range.text {
+                continue;
+            }
+
+            let mut new_text = range.text;
+            if client_id.is_intellij() || client_id.is_other() {
+                new_text = new_text.replace("\r\n", "\n");
+            }
+            text_edits.push(TextEdit {
+                range: Range {
+                    start: Position {
+                        line: range.start_line as u32,
+                        character: 0,
+                    },
+                    end: Position {
+                        line: range.end_line as u32 + 1,
+                        character: 0,
+                    },
+                },
+                new_text,
+            });
+        }
+        return Some(text_edits);
+    }
+
     let mut formatted_text = if let Some(external_config) = &emmyrc.format.external_tool {
         external_tool_format(
             external_config,
@@ -60,6 +105,9 @@ pub async fn on_formatting_handler(
             formatting_options,
         )
         .await?
+    } else if emmyrc.format.formatter == EmmyrcFormatter::Native {
+        let style = native_code_style(&emmyrc.format, &formatting_options);
+        reformat_lua_code(text, &style)
     } else {
         reformat_code(text, &normalized_path, formatting_options)
     };
@@ -83,6 +131,19 @@ pub async fn on_formatting_handler(
     Some(text_edits)
 }
 
+/// Style of the native formatter from the client options and the `format` config.
+pub fn native_code_style(config: &EmmyrcReformat, options: &FormattingOptions) -> LuaCodeStyle {
+    LuaCodeStyle {
+        indent: if options.use_tabs {
+            LuaIndent::Tab
+        } else {
+            LuaIndent::Space(options.indent_size as usize)
+        },
+        insert_final_newline: options.insert_final_newline,
+        ..config.get_native_style()
+    }
+}
+
 pub struct DocumentFormattingCapabilities;
 
 impl RegisterCapabilities for DocumentFormattingCapabilities {
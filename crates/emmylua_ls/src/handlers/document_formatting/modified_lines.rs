@@ -0,0 +1,161 @@
+/// Above this many changed lines the diff gives up and the whole changed region is modified.
+const MAX_DIFF_EDITS: usize = 1000;
+
+/// Ranges `(start_line, end_line)` of the lines of `new` which are added or changed since `old`.
+/// Where lines were only deleted, the line following the deletion is modified.
+pub fn get_modified_line_ranges(old: &str, new: &str) -> Vec<(usize, usize)> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old_lines[prefix..old_lines.len() - suffix];
+    let new_middle = &new_lines[prefix..new_lines.len() - suffix];
+    if old_middle.is_empty() && new_middle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut modified = vec![false; new_lines.len()];
+    match diff_kept_lines(old_middle, new_middle) {
+        Some((old_kept, new_kept)) => {
+            let (mut i, mut j) = (0, 0);
+            while i < old_middle.len() || j < new_middle.len() {
+                // a deletion marks the line replacing it or the line following it
+                if i < old_middle.len() && !old_kept[i] {
+                    mark_deletion(&mut modified, prefix + j);
+                    i += 1;
+                } else if j < new_middle.len() && !new_kept[j] {
+                    modified[prefix + j] = true;
+                    j += 1;
+                } else {
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        None if new_middle.is_empty() => mark_deletion(&mut modified, prefix),
+        None => modified[prefix..prefix + new_middle.len()].fill(true),
+    }
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (line, _) in modified
+        .iter()
+        .enumerate()
+        .filter(|(_, modified)| **modified)
+    {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+    ranges
+}
+
+fn mark_deletion(modified: &mut [bool], line: usize) {
+    if let Some(last) = modified.len().checked_sub(1) {
+        modified[line.min(last)] = true;
+    }
+}
+
+/// Myers diff of two line lists, returns which lines of each side are kept, or `None` when they
+/// differ by more than `MAX_DIFF_EDITS` lines.
+fn diff_kept_lines(old: &[&str], new: &[&str]) -> Option<(Vec<bool>, Vec<bool>)> {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    let mut v = vec![0isize; 2 * max + 3];
+    // `trace[d]` holds the diagonals `-(d + 1)..=d + 1` before step `d`
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    for d in 0..=max.min(MAX_DIFF_EDITS) as isize {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let index = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, n, m));
+            }
+        }
+    }
+    None
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> (Vec<bool>, Vec<bool>) {
+    let mut old_kept = vec![false; n as usize];
+    let mut new_kept = vec![false; m as usize];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let get = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = get(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y && x > 0 && y > 0 {
+            x -= 1;
+            y -= 1;
+            old_kept[x as usize] = true;
+            new_kept[y as usize] = true;
+        }
+        if d > 0 {
+            x = prev_x;
+            y = prev_y;
+        }
+    }
+    (old_kept, new_kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unchanged() {
+        let text = "local a = 1\nlocal b = 2\n";
+        assert_eq!(get_modified_line_ranges(text, text), vec![]);
+    }
+
+    #[test]
+    fn test_changed_and_added_lines() {
+        let old = "a\nb\nc\nd\ne\nf\n";
+        let new = "a\nB\nc\nd\nx\ny\ne\nf\n";
+        assert_eq!(get_modified_line_ranges(old, new), vec![(1, 1), (4, 5)]);
+    }
+
+    #[test]
+    fn test_deleted_lines() {
+        let old = "a\nb\nc\nd\n";
+        assert_eq!(get_modified_line_ranges(old, "a\nd\n"), vec![(1, 1)]);
+        assert_eq!(get_modified_line_ranges(old, "a\nb\nc\n"), vec![(2, 2)]);
+        assert_eq!(get_modified_line_ranges(old, ""), vec![]);
+    }
+
+    #[test]
+    fn test_moved_line() {
+        let old = "a\nb\nc\nd\n";
+        let new = "b\nc\nd\na\n";
+        assert_eq!(get_modified_line_ranges(old, new), vec![(0, 0), (3, 3)]);
+    }
+}
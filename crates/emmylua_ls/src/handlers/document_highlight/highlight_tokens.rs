@@ -1,7 +1,10 @@
 use emmylua_code_analysis::{
     LuaDeclId, LuaDocument, LuaSemanticDeclId, SemanticDeclLevel, SemanticModel,
 };
-use emmylua_parser::{LuaAstNode, LuaSyntaxKind, LuaSyntaxNode, LuaSyntaxToken, LuaTokenKind};
+use emmylua_parser::{
+    LuaAstNode, LuaBlock, LuaGotoStat, LuaLabelStat, LuaSyntaxKind, LuaSyntaxNode, LuaSyntaxToken,
+    LuaTokenKind,
+};
 use lsp_types::{DocumentHighlight, DocumentHighlightKind};
 use rowan::NodeOrToken;
 
@@ -11,6 +14,12 @@ pub fn highlight_tokens(
 ) -> Option<Vec<DocumentHighlight>> {
     let mut result = Vec::new();
     match token.kind().into() {
+        LuaTokenKind::TkName | LuaTokenKind::TkDbColon
+            if is_parent_syntax(&token, LuaSyntaxKind::GotoStat)
+                || is_parent_syntax(&token, LuaSyntaxKind::LabelStat) =>
+        {
+            highlight_goto_label(semantic_model, token.parent()?, &mut result);
+        }
         LuaTokenKind::TkName => {
             let semantic_decl =
                 semantic_model.find_decl(token.clone().into(), SemanticDeclLevel::NoTrace);
@@ -124,23 +133,122 @@ fn highlight_keywords(
     result: &mut Vec<DocumentHighlight>,
 ) -> Option<()> {
     let document = semantic_model.get_document();
-    let parent_node = token.parent()?;
-    match parent_node.kind().into() {
+    let mut node = token.parent()?;
+    // the `end` of a function statement is in its closure, `elseif` and `else` are in clauses
+    if node.kind() == LuaSyntaxKind::ClosureExpr.into()
+        && let Some(parent) = node.parent()
+        && matches!(
+            parent.kind().into(),
+            LuaSyntaxKind::LocalFuncStat | LuaSyntaxKind::FuncStat
+        )
+    {
+        node = parent;
+    } else if matches!(
+        node.kind().into(),
+        LuaSyntaxKind::ElseIfClauseStat | LuaSyntaxKind::ElseClauseStat
+    ) {
+        node = node.parent()?;
+    }
+
+    match node.kind().into() {
         LuaSyntaxKind::LocalFuncStat | LuaSyntaxKind::FuncStat => {
-            highlight_node_keywords(&document, parent_node.clone(), result);
-            let closure_node = parent_node
+            highlight_node_keywords(&document, node.clone(), result);
+            let closure_node = node
                 .children()
                 .find(|node| node.kind() == LuaSyntaxKind::ClosureExpr.into())?;
             highlight_node_keywords(&document, closure_node, result);
         }
+        LuaSyntaxKind::IfStat => {
+            highlight_node_keywords(&document, node.clone(), result);
+            for clause in node.children().filter(|node| {
+                matches!(
+                    node.kind().into(),
+                    LuaSyntaxKind::ElseIfClauseStat | LuaSyntaxKind::ElseClauseStat
+                )
+            }) {
+                highlight_node_keywords(&document, clause, result);
+            }
+        }
+        LuaSyntaxKind::GotoStat => {
+            highlight_goto_label(semantic_model, node, result);
+        }
         _ => {
-            highlight_node_keywords(&document, parent_node, result);
+            highlight_node_keywords(&document, node, result);
         }
     }
 
     Some(())
 }
 
+/// Highlight a label and the `goto` statements jumping to it, from either of them.
+fn highlight_goto_label(
+    semantic_model: &SemanticModel,
+    node: LuaSyntaxNode,
+    result: &mut Vec<DocumentHighlight>,
+) -> Option<()> {
+    let label = match LuaGotoStat::cast(node.clone()) {
+        Some(goto_stat) => find_goto_label(&goto_stat),
+        None => LuaLabelStat::cast(node.clone()),
+    };
+    let document = semantic_model.get_document();
+    let Some(label) = label else {
+        // a `goto` without a visible label only highlights itself
+        let range = document.to_lsp_range(node.text_range())?;
+        result.push(DocumentHighlight {
+            range,
+            kind: Some(DocumentHighlightKind::TEXT),
+        });
+        return Some(());
+    };
+
+    let range = document.to_lsp_range(label.get_range())?;
+    result.push(DocumentHighlight {
+        range,
+        kind: Some(DocumentHighlightKind::TEXT),
+    });
+    let block = label.get_parent::<LuaBlock>()?;
+    for goto_stat in block.descendants::<LuaGotoStat>() {
+        if find_goto_label(&goto_stat).as_ref() == Some(&label) {
+            let range = document.to_lsp_range(goto_stat.get_range())?;
+            result.push(DocumentHighlight {
+                range,
+                kind: Some(DocumentHighlightKind::TEXT),
+            });
+        }
+    }
+
+    Some(())
+}
+
+/// The label a `goto` jumps to, declared in an enclosing block of the same function.
+fn find_goto_label(goto_stat: &LuaGotoStat) -> Option<LuaLabelStat> {
+    let name = goto_stat.get_label_name_token()?;
+    for node in goto_stat.syntax().ancestors() {
+        if node.kind() == LuaSyntaxKind::ClosureExpr.into() {
+            return None;
+        }
+        let Some(block) = LuaBlock::cast(node) else {
+            continue;
+        };
+        let label = block.children::<LuaLabelStat>().find(|label| {
+            label
+                .get_label_name_token()
+                .is_some_and(|label_name| label_name.get_name_text() == name.get_name_text())
+        });
+        if label.is_some() {
+            return label;
+        }
+    }
+
+    None
+}
+
+fn is_parent_syntax(token: &LuaSyntaxToken, kind: LuaSyntaxKind) -> bool {
+    token
+        .parent()
+        .is_some_and(|parent| parent.kind() == kind.into())
+}
+
 fn highlight_node_keywords(
     document: &LuaDocument,
     node: LuaSyntaxNode,
@@ -1,6 +1,9 @@
 mod external_range_format;
 
-use emmylua_code_analysis::{FormattingOptions, range_format_code};
+use emmylua_code_analysis::{
+    EmmyrcFormatter, FormattingOptions, RangeFormatResult, range_format_code,
+};
+use emmylua_code_style::range_format_lua_code;
 use lsp_types::{
     ClientCapabilities, DocumentRangeFormattingParams, OneOf, Position, Range, ServerCapabilities,
     TextEdit,
@@ -9,7 +12,10 @@ use tokio_util::sync::CancellationToken;
 
 use crate::{
     context::ServerContextSnapshot,
-    handlers::document_range_formatting::external_range_format::external_tool_range_format,
+    handlers::{
+        document_formatting::native_code_style,
+        document_range_formatting::external_range_format::external_tool_range_format,
+    },
 };
 
 use super::RegisterCapabilities;
@@ -58,6 +64,21 @@ pub async fn on_range_formatting_handler(
             formatting_options,
         )
         .await?
+    } else if emmyrc.format.formatter == EmmyrcFormatter::Native {
+        let style = native_code_style(&emmyrc.format, &formatting_options);
+        let range = range_format_lua_code(
+            text,
+            &style,
+            request_range.start.line as usize,
+            request_range.end.line as usize,
+        )?;
+        RangeFormatResult {
+            start_line: range.start_line as i32,
+            start_col: 0,
+            end_line: range.end_line as i32,
+            end_col: 0,
+            text: range.text,
+        }
     } else {
         range_format_code(
             text,
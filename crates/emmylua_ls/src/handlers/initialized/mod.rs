@@ -13,14 +13,14 @@ use crate::{
     },
     handlers::{
         initialized::collect_files::calculate_include_and_exclude,
-        text_document::register_files_watch,
+        text_document::register_files_watch, type_hierarchy::register_type_hierarchy,
     },
     logger::init_logger,
 };
 pub use client_config::{ClientConfig, get_client_config};
 use codestyle::load_editorconfig;
 use collect_files::collect_files;
-use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, uri_to_file_path};
+use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, get_index_cache_path, uri_to_file_path};
 use lsp_types::InitializeParams;
 use tokio::sync::RwLock;
 
@@ -96,6 +96,7 @@ pub async fn initialized_handler(
         log::info!("workspace manager initialized");
     }
     register_files_watch(context.clone(), &params.capabilities).await;
+    register_type_hierarchy(context.client(), &params.capabilities);
     Some(())
 }
 
@@ -160,7 +161,21 @@ pub async fn init_analysis(
             Some(format!("Indexing {} files", file_count)),
         );
 
-        mut_analysis.update_files_by_path(files);
+        let index_cache_path = if emmyrc.workspace.enable_index_cache {
+            workspace_folders
+                .first()
+                .and_then(|main_root| get_index_cache_path(&emmyrc, main_root))
+        } else {
+            None
+        };
+        if let Some(cache_path) = index_cache_path {
+            mut_analysis.update_files_by_path_with_cache(files, &cache_path);
+            if let Err(e) = mut_analysis.save_index_cache(&cache_path) {
+                log::warn!("failed to save index cache {:?}: {}", cache_path, e);
+            }
+        } else {
+            mut_analysis.update_files_by_path(files);
+        }
     }
 
     status_bar.update_progress_task(
@@ -0,0 +1,100 @@
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, LuaSemanticDeclId, SemanticDeclLevel};
+use emmylua_parser::{LuaAstNode, LuaTokenKind};
+use lsp_types::{
+    ClientCapabilities, LinkedEditingRangeParams, LinkedEditingRangeServerCapabilities,
+    LinkedEditingRanges, Position, ServerCapabilities,
+};
+use rowan::TokenAtOffset;
+use tokio_util::sync::CancellationToken;
+
+use crate::context::ServerContextSnapshot;
+
+use super::RegisterCapabilities;
+
+pub async fn on_linked_editing_range_handler(
+    context: ServerContextSnapshot,
+    params: LinkedEditingRangeParams,
+    _: CancellationToken,
+) -> Option<LinkedEditingRanges> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+    let position = params.text_document_position_params.position;
+
+    linked_editing_range(&analysis, file_id, position)
+}
+
+/// The declaration and references of the local at `position`, which are all in its file.
+pub fn linked_editing_range(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<LinkedEditingRanges> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let root = semantic_model.get_root();
+    let position_offset = {
+        let document = semantic_model.get_document();
+        document.get_offset(position.line as usize, position.character as usize)?
+    };
+
+    if position_offset > root.syntax().text_range().end() {
+        return None;
+    }
+
+    let token = match root.syntax().token_at_offset(position_offset) {
+        TokenAtOffset::None => return None,
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+    };
+    if token.kind() != LuaTokenKind::TkName.into() {
+        return None;
+    }
+
+    let LuaSemanticDeclId::LuaDecl(decl_id) =
+        semantic_model.find_decl(token.into(), SemanticDeclLevel::NoTrace)?
+    else {
+        return None;
+    };
+    let db = semantic_model.get_db();
+    let decl = db
+        .get_decl_index()
+        .get_decl_tree(&file_id)?
+        .get_decl(&decl_id)?;
+    if !decl.is_local() {
+        return None;
+    }
+
+    let document = semantic_model.get_document();
+    let mut ranges = vec![document.to_lsp_range(decl.get_range())?];
+    if let Some(decl_refs) = db
+        .get_reference_index()
+        .get_decl_references(&file_id, &decl_id)
+    {
+        for decl_ref in &decl_refs.cells {
+            let range = document.to_lsp_range(decl_ref.range)?;
+            if !ranges.contains(&range) {
+                ranges.push(range);
+            }
+        }
+    }
+
+    Some(LinkedEditingRanges {
+        ranges,
+        word_pattern: None,
+    })
+}
+
+pub struct LinkedEditingRangeCapabilities;
+
+impl RegisterCapabilities for LinkedEditingRangeCapabilities {
+    fn register_capabilities(server_capabilities: &mut ServerCapabilities, _: &ClientCapabilities) {
+        server_capabilities.linked_editing_range_provider =
+            Some(LinkedEditingRangeServerCapabilities::Simple(true));
+    }
+}
@@ -21,6 +21,7 @@ mod implementation;
 mod initialized;
 mod inlay_hint;
 mod inline_values;
+mod linked_editing_range;
 mod notification_handler;
 mod references;
 mod rename;
@@ -29,6 +30,8 @@ mod response_handler;
 mod semantic_token;
 mod signature_helper;
 mod text_document;
+mod type_definition;
+mod type_hierarchy;
 mod workspace;
 mod workspace_symbol;
 
@@ -74,6 +77,7 @@ capabilities!(modules: {
     document_link => DocumentLinkCapabilities,
     document_selection_range => DocumentSelectionRangeCapabilities,
     document_highlight => DocumentHighlightCapabilities,
+    linked_editing_range => LinkedEditingRangeCapabilities,
     document_formatting => DocumentFormattingCapabilities,
     document_range_formatting => DocumentRangeFormattingCapabilities,
     // document_type_format => DocumentTypeFormattingCapabilities,
@@ -81,6 +85,7 @@ capabilities!(modules: {
     inlay_hint => InlayHintCapabilities,
     definition => DefinitionCapabilities,
     implementation => ImplementationCapabilities,
+    type_definition => TypeDefinitionCapabilities,
     references => ReferencesCapabilities,
     rename => RenameCapabilities,
     code_lens => CodeLensCapabilities,
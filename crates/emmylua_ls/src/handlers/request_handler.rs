@@ -7,11 +7,13 @@ use lsp_types::request::{
     CodeActionRequest, CodeLensRequest, CodeLensResolve, ColorPresentationRequest, Completion,
     DocumentColor, DocumentDiagnosticRequest, DocumentHighlightRequest, DocumentLinkRequest,
     DocumentLinkResolve, DocumentSymbolRequest, ExecuteCommand, FoldingRangeRequest, Formatting,
-    GotoDefinition, GotoImplementation, HoverRequest, InlayHintRequest, InlayHintResolveRequest,
-    InlineValueRequest, OnTypeFormatting, PrepareRenameRequest, RangeFormatting, References,
-    Rename, Request as LspRequest, ResolveCompletionItem, SelectionRangeRequest,
-    SemanticTokensFullRequest, SignatureHelpRequest, WorkspaceDiagnosticRequest,
-    WorkspaceSymbolRequest,
+    GotoDefinition, GotoImplementation, GotoTypeDefinition, HoverRequest, InlayHintRequest,
+    InlayHintResolveRequest, InlineValueRequest, LinkedEditingRange, OnTypeFormatting,
+    PrepareRenameRequest, RangeFormatting, References, Rename, Request as LspRequest,
+    ResolveCompletionItem, SelectionRangeRequest, SemanticTokensFullDeltaRequest,
+    SemanticTokensFullRequest, SemanticTokensRangeRequest, SignatureHelpRequest,
+    TypeHierarchyPrepare, TypeHierarchySubtypes, TypeHierarchySupertypes,
+    WorkspaceDiagnosticRequest, WorkspaceSymbolRequest,
 };
 
 use crate::{
@@ -44,10 +46,18 @@ use super::{
     implementation::on_implementation_handler,
     inlay_hint::{on_inlay_hint_handler, on_resolve_inlay_hint},
     inline_values::on_inline_values_handler,
+    linked_editing_range::on_linked_editing_range_handler,
     references::on_references_handler,
     rename::{on_prepare_rename_handler, on_rename_handler},
-    semantic_token::on_semantic_token_handler,
+    semantic_token::{
+        on_semantic_token_delta_handler, on_semantic_token_handler, on_semantic_token_range_handler,
+    },
     signature_helper::on_signature_helper_handler,
+    type_definition::on_type_definition_handler,
+    type_hierarchy::{
+        on_prepare_type_hierarchy_handler, on_type_hierarchy_subtypes_handler,
+        on_type_hierarchy_supertypes_handler,
+    },
     workspace_symbol::on_workspace_symbol_handler,
 };
 
@@ -101,6 +111,10 @@ pub async fn on_request_handler(
         InlayHintResolveRequest => on_resolve_inlay_hint,
         GotoDefinition => on_goto_definition_handler,
         GotoImplementation => on_implementation_handler,
+        GotoTypeDefinition => on_type_definition_handler,
+        TypeHierarchyPrepare => on_prepare_type_hierarchy_handler,
+        TypeHierarchySupertypes => on_type_hierarchy_supertypes_handler,
+        TypeHierarchySubtypes => on_type_hierarchy_subtypes_handler,
         References => on_references_handler,
         Rename => on_rename_handler,
         PrepareRenameRequest => on_prepare_rename_handler,
@@ -108,7 +122,10 @@ pub async fn on_request_handler(
         CodeLensResolve => on_resolve_code_lens_handler,
         SignatureHelpRequest => on_signature_helper_handler,
         DocumentHighlightRequest => on_document_highlight_handler,
+        LinkedEditingRange => on_linked_editing_range_handler,
         SemanticTokensFullRequest => on_semantic_token_handler,
+        SemanticTokensFullDeltaRequest => on_semantic_token_delta_handler,
+        SemanticTokensRangeRequest => on_semantic_token_range_handler,
         ExecuteCommand => on_execute_command_handler,
         CodeActionRequest => on_code_action_handler,
         InlineValueRequest => on_inline_values_handler,
@@ -17,13 +17,15 @@ use emmylua_parser::{
 };
 use emmylua_parser_desc::{CodeBlockHighlightKind, DescItem, DescItemKind};
 use lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType};
-use rowan::{NodeOrToken, TextRange, TextSize};
+use rowan::{NodeOrToken, TextRange, TextSize, WalkEvent};
 
+/// Build the tokens of the file, or only of the syntax overlapping `range` when given.
 pub fn build_semantic_tokens(
     semantic_model: &SemanticModel,
     support_muliline_token: bool,
     client_id: ClientId,
     emmyrc: &Emmyrc,
+    range: Option<TextRange>,
 ) -> Option<Vec<SemanticToken>> {
     let root = semantic_model.get_root();
     let document = semantic_model.get_document();
@@ -34,7 +36,20 @@ pub fn build_semantic_tokens(
         SEMANTIC_TOKEN_MODIFIERS.to_vec(),
     );
 
-    for node_or_token in root.syntax().descendants_with_tokens() {
+    let mut preorder = root.syntax().preorder_with_tokens();
+    while let Some(event) = preorder.next() {
+        let WalkEvent::Enter(node_or_token) = event else {
+            continue;
+        };
+        if let Some(range) = range
+            && node_or_token.text_range().intersect(range).is_none()
+        {
+            if node_or_token.as_node().is_some() {
+                preorder.skip_subtree();
+            }
+            continue;
+        }
+
         match node_or_token {
             NodeOrToken::Node(node) => {
                 build_node_semantic_token(semantic_model, &mut builder, node, emmyrc);
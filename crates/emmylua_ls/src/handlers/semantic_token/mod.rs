@@ -2,23 +2,34 @@ mod build_semantic_tokens;
 mod function_string_highlight;
 mod language_injector;
 mod semantic_token_builder;
+mod semantic_token_delta;
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use crate::context::{ClientId, ServerContextSnapshot};
 use build_semantic_tokens::build_semantic_tokens;
 use emmylua_code_analysis::{EmmyLuaAnalysis, FileId};
 use lsp_types::{
-    ClientCapabilities, SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend,
-    SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
-    SemanticTokensServerCapabilities, ServerCapabilities,
+    ClientCapabilities, Range, SemanticTokens, SemanticTokensDelta, SemanticTokensDeltaParams,
+    SemanticTokensFullDeltaResult, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, SemanticTokensParams, SemanticTokensRangeParams,
+    SemanticTokensRangeResult, SemanticTokensResult, SemanticTokensServerCapabilities,
+    ServerCapabilities, Uri,
 };
 #[allow(unused)]
 pub use semantic_token_builder::{
     CustomSemanticTokenType, SEMANTIC_TOKEN_MODIFIERS, SEMANTIC_TOKEN_TYPES,
 };
+use semantic_token_delta::diff_semantic_tokens;
 use tokio_util::sync::CancellationToken;
 
 use super::RegisterCapabilities;
 
+static NEXT_RESULT_ID: AtomicU64 = AtomicU64::new(1);
+
 pub async fn on_semantic_token_handler(
     context: ServerContextSnapshot,
     params: SemanticTokensParams,
@@ -32,9 +43,102 @@ pub async fn on_semantic_token_handler(
     let client_id = workspace_manager.client_config.client_id;
     let _ = workspace_manager;
 
-    semantic_token(
+    let SemanticTokensResult::Tokens(tokens) = semantic_token(
+        &analysis,
+        file_id,
+        context.lsp_features().supports_multiline_tokens(),
+        client_id,
+    )?
+    else {
+        return None;
+    };
+    let mut workspace_manager = context.workspace_manager().write().await;
+    let tokens = store_semantic_tokens(&mut workspace_manager.semantic_tokens, uri, tokens);
+
+    Some(SemanticTokensResult::Tokens(tokens))
+}
+
+pub async fn on_semantic_token_delta_handler(
+    context: ServerContextSnapshot,
+    params: SemanticTokensDeltaParams,
+    _: CancellationToken,
+) -> Option<SemanticTokensFullDeltaResult> {
+    let uri = params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+
+    let workspace_manager = context.workspace_manager().read().await;
+    let client_id = workspace_manager.client_config.client_id;
+    let _ = workspace_manager;
+
+    let SemanticTokensResult::Tokens(tokens) = semantic_token(
+        &analysis,
+        file_id,
+        context.lsp_features().supports_multiline_tokens(),
+        client_id,
+    )?
+    else {
+        return None;
+    };
+    let mut workspace_manager = context.workspace_manager().write().await;
+    Some(semantic_tokens_delta(
+        &mut workspace_manager.semantic_tokens,
+        uri,
+        tokens,
+        params.previous_result_id,
+    ))
+}
+
+/// Gives `tokens` a fresh result id and remembers them as the last result sent for `uri`.
+pub fn store_semantic_tokens(
+    cache: &mut HashMap<Uri, SemanticTokens>,
+    uri: Uri,
+    mut tokens: SemanticTokens,
+) -> SemanticTokens {
+    tokens.result_id = Some(NEXT_RESULT_ID.fetch_add(1, Ordering::Relaxed).to_string());
+    cache.insert(uri, tokens.clone());
+    tokens
+}
+
+/// Stores `tokens` and answers with the edits from the result `previous_result_id` names.
+pub fn semantic_tokens_delta(
+    cache: &mut HashMap<Uri, SemanticTokens>,
+    uri: Uri,
+    tokens: SemanticTokens,
+    previous_result_id: String,
+) -> SemanticTokensFullDeltaResult {
+    let previous = cache.remove(&uri);
+    let tokens = store_semantic_tokens(cache, uri, tokens);
+
+    // the client sends the id of the last result it got, anything else needs the full tokens
+    match previous {
+        Some(previous) if previous.result_id == Some(previous_result_id) => {
+            SemanticTokensFullDeltaResult::TokensDelta(SemanticTokensDelta {
+                result_id: tokens.result_id,
+                edits: diff_semantic_tokens(&previous.data, &tokens.data),
+            })
+        }
+        _ => SemanticTokensFullDeltaResult::Tokens(tokens),
+    }
+}
+
+pub async fn on_semantic_token_range_handler(
+    context: ServerContextSnapshot,
+    params: SemanticTokensRangeParams,
+    _: CancellationToken,
+) -> Option<SemanticTokensRangeResult> {
+    let uri = params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+
+    let workspace_manager = context.workspace_manager().read().await;
+    let client_id = workspace_manager.client_config.client_id;
+    let _ = workspace_manager;
+
+    semantic_token_range(
         &analysis,
         file_id,
+        params.range,
         context.lsp_features().supports_multiline_tokens(),
         client_id,
     )
@@ -57,6 +161,7 @@ pub fn semantic_token(
         supports_multiline_tokens,
         client_id,
         emmyrc,
+        None,
     )?;
 
     Some(SemanticTokensResult::Tokens(SemanticTokens {
@@ -65,6 +170,35 @@ pub fn semantic_token(
     }))
 }
 
+pub fn semantic_token_range(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    range: Range,
+    supports_multiline_tokens: bool,
+    client_id: ClientId,
+) -> Option<SemanticTokensRangeResult> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let emmyrc = semantic_model.get_emmyrc();
+    if !emmyrc.semantic_tokens.enable {
+        return None;
+    }
+
+    let document = semantic_model.get_document();
+    let range = document.to_rowan_range(range)?;
+    let result = build_semantic_tokens(
+        &semantic_model,
+        supports_multiline_tokens,
+        client_id,
+        emmyrc,
+        Some(range),
+    )?;
+
+    Some(SemanticTokensRangeResult::Tokens(SemanticTokens {
+        result_id: None,
+        data: result,
+    }))
+}
+
 pub struct SemanticTokenCapabilities;
 
 impl RegisterCapabilities for SemanticTokenCapabilities {
@@ -78,7 +212,8 @@ impl RegisterCapabilities for SemanticTokenCapabilities {
                     token_modifiers: SEMANTIC_TOKEN_MODIFIERS.to_vec(),
                     token_types: SEMANTIC_TOKEN_TYPES.to_vec(),
                 },
-                full: Some(SemanticTokensFullOptions::Bool(true)),
+                full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                range: Some(true),
                 ..Default::default()
             }),
         );
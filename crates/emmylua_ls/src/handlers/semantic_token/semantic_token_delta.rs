@@ -0,0 +1,88 @@
+use lsp_types::{SemanticToken, SemanticTokensEdit};
+
+/// The edit turning `old` into `new`, replacing everything between their common prefix and suffix.
+/// Edit offsets count integers of the encoded array, five per token.
+pub fn diff_semantic_tokens(
+    old: &[SemanticToken],
+    new: &[SemanticToken],
+) -> Vec<SemanticTokensEdit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let deleted = old.len() - prefix - suffix;
+    let inserted = &new[prefix..new.len() - suffix];
+    if deleted == 0 && inserted.is_empty() {
+        return Vec::new();
+    }
+
+    vec![SemanticTokensEdit {
+        start: (prefix * 5) as u32,
+        delete_count: (deleted * 5) as u32,
+        data: if inserted.is_empty() {
+            None
+        } else {
+            Some(inserted.to_vec())
+        },
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(delta_line: u32, length: u32) -> SemanticToken {
+        SemanticToken {
+            delta_line,
+            delta_start: 0,
+            length,
+            token_type: 0,
+            token_modifiers_bitset: 0,
+        }
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let tokens = vec![token(0, 1), token(1, 2)];
+        assert_eq!(diff_semantic_tokens(&tokens, &tokens), vec![]);
+    }
+
+    #[test]
+    fn test_replaced_token() {
+        let old = vec![token(0, 1), token(1, 2), token(1, 3)];
+        let new = vec![token(0, 1), token(1, 5), token(1, 3)];
+        assert_eq!(
+            diff_semantic_tokens(&old, &new),
+            vec![SemanticTokensEdit {
+                start: 5,
+                delete_count: 5,
+                data: Some(vec![token(1, 5)]),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_inserted_and_deleted_tokens() {
+        let old = vec![token(0, 1), token(1, 3)];
+        let new = vec![token(0, 1), token(1, 2), token(1, 3)];
+        assert_eq!(
+            diff_semantic_tokens(&old, &new),
+            vec![SemanticTokensEdit {
+                start: 5,
+                delete_count: 0,
+                data: Some(vec![token(1, 2)]),
+            }]
+        );
+        assert_eq!(
+            diff_semantic_tokens(&new, &old),
+            vec![SemanticTokensEdit {
+                start: 5,
+                delete_count: 5,
+                data: None,
+            }]
+        );
+    }
+}
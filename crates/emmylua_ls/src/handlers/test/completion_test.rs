@@ -659,6 +659,36 @@ mod tests {
         Ok(())
     }
 
+    #[gtest]
+    fn test_template_literal() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        check!(ws.check_completion_with_kind(
+            r#"
+                ---@alias Event "click" | "close"
+
+                ---@param name `on_${Event}`
+                local function on(name)
+                end
+
+                on(<??>)
+            "#,
+            vec![
+                VirtualCompletionItem {
+                    label: "\"on_click\"".to_string(),
+                    kind: CompletionItemKind::ENUM_MEMBER,
+                    ..Default::default()
+                },
+                VirtualCompletionItem {
+                    label: "\"on_close\"".to_string(),
+                    kind: CompletionItemKind::ENUM_MEMBER,
+                    ..Default::default()
+                },
+            ],
+            CompletionTriggerKind::TRIGGER_CHARACTER,
+        ));
+        Ok(())
+    }
+
     #[gtest]
     fn test_str_tpl_ref_4() -> Result<()> {
         let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
@@ -2158,4 +2188,38 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[gtest]
+    fn test_utility_type_members() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@class User
+                ---@field id integer
+                ---@field name string
+                ---@field password string
+            "#,
+        );
+        check!(ws.check_completion_with_kind(
+            r#"
+                ---@type std.Pick<User, "id" | "name">
+                local user
+                user.<??>
+            "#,
+            vec![
+                VirtualCompletionItem {
+                    label: "id".to_string(),
+                    kind: CompletionItemKind::VARIABLE,
+                    ..Default::default()
+                },
+                VirtualCompletionItem {
+                    label: "name".to_string(),
+                    kind: CompletionItemKind::VARIABLE,
+                    ..Default::default()
+                },
+            ],
+            CompletionTriggerKind::TRIGGER_CHARACTER,
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+    use lsp_types::{Position, Range};
+
+    fn range(line: u32, start: u32, end: u32) -> Range {
+        Range::new(Position::new(line, start), Position::new(line, end))
+    }
+
+    #[gtest]
+    fn test_function_end() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_document_highlight(
+            "local function f()\n    return 1\ne<??>nd\n",
+            vec![range(0, 0, 5), range(0, 6, 14), range(2, 0, 3)],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_if_chain() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let code = "if a then\nelse<??>if b then\nelse\nend\n";
+        check!(ws.check_document_highlight(
+            code,
+            vec![
+                range(0, 0, 2),
+                range(0, 5, 9),
+                range(1, 0, 6),
+                range(1, 9, 13),
+                range(2, 0, 4),
+                range(3, 0, 3),
+            ],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_repeat_until() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_document_highlight(
+            "re<??>peat\n    local a = 1\nuntil true\n",
+            vec![range(0, 0, 6), range(2, 0, 5)],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_goto_label() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let code = r#"for i = 1, 10 do
+    if i > 5 then
+        go<??>to continue
+    end
+    goto continue
+    ::continue::
+end
+::continue::
+"#;
+        check!(ws.check_document_highlight(
+            code,
+            vec![range(2, 8, 21), range(4, 4, 17), range(5, 4, 16)],
+        ));
+        check!(ws.check_document_highlight(
+            "::done::\ndo\n    goto do<??>ne\nend\nlocal function f()\n    goto done\nend\n",
+            vec![range(0, 0, 8), range(2, 4, 13)],
+        ));
+        Ok(())
+    }
+}
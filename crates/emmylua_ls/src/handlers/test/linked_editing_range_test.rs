@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+    use lsp_types::{Position, Range};
+
+    fn range(line: u32, start: u32, end: u32) -> Range {
+        Range::new(Position::new(line, start), Position::new(line, end))
+    }
+
+    #[gtest]
+    fn test_local() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_linked_editing_range(
+            "local co<??>unt = 0\ncount = count + 1\nprint(count)\n",
+            Some(vec![
+                range(0, 6, 11),
+                range(1, 0, 5),
+                range(1, 8, 13),
+                range(2, 6, 11),
+            ]),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_param() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_linked_editing_range(
+            "local function f(value)\n    return val<??>ue\nend\n",
+            Some(vec![range(0, 17, 22), range(1, 11, 16)]),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_global() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_linked_editing_range("glo<??>bal_count = 0\nprint(global_count)\n", None,));
+        Ok(())
+    }
+}
@@ -2,11 +2,15 @@ mod code_actions_test;
 mod completion_resolve_test;
 mod completion_test;
 mod definition_test;
+mod document_highlight_test;
 mod hover_function_test;
 mod hover_test;
 mod implementation_test;
 mod inlay_hint_test;
+mod linked_editing_range_test;
 mod references_test;
 mod rename_test;
 mod semantic_token_test;
 mod signature_helper_test;
+mod type_definition_test;
+mod type_hierarchy_test;
@@ -1,7 +1,17 @@
 #[cfg(test)]
 mod tests {
-    use crate::handlers::test_lib::ProviderVirtualWorkspace;
+    use std::collections::{HashMap, HashSet};
+
+    use crate::context::ClientId;
+    use crate::handlers::semantic_token::{
+        semantic_token, semantic_tokens_delta, store_semantic_tokens,
+    };
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, VirtualSemanticToken, check};
     use googletest::prelude::*;
+    use lsp_types::{
+        Position, Range, SemanticTokenType, SemanticTokens, SemanticTokensFullDeltaResult,
+        SemanticTokensResult,
+    };
 
     #[gtest]
     fn test_1() -> Result<()> {
@@ -14,4 +24,87 @@ mod tests {
         );
         Ok(())
     }
+
+    #[gtest]
+    fn test_range() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_semantic_token_range(
+            "local a = 1\nlocal b = 2\nlocal c = 3\n",
+            Range::new(Position::new(1, 0), Position::new(1, 11)),
+            vec![
+                VirtualSemanticToken {
+                    line: 1,
+                    start: 6,
+                    length: 1,
+                    token_type: SemanticTokenType::VARIABLE,
+                    token_modifier: HashSet::new(),
+                },
+                VirtualSemanticToken {
+                    line: 1,
+                    start: 8,
+                    length: 1,
+                    token_type: SemanticTokenType::OPERATOR,
+                    token_modifier: HashSet::new(),
+                },
+                VirtualSemanticToken {
+                    line: 1,
+                    start: 10,
+                    length: 1,
+                    token_type: SemanticTokenType::NUMBER,
+                    token_modifier: HashSet::new(),
+                },
+            ],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_delta() -> Result<()> {
+        fn tokens(ws: &mut ProviderVirtualWorkspace, content: &str) -> Result<SemanticTokens> {
+            let file_id = ws.def_file("delta.lua", content);
+            match semantic_token(&ws.analysis, file_id, true, ClientId::VSCode) {
+                Some(SemanticTokensResult::Tokens(tokens)) => Ok(tokens),
+                _ => Err("expected SemanticTokensResult::Tokens").or_fail(),
+            }
+        }
+
+        let mut ws = ProviderVirtualWorkspace::new();
+        let uri = ws.virtual_url_generator.new_uri("delta.lua");
+        let mut cache = HashMap::new();
+
+        let old = tokens(&mut ws, "local a = 1\nlocal b = 2\n")?;
+        let full = store_semantic_tokens(&mut cache, uri.clone(), old.clone());
+        let full_id = full.result_id.clone().or_fail()?;
+
+        let new = tokens(&mut ws, "local a = 1\nlocal b = 'b'\n")?;
+        let SemanticTokensFullDeltaResult::TokensDelta(delta) =
+            semantic_tokens_delta(&mut cache, uri.clone(), new.clone(), full_id.clone())
+        else {
+            return fail!("expected a delta for the last result id");
+        };
+        verify_that!(delta.result_id, some(not(eq(&full_id))))?;
+        verify_eq!(delta.edits.len(), 1)?;
+        let edit = &delta.edits[0];
+        let mut data = old.data.clone();
+        let start = edit.start as usize / 5;
+        data.splice(
+            start..start + edit.delete_count as usize / 5,
+            edit.data.clone().unwrap_or_default(),
+        );
+        verify_eq!(data, new.data)?;
+
+        // the id of the full result is stale once a delta was sent
+        let result = semantic_tokens_delta(&mut cache, uri.clone(), new.clone(), full_id);
+        let SemanticTokensFullDeltaResult::Tokens(fallback) = result else {
+            return fail!("expected full tokens for a stale result id, got {result:?}");
+        };
+        verify_eq!(fallback.data, new.data.clone())?;
+
+        let result = semantic_tokens_delta(&mut cache, uri, new.clone(), "unknown".to_string());
+        let SemanticTokensFullDeltaResult::Tokens(fallback) = result else {
+            return fail!("expected full tokens for an unknown result id, got {result:?}");
+        };
+        verify_eq!(fallback.data, new.data)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, VirtualLocation, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_class_instance() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file(
+            "player.lua",
+            r#"
+                ---@class Player
+                local Player = {}
+
+                ---@return Player
+                function Player.new()
+                    return setmetatable({}, { __index = Player })
+                end
+
+                return Player
+            "#,
+        );
+        check!(ws.check_type_definition(
+            r#"
+                local Player = require("player")
+                local p<??> = Player.new()
+            "#,
+            vec![VirtualLocation {
+                file: "player.lua".to_string(),
+                line: 1,
+            }],
+        ));
+        check!(ws.check_type_definition(
+            r#"
+                local Player = require("player")
+                local p = Player.new()
+                print(p<??>)
+            "#,
+            vec![VirtualLocation {
+                file: "player.lua".to_string(),
+                line: 1,
+            }],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_generic() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_definition(
+            r#"
+                ---@class List<T>
+                ---@field items T[]
+
+                ---@type List<string>
+                local li<??>st
+            "#,
+            vec![VirtualLocation {
+                file: "".to_string(),
+                line: 1,
+            }],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_union() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_definition(
+            r#"
+                ---@class Cat
+
+                ---@class Dog
+
+                ---@param pet Cat | Dog | nil
+                local function feed(pet)
+                    print(pe<??>t)
+                end
+            "#,
+            vec![
+                VirtualLocation {
+                    file: "".to_string(),
+                    line: 1,
+                },
+                VirtualLocation {
+                    file: "".to_string(),
+                    line: 3,
+                },
+            ],
+        ));
+        Ok(())
+    }
+}
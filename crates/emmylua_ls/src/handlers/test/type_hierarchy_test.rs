@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_class_hierarchy() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file(
+            "shapes.lua",
+            r#"
+                ---@class Drawable
+
+                ---@class Shape : Drawable
+
+                ---@class Circle : Shape
+
+                ---@class Square : Shape, Drawable
+            "#,
+        );
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@type Sha<??>pe
+                local shape
+            "#,
+            "Shape",
+            vec!["Drawable"],
+            vec!["Circle", "Square"],
+        ));
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@type Square
+                local squ<??>are
+            "#,
+            "Square",
+            vec!["Drawable", "Shape"],
+            vec![],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_generic_super() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@class List<T>
+
+                ---@class Names : List<string>
+
+                ---@class Na<??>mes2 : Names
+            "#,
+            "Names2",
+            vec!["Names"],
+            vec![],
+        ));
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@type Li<??>st<string>
+                local list
+            "#,
+            "List",
+            vec![],
+            vec!["Names"],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_partial_class() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file(
+            "a.lua",
+            r#"
+                ---@class Base
+
+                ---@class Mixin
+
+                ---@class (partial) Widget : Base
+            "#,
+        );
+        ws.def_file(
+            "b.lua",
+            r#"
+                ---@class (partial) Widget : Mixin
+            "#,
+        );
+        ws.def_file(
+            "c.lua",
+            r#"
+                ---@class Button : Widget
+            "#,
+        );
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@type Wid<??>get
+                local widget
+            "#,
+            "Widget",
+            vec!["Base", "Mixin"],
+            vec!["Button"],
+        ));
+        Ok(())
+    }
+}
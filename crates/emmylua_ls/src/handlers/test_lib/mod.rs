@@ -4,8 +4,9 @@ use itertools::Itertools;
 use lsp_types::{
     CodeActionOrCommand, CompletionItem, CompletionItemKind, CompletionResponse,
     CompletionTriggerKind, GotoDefinitionResponse, Hover, HoverContents, InlayHintLabel, Location,
-    MarkupContent, Position, SemanticTokenModifier, SemanticTokenType, SemanticTokensResult,
-    SignatureHelpContext, SignatureHelpTriggerKind, SignatureInformation, TextEdit,
+    MarkupContent, Position, Range, SemanticToken, SemanticTokenModifier, SemanticTokenType,
+    SemanticTokensRangeResult, SemanticTokensResult, SignatureHelpContext,
+    SignatureHelpTriggerKind, SignatureInformation, TextEdit,
 };
 use std::collections::HashSet;
 use std::{ops::Deref, sync::Arc};
@@ -18,12 +19,20 @@ use crate::{
         completion::{completion, completion_resolve},
         inlay_hint::inlay_hint,
         rename::rename,
-        semantic_token::semantic_token,
+        semantic_token::{semantic_token, semantic_token_range},
         signature_helper::signature_help,
     },
 };
 
-use super::{hover::hover, implementation::implementation, references::references};
+use super::{
+    document_highlight::document_highlight,
+    hover::hover,
+    implementation::implementation,
+    linked_editing_range::linked_editing_range,
+    references::references,
+    type_definition::type_definition,
+    type_hierarchy::{prepare_type_hierarchy, subtypes, supertypes},
+};
 use crate::handlers::semantic_token::{SEMANTIC_TOKEN_MODIFIERS, SEMANTIC_TOKEN_TYPES};
 
 /// Calling this macro on a [`Result`] is equivalent to `result?`,
@@ -310,6 +319,92 @@ impl ProviderVirtualWorkspace {
         Self::assert_definition(result, expected)
     }
 
+    pub fn check_type_definition(
+        &mut self,
+        block_str: &str,
+        expected: Vec<VirtualLocation>,
+    ) -> Result<()> {
+        let (content, position) = Self::handle_file_content(block_str)?;
+        let file_id = self.def(&content);
+        let result = type_definition(&self.analysis, file_id, position)
+            .ok_or("failed to get go to type definition response")
+            .or_fail()?;
+
+        Self::assert_definition(result, expected)
+    }
+
+    /// Check the names of the supertypes and subtypes of the type at the cursor.
+    pub fn check_type_hierarchy(
+        &mut self,
+        block_str: &str,
+        expected_name: &str,
+        expected_supertypes: Vec<&str>,
+        expected_subtypes: Vec<&str>,
+    ) -> Result<()> {
+        let (content, position) = Self::handle_file_content(block_str)?;
+        let file_id = self.def(&content);
+        let items = prepare_type_hierarchy(&self.analysis, file_id, position)
+            .ok_or("failed to prepare type hierarchy")
+            .or_fail()?;
+        verify_that!(items.len(), eq(1))?;
+        let item = &items[0];
+        verify_that!(item.name.as_str(), eq(expected_name))?;
+
+        let names = |items: Option<Vec<lsp_types::TypeHierarchyItem>>| {
+            items
+                .unwrap_or_default()
+                .into_iter()
+                .map(|item| item.name)
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+        verify_that!(
+            names(supertypes(&self.analysis, item)),
+            eq(&expected_supertypes.into_iter().sorted().collect::<Vec<_>>())
+        )?;
+        verify_that!(
+            names(subtypes(&self.analysis, item)),
+            eq(&expected_subtypes.into_iter().sorted().collect::<Vec<_>>())
+        )
+    }
+
+    pub fn check_document_highlight(
+        &mut self,
+        block_str: &str,
+        expected: Vec<Range>,
+    ) -> Result<()> {
+        let (content, position) = Self::handle_file_content(block_str)?;
+        let file_id = self.def(&content);
+        let result = document_highlight(&self.analysis, file_id, position)
+            .ok_or("failed to get document highlight")
+            .or_fail()?;
+        let ranges = result
+            .into_iter()
+            .map(|highlight| highlight.range)
+            .sorted_by_key(|range| (range.start.line, range.start.character))
+            .collect::<Vec<_>>();
+
+        verify_eq!(ranges, expected)
+    }
+
+    pub fn check_linked_editing_range(
+        &mut self,
+        block_str: &str,
+        expected: Option<Vec<Range>>,
+    ) -> Result<()> {
+        let (content, position) = Self::handle_file_content(block_str)?;
+        let file_id = self.def(&content);
+        let ranges = linked_editing_range(&self.analysis, file_id, position).map(|result| {
+            result
+                .ranges
+                .into_iter()
+                .sorted_by_key(|range| (range.start.line, range.start.character))
+                .collect::<Vec<_>>()
+        });
+
+        verify_eq!(ranges, expected)
+    }
+
     fn assert_definition(
         result: GotoDefinitionResponse,
         expected: Vec<VirtualLocation>,
@@ -511,6 +606,30 @@ impl ProviderVirtualWorkspace {
             return fail!("expected SemanticTokensResult::Tokens, got {result:?}");
         };
 
+        Self::assert_semantic_tokens(result.data, expected)
+    }
+
+    pub fn check_semantic_token_range(
+        &mut self,
+        block_str: &str,
+        range: Range,
+        expected: Vec<VirtualSemanticToken>,
+    ) -> Result<()> {
+        let file_id = self.def(block_str);
+        let result = semantic_token_range(&self.analysis, file_id, range, true, ClientId::VSCode)
+            .ok_or("failed to get semantic tokens")
+            .or_fail()?;
+        let SemanticTokensRangeResult::Tokens(result) = result else {
+            return fail!("expected SemanticTokensRangeResult::Tokens, got {result:?}");
+        };
+
+        Self::assert_semantic_tokens(result.data, expected)
+    }
+
+    fn assert_semantic_tokens(
+        data: Vec<SemanticToken>,
+        expected: Vec<VirtualSemanticToken>,
+    ) -> Result<()> {
         fn type_index_to_type(index: u32) -> Result<SemanticTokenType> {
             SEMANTIC_TOKEN_TYPES
                 .get(index as usize)
@@ -540,7 +659,7 @@ impl ProviderVirtualWorkspace {
         let mut virtual_result = Vec::new();
         let mut line = 0;
         let mut start = 0;
-        for token in result.data {
+        for token in data {
             if token.delta_line > 0 {
                 line += token.delta_line;
                 start = 0;
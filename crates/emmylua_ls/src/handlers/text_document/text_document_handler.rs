@@ -35,7 +35,7 @@ pub async fn on_did_open_text_document(
     // Update file and get diagnostic settings
     let (file_id, supports_pull, interval) = {
         let mut analysis = context.analysis().write().await;
-        let file_id = analysis.update_file_by_uri(&uri, Some(text));
+        let file_id = analysis.update_file_by_uri(&uri, Some(text.clone()));
         let emmyrc = analysis.get_emmyrc();
         let interval = emmyrc.diagnostics.diagnostic_interval.unwrap_or(500);
         let supports_pull = context.lsp_features().supports_pull_diagnostic();
@@ -55,6 +55,7 @@ pub async fn on_did_open_text_document(
     // Update open files list
     {
         let mut workspace = context.workspace_manager().write().await;
+        workspace.saved_texts.insert(uri.clone(), text);
         workspace.current_open_files.insert(uri);
     }
 
@@ -63,9 +64,22 @@ pub async fn on_did_open_text_document(
 
 pub async fn on_did_save_text_document(
     context: ServerContextSnapshot,
-    _: DidSaveTextDocumentParams,
+    params: DidSaveTextDocumentParams,
 ) -> Option<()> {
-    let emmyrc = context.analysis().read().await.get_emmyrc();
+    let uri = params.text_document.uri;
+    let (emmyrc, saved_text) = {
+        let analysis = context.analysis().read().await;
+        let saved_text = analysis.get_file_id(&uri).and_then(|file_id| {
+            let vfs = analysis.compilation.get_db().get_vfs();
+            Some(vfs.get_document(&file_id)?.get_text().to_string())
+        });
+        (analysis.get_emmyrc(), saved_text)
+    };
+    if let Some(saved_text) = saved_text {
+        let mut workspace = context.workspace_manager().write().await;
+        workspace.saved_texts.insert(uri, saved_text);
+    }
+
     if !emmyrc.workspace.enable_reindex {
         if context.lsp_features().supports_workspace_diagnostic() {
             context
@@ -155,6 +169,8 @@ pub async fn on_did_close_document(
     workspace
         .current_open_files
         .remove(&params.text_document.uri);
+    workspace.saved_texts.remove(&params.text_document.uri);
+    workspace.semantic_tokens.remove(&params.text_document.uri);
     drop(workspace);
     let lsp_features = context.lsp_features();
 
@@ -0,0 +1,132 @@
+use crate::context::ServerContextSnapshot;
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, LuaType, LuaTypeDeclId, SemanticModel};
+use emmylua_parser::{LuaAstNode, LuaTokenKind};
+use lsp_types::{
+    ClientCapabilities, GotoDefinitionResponse, Location, Position, ServerCapabilities,
+    TypeDefinitionProviderCapability,
+    request::{GotoTypeDefinitionParams, GotoTypeDefinitionResponse},
+};
+use rowan::TokenAtOffset;
+use tokio_util::sync::CancellationToken;
+
+use super::RegisterCapabilities;
+
+pub async fn on_type_definition_handler(
+    context: ServerContextSnapshot,
+    params: GotoTypeDefinitionParams,
+    _: CancellationToken,
+) -> Option<GotoTypeDefinitionResponse> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+    let position = params.text_document_position_params.position;
+
+    type_definition(&analysis, file_id, position)
+}
+
+pub fn type_definition(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<GotoTypeDefinitionResponse> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let root = semantic_model.get_root();
+    let position_offset = {
+        let document = semantic_model.get_document();
+        document.get_offset(position.line as usize, position.character as usize)?
+    };
+
+    if position_offset > root.syntax().text_range().end() {
+        return None;
+    }
+
+    let token = match root.syntax().token_at_offset(position_offset) {
+        TokenAtOffset::None => return None,
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+    };
+
+    let typ = semantic_model.get_semantic_info(token.into())?.typ;
+    let mut type_decl_ids = Vec::new();
+    collect_type_decl_ids(&typ, &mut type_decl_ids);
+
+    let locations: Vec<Location> = type_decl_ids
+        .iter()
+        .flat_map(|type_decl_id| get_type_decl_locations(&semantic_model, type_decl_id))
+        .collect();
+    if locations.is_empty() {
+        return None;
+    }
+
+    Some(GotoDefinitionResponse::Array(locations))
+}
+
+/// Collect the classes, aliases and enums `typ` refers to, a union gives every one of its members.
+pub fn collect_type_decl_ids(typ: &LuaType, type_decl_ids: &mut Vec<LuaTypeDeclId>) {
+    match typ {
+        LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id) => {
+            if !type_decl_ids.contains(type_decl_id) {
+                type_decl_ids.push(type_decl_id.clone());
+            }
+        }
+        LuaType::Generic(generic) => {
+            collect_type_decl_ids(&LuaType::Ref(generic.get_base_type_id()), type_decl_ids);
+        }
+        LuaType::Instance(instance) => collect_type_decl_ids(instance.get_base(), type_decl_ids),
+        LuaType::Array(array) => collect_type_decl_ids(array.get_base(), type_decl_ids),
+        LuaType::TypeGuard(inner) => collect_type_decl_ids(inner, type_decl_ids),
+        LuaType::Union(union) => {
+            for typ in union.into_vec() {
+                collect_type_decl_ids(&typ, type_decl_ids);
+            }
+        }
+        LuaType::Intersection(intersection) => {
+            for typ in intersection.get_types() {
+                collect_type_decl_ids(typ, type_decl_ids);
+            }
+        }
+        LuaType::MultiLineUnion(multi_union) => {
+            for (typ, _) in multi_union.get_unions() {
+                collect_type_decl_ids(typ, type_decl_ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn get_type_decl_locations(
+    semantic_model: &SemanticModel,
+    type_decl_id: &LuaTypeDeclId,
+) -> Vec<Location> {
+    let Some(type_decl) = semantic_model
+        .get_db()
+        .get_type_index()
+        .get_type_decl(type_decl_id)
+    else {
+        return Vec::new();
+    };
+
+    type_decl
+        .get_locations()
+        .iter()
+        .filter_map(|lua_location| {
+            let document = semantic_model.get_document_by_file_id(lua_location.file_id)?;
+            document.to_lsp_location(lua_location.range)
+        })
+        .collect()
+}
+
+pub struct TypeDefinitionCapabilities;
+
+impl RegisterCapabilities for TypeDefinitionCapabilities {
+    fn register_capabilities(server_capabilities: &mut ServerCapabilities, _: &ClientCapabilities) {
+        server_capabilities.type_definition_provider =
+            Some(TypeDefinitionProviderCapability::Simple(true));
+    }
+}
@@ -0,0 +1,66 @@
+use emmylua_code_analysis::{DbIndex, LuaType, LuaTypeDeclId};
+use lsp_types::{SymbolKind, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeHierarchyItemData {
+    pub type_decl_id: LuaTypeDeclId,
+}
+
+pub fn build_type_hierarchy_item(
+    db: &DbIndex,
+    type_decl_id: &LuaTypeDeclId,
+) -> Option<TypeHierarchyItem> {
+    let type_decl = db.get_type_index().get_type_decl(type_decl_id)?;
+    // a partial class declared in several files is shown at its first declaration
+    let location = type_decl.get_locations().first()?;
+    let document = db.get_vfs().get_document(&location.file_id)?;
+    let range = document.to_lsp_range(location.range)?;
+    let data = TypeHierarchyItemData {
+        type_decl_id: type_decl_id.clone(),
+    };
+
+    Some(TypeHierarchyItem {
+        name: type_decl.get_full_name().to_string(),
+        kind: if type_decl.is_enum() {
+            SymbolKind::ENUM
+        } else {
+            SymbolKind::CLASS
+        },
+        tags: None,
+        detail: None,
+        uri: document.get_uri(),
+        range,
+        selection_range: range,
+        data: Some(serde_json::to_value(data).ok()?),
+    })
+}
+
+pub fn build_supertypes(db: &DbIndex, type_decl_id: &LuaTypeDeclId) -> Vec<TypeHierarchyItem> {
+    let mut super_ids: Vec<LuaTypeDeclId> = Vec::new();
+    if let Some(supers) = db.get_type_index().get_super_types_iter(type_decl_id) {
+        for super_type in supers {
+            let super_id = match super_type {
+                LuaType::Ref(id) | LuaType::Def(id) => id.clone(),
+                LuaType::Generic(generic) => generic.get_base_type_id(),
+                _ => continue,
+            };
+            if !super_ids.contains(&super_id) {
+                super_ids.push(super_id);
+            }
+        }
+    }
+
+    super_ids
+        .iter()
+        .filter_map(|super_id| build_type_hierarchy_item(db, super_id))
+        .collect()
+}
+
+pub fn build_subtypes(db: &DbIndex, type_decl_id: &LuaTypeDeclId) -> Vec<TypeHierarchyItem> {
+    db.get_type_index()
+        .get_sub_types(type_decl_id)
+        .iter()
+        .filter_map(|sub_id| build_type_hierarchy_item(db, sub_id))
+        .collect()
+}
@@ -0,0 +1,151 @@
+mod build_type_hierarchy;
+
+pub use build_type_hierarchy::TypeHierarchyItemData;
+use build_type_hierarchy::{build_subtypes, build_supertypes, build_type_hierarchy_item};
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, FileId, LuaSemanticDeclId, LuaTypeDeclId, SemanticDeclLevel,
+};
+use emmylua_parser::{LuaAstNode, LuaTokenKind};
+use lsp_types::{
+    ClientCapabilities, Position, Registration, RegistrationParams,
+    TextDocumentRegistrationOptions, TypeHierarchyItem, TypeHierarchyOptions,
+    TypeHierarchyPrepareParams, TypeHierarchyRegistrationOptions, TypeHierarchySubtypesParams,
+    TypeHierarchySupertypesParams,
+};
+use rowan::TokenAtOffset;
+use tokio_util::sync::CancellationToken;
+
+use super::type_definition::collect_type_decl_ids;
+use crate::context::{ClientProxy, ServerContextSnapshot};
+
+pub async fn on_prepare_type_hierarchy_handler(
+    context: ServerContextSnapshot,
+    params: TypeHierarchyPrepareParams,
+    _: CancellationToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+    let position = params.text_document_position_params.position;
+
+    prepare_type_hierarchy(&analysis, file_id, position)
+}
+
+pub fn prepare_type_hierarchy(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let root = semantic_model.get_root();
+    let position_offset = {
+        let document = semantic_model.get_document();
+        document.get_offset(position.line as usize, position.character as usize)?
+    };
+
+    if position_offset > root.syntax().text_range().end() {
+        return None;
+    }
+
+    let token = match root.syntax().token_at_offset(position_offset) {
+        TokenAtOffset::None => return None,
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+    };
+
+    // a type name in a doc comment, otherwise the inferred type of a variable or expression
+    let mut type_decl_ids: Vec<LuaTypeDeclId> = Vec::new();
+    match semantic_model.find_decl(token.clone().into(), SemanticDeclLevel::default()) {
+        Some(LuaSemanticDeclId::TypeDecl(type_decl_id)) => type_decl_ids.push(type_decl_id),
+        _ => {
+            let typ = semantic_model.get_semantic_info(token.into())?.typ;
+            collect_type_decl_ids(&typ, &mut type_decl_ids);
+        }
+    }
+
+    let db = semantic_model.get_db();
+    let items: Vec<TypeHierarchyItem> = type_decl_ids
+        .iter()
+        .filter_map(|type_decl_id| build_type_hierarchy_item(db, type_decl_id))
+        .collect();
+    if items.is_empty() {
+        return None;
+    }
+
+    Some(items)
+}
+
+pub async fn on_type_hierarchy_supertypes_handler(
+    context: ServerContextSnapshot,
+    params: TypeHierarchySupertypesParams,
+    _: CancellationToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let analysis = context.analysis().read().await;
+    supertypes(&analysis, &params.item)
+}
+
+pub fn supertypes(
+    analysis: &EmmyLuaAnalysis,
+    item: &TypeHierarchyItem,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let data = serde_json::from_value::<TypeHierarchyItemData>(item.data.clone()?).ok()?;
+    Some(build_supertypes(
+        analysis.compilation.get_db(),
+        &data.type_decl_id,
+    ))
+}
+
+pub async fn on_type_hierarchy_subtypes_handler(
+    context: ServerContextSnapshot,
+    params: TypeHierarchySubtypesParams,
+    _: CancellationToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let analysis = context.analysis().read().await;
+    subtypes(&analysis, &params.item)
+}
+
+pub fn subtypes(
+    analysis: &EmmyLuaAnalysis,
+    item: &TypeHierarchyItem,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let data = serde_json::from_value::<TypeHierarchyItemData>(item.data.clone()?).ok()?;
+    Some(build_subtypes(
+        analysis.compilation.get_db(),
+        &data.type_decl_id,
+    ))
+}
+
+/// `ServerCapabilities` has no type hierarchy field, so the provider is registered dynamically.
+pub fn register_type_hierarchy(client: &ClientProxy, client_capabilities: &ClientCapabilities) {
+    let can_register = client_capabilities
+        .text_document
+        .as_ref()
+        .and_then(|text_document| text_document.type_hierarchy.as_ref())
+        .and_then(|type_hierarchy| type_hierarchy.dynamic_registration)
+        .unwrap_or_default();
+    if !can_register {
+        return;
+    }
+
+    let options = TypeHierarchyRegistrationOptions {
+        text_document_registration_options: TextDocumentRegistrationOptions {
+            document_selector: None,
+        },
+        type_hierarchy_options: TypeHierarchyOptions::default(),
+        static_registration_options: Default::default(),
+    };
+    let registration = Registration {
+        id: "emmylua_type_hierarchy".to_string(),
+        method: "textDocument/prepareTypeHierarchy".to_string(),
+        register_options: Some(serde_json::to_value(options).unwrap()),
+    };
+    client.dynamic_register_capability(RegistrationParams {
+        registrations: vec![registration],
+    });
+}
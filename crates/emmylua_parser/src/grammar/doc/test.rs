@@ -2474,6 +2474,47 @@ Syntax(Chunk)@0..123
         assert_ast_eq!(code, result);
     }
 
+    #[test]
+    fn test_template_literal_type() {
+        let code = r#"
+        ---@alias Handler `on_${Event}`
+        ---@param a `rpc:${Name}_${integer}`|nil
+        "#;
+        let result = r#"
+Syntax(Chunk)@0..98
+  Syntax(Block)@0..98
+    Token(TkEndOfLine)@0..1 "\n"
+    Token(TkWhitespace)@1..9 "        "
+    Syntax(Comment)@9..89
+      Token(TkDocStart)@9..13 "---@"
+      Syntax(DocTagAlias)@13..40
+        Token(TkTagAlias)@13..18 "alias"
+        Token(TkWhitespace)@18..19 " "
+        Token(TkName)@19..26 "Handler"
+        Token(TkWhitespace)@26..27 " "
+        Syntax(TypeStringTemplate)@27..40
+          Token(TkStringTemplateType)@27..40 "`on_${Event}`"
+      Token(TkEndOfLine)@40..41 "\n"
+      Token(TkWhitespace)@41..49 "        "
+      Token(TkDocStart)@49..53 "---@"
+      Syntax(DocTagParam)@53..89
+        Token(TkTagParam)@53..58 "param"
+        Token(TkWhitespace)@58..59 " "
+        Token(TkName)@59..60 "a"
+        Token(TkWhitespace)@60..61 " "
+        Syntax(TypeBinary)@61..89
+          Syntax(TypeStringTemplate)@61..85
+            Token(TkStringTemplateType)@61..85 "`rpc:${Name}_${integer}`"
+          Token(TkDocOr)@85..86 "|"
+          Syntax(TypeName)@86..89
+            Token(TkName)@86..89 "nil"
+    Token(TkEndOfLine)@89..90 "\n"
+    Token(TkWhitespace)@90..98 "        "
+        "#;
+
+        assert_ast_eq!(code, result);
+    }
+
     #[test]
     fn test_comment() {
         let code = r#"
@@ -2935,4 +2976,65 @@ Syntax(Chunk)@0..105
         "#;
         assert_ast_eq!(code, result);
     }
+
+    #[test]
+    fn test_mapped_type() {
+        let code = r#"
+        ---@alias Patch<T> { [K in keyof T as K]-?: T[K] }
+        "#;
+        let result = r#"
+Syntax(Chunk)@0..68
+  Syntax(Block)@0..68
+    Token(TkEndOfLine)@0..1 "\n"
+    Token(TkWhitespace)@1..9 "        "
+    Syntax(Comment)@9..59
+      Token(TkDocStart)@9..13 "---@"
+      Syntax(DocTagAlias)@13..59
+        Token(TkTagAlias)@13..18 "alias"
+        Token(TkWhitespace)@18..19 " "
+        Token(TkName)@19..24 "Patch"
+        Syntax(DocGenericDeclareList)@24..27
+          Token(TkLt)@24..25 "<"
+          Syntax(DocGenericParameter)@25..26
+            Token(TkName)@25..26 "T"
+          Token(TkGt)@26..27 ">"
+        Token(TkWhitespace)@27..28 " "
+        Syntax(TypeMapped)@28..59
+          Token(TkLeftBrace)@28..29 "{"
+          Token(TkWhitespace)@29..30 " "
+          Syntax(DocMappedKeys)@30..49
+            Token(TkLeftBracket)@30..31 "["
+            Token(TkName)@31..32 "K"
+            Token(TkWhitespace)@32..33 " "
+            Token(TkIn)@33..35 "in"
+            Token(TkWhitespace)@35..36 " "
+            Syntax(TypeUnary)@36..43
+              Token(TkDocKeyOf)@36..41 "keyof"
+              Token(TkWhitespace)@41..42 " "
+              Syntax(TypeName)@42..43
+                Token(TkName)@42..43 "T"
+            Token(TkWhitespace)@43..44 " "
+            Token(TkDocAs)@44..46 "as"
+            Token(TkWhitespace)@46..47 " "
+            Syntax(TypeName)@47..48
+              Token(TkName)@47..48 "K"
+            Token(TkRightBracket)@48..49 "]"
+          Token(TkMinus)@49..50 "-"
+          Token(TkDocQuestion)@50..51 "?"
+          Token(TkColon)@51..52 ":"
+          Token(TkWhitespace)@52..53 " "
+          Syntax(TypeIndexAccess)@53..57
+            Syntax(TypeName)@53..54
+              Token(TkName)@53..54 "T"
+            Token(TkLeftBracket)@54..55 "["
+            Syntax(TypeName)@55..56
+              Token(TkName)@55..56 "K"
+            Token(TkRightBracket)@56..57 "]"
+          Token(TkWhitespace)@57..58 " "
+          Token(TkRightBrace)@58..59 "}"
+    Token(TkEndOfLine)@59..60 "\n"
+    Token(TkWhitespace)@60..68 "        "
+        "#;
+        assert_ast_eq!(code, result);
+    }
 }
@@ -143,10 +143,31 @@ fn parse_primary_type(p: &mut LuaDocParser) -> DocParseResult {
 
 // { <name>: <type>, ... }
 // { <name> : <type>, ... }
+// { [<name> in <type>]: <type> }
+// { [<name> in <type> as <type>]+?: <type> }
 fn parse_object_or_mapped_type(p: &mut LuaDocParser) -> DocParseResult {
-    let m = p.mark(LuaSyntaxKind::TypeObject);
+    let mut m = p.mark(LuaSyntaxKind::TypeObject);
     p.bump();
 
+    if is_mapped_keys_start(p) {
+        m.set_kind(p, LuaSyntaxKind::TypeMapped);
+        parse_mapped_keys(p)?;
+        if matches!(
+            p.current_token(),
+            LuaTokenKind::TkPlus | LuaTokenKind::TkMinus
+        ) {
+            p.bump();
+            expect_token(p, LuaTokenKind::TkDocQuestion)?;
+        } else {
+            if_token_bump(p, LuaTokenKind::TkDocQuestion);
+        }
+        expect_token(p, LuaTokenKind::TkColon)?;
+        parse_type(p)?;
+        if_token_bump(p, LuaTokenKind::TkComma);
+        expect_token(p, LuaTokenKind::TkRightBrace)?;
+        return Ok(m.complete(p));
+    }
+
     if p.current_token() != LuaTokenKind::TkRightBrace {
         parse_typed_field(p)?;
         while p.current_token() == LuaTokenKind::TkComma {
@@ -163,6 +184,39 @@ fn parse_object_or_mapped_type(p: &mut LuaDocParser) -> DocParseResult {
     Ok(m.complete(p))
 }
 
+// `in` is not a keyword in doc comments, so `[<name> in` is recognized from the source text
+fn is_mapped_keys_start(p: &LuaDocParser) -> bool {
+    if p.current_token() != LuaTokenKind::TkLeftBracket {
+        return false;
+    }
+
+    let rest = &p.origin_text()[p.current_token_range().end_offset()..];
+    let mut words = rest.split_ascii_whitespace();
+    match (words.next(), words.next()) {
+        (Some(name), Some("in")) => name.chars().all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+// [<name> in <type>]
+// [<name> in <type> as <type>]
+fn parse_mapped_keys(p: &mut LuaDocParser) -> DocParseResult {
+    let m = p.mark(LuaSyntaxKind::DocMappedKeys);
+    p.bump();
+    expect_token(p, LuaTokenKind::TkName)?;
+    if p.current_token() == LuaTokenKind::TkName && p.current_token_text() == "in" {
+        p.set_current_token_kind(LuaTokenKind::TkIn);
+    }
+    expect_token(p, LuaTokenKind::TkIn)?;
+    parse_type(p)?;
+    if p.current_token() == LuaTokenKind::TkDocAs {
+        p.bump();
+        parse_type(p)?;
+    }
+    expect_token(p, LuaTokenKind::TkRightBracket)?;
+    Ok(m.complete(p))
+}
+
 // <name> : <type>
 // [<number>] : <type>
 // [<string>] : <type>
@@ -376,12 +430,10 @@ fn parse_suffixed_type(p: &mut LuaDocParser, cm: CompleteMarker) -> DocParseResu
             LuaTokenKind::TkLeftBracket => {
                 let mut m = cm.precede(p, LuaSyntaxKind::TypeArray);
                 p.bump();
-                if matches!(
-                    p.current_token(),
-                    LuaTokenKind::TkString | LuaTokenKind::TkInt | LuaTokenKind::TkName
-                ) {
-                    m.set_kind(p, LuaSyntaxKind::IndexExpr);
-                    p.bump();
+                // <type>[<key type>]
+                if p.current_token() != LuaTokenKind::TkRightBracket {
+                    m.set_kind(p, LuaSyntaxKind::TypeIndexAccess);
+                    parse_type(p)?;
                 }
                 expect_token(p, LuaTokenKind::TkRightBracket)?;
                 cm = m.complete(p);
@@ -7,6 +7,8 @@ mod lua_type_operator_kind;
 mod lua_version;
 mod lua_visibility_kind;
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 pub use lua_language_level::LuaLanguageLevel;
 pub use lua_non_std_symbol::{LuaNonStdSymbol, LuaNonStdSymbolSet};
 pub use lua_operator_kind::{BinaryOperator, UNARY_PRIORITY, UnaryOperator};
@@ -94,6 +96,25 @@ impl LuaKind {
     }
 }
 
+impl Serialize for LuaKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u16(self.get_raw())
+    }
+}
+
+impl<'de> Deserialize<'de> for LuaKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = u16::deserialize(deserializer)?;
+        Ok(LuaKind::from_raw(raw))
+    }
+}
+
 #[derive(Debug)]
 pub struct PriorityTable {
     pub left: i32,
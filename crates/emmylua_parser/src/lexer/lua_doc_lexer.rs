@@ -719,10 +719,27 @@ fn is_doc_whitespace(ch: char) -> bool {
 }
 
 fn read_doc_name<'a>(reader: &'a mut Reader) -> (&'a str, bool /* str tpl */) {
-    reader.bump();
     let mut str_tpl = false;
+    let mut in_tpl = false;
+    if reader.current_char() == '`' {
+        str_tpl = true;
+        in_tpl = true;
+    }
+    reader.bump();
     while !reader.is_eof() {
         match reader.current_char() {
+            // `on_${Event}`, the placeholder may hold any type
+            '$' if in_tpl && reader.next_char() == '{' => {
+                read_template_placeholder(reader);
+            }
+            '`' => {
+                str_tpl = true;
+                in_tpl = !in_tpl;
+                reader.bump();
+            }
+            ch if in_tpl && !ch.is_whitespace() => {
+                reader.bump();
+            }
             ch if is_name_continue(ch) => {
                 reader.bump();
             }
@@ -735,10 +752,6 @@ fn read_doc_name<'a>(reader: &'a mut Reader) -> (&'a str, bool /* str tpl */) {
 
                 reader.bump();
             }
-            '`' => {
-                str_tpl = true;
-                reader.bump();
-            }
             _ => break,
         }
     }
@@ -746,6 +759,28 @@ fn read_doc_name<'a>(reader: &'a mut Reader) -> (&'a str, bool /* str tpl */) {
     (reader.current_text(), str_tpl)
 }
 
+fn read_template_placeholder(reader: &mut Reader) {
+    // skip '${'
+    reader.bump();
+    reader.bump();
+    let mut depth = 1;
+    while !reader.is_eof() {
+        match reader.current_char() {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    reader.bump();
+                    return;
+                }
+            }
+            '`' => return,
+            _ => {}
+        }
+        reader.bump();
+    }
+}
+
 fn is_source_continue(ch: char) -> bool {
     is_name_continue(ch)
         || ch == '.'
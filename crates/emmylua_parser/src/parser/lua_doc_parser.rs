@@ -183,6 +183,10 @@ impl<'b> LuaDocParser<'_, 'b> {
         &self.origin_text()[range.start_offset..range.end_offset()]
     }
 
+    pub fn set_current_token_kind(&mut self, kind: LuaTokenKind) {
+        self.current_token = kind;
+    }
+
     pub fn origin_text(&self) -> &'b str {
         self.lua_parser.origin_text()
     }
@@ -23,6 +23,8 @@ pub enum LuaDocType {
     StrTpl(LuaDocStrTplType),
     MultiLineUnion(LuaDocMultiLineUnionType),
     Attribute(LuaDocAttributeType),
+    Mapped(LuaDocMappedType),
+    IndexAccess(LuaDocIndexAccessType),
 }
 
 impl LuaAstNode for LuaDocType {
@@ -43,6 +45,8 @@ impl LuaAstNode for LuaDocType {
             LuaDocType::StrTpl(it) => it.syntax(),
             LuaDocType::MultiLineUnion(it) => it.syntax(),
             LuaDocType::Attribute(it) => it.syntax(),
+            LuaDocType::Mapped(it) => it.syntax(),
+            LuaDocType::IndexAccess(it) => it.syntax(),
         }
     }
 
@@ -67,6 +71,8 @@ impl LuaAstNode for LuaDocType {
                 | LuaSyntaxKind::TypeStringTemplate
                 | LuaSyntaxKind::TypeMultiLineUnion
                 | LuaSyntaxKind::TypeAttribute
+                | LuaSyntaxKind::TypeMapped
+                | LuaSyntaxKind::TypeIndexAccess
         )
     }
 
@@ -106,6 +112,10 @@ impl LuaAstNode for LuaDocType {
             LuaSyntaxKind::TypeAttribute => {
                 Some(LuaDocType::Attribute(LuaDocAttributeType::cast(syntax)?))
             }
+            LuaSyntaxKind::TypeMapped => Some(LuaDocType::Mapped(LuaDocMappedType::cast(syntax)?)),
+            LuaSyntaxKind::TypeIndexAccess => Some(LuaDocType::IndexAccess(
+                LuaDocIndexAccessType::cast(syntax)?,
+            )),
             _ => None,
         }
     }
@@ -665,6 +675,41 @@ impl LuaDocStrTplType {
 
         (first, second, third)
     }
+
+    /// The pieces of a template literal like `on_${Event}`, `None` for the `T` form.
+    pub fn get_template_parts(&self) -> Option<Vec<LuaDocStrTplPart>> {
+        let str_tpl = self.token_by_kind(LuaTokenKind::TkStringTemplateType)?;
+        let text = str_tpl.get_text();
+        let body = text.strip_prefix('`')?.strip_suffix('`')?;
+        if !body.contains("${") {
+            return None;
+        }
+
+        let mut parts = Vec::new();
+        let mut rest = body;
+        while let Some(start) = rest.find("${") {
+            if start > 0 {
+                parts.push(LuaDocStrTplPart::Text(rest[..start].to_string()));
+            }
+            let placeholder = &rest[start + 2..];
+            let end = placeholder.find('}')?;
+            parts.push(LuaDocStrTplPart::Type(
+                placeholder[..end].trim().to_string(),
+            ));
+            rest = &placeholder[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(LuaDocStrTplPart::Text(rest.to_string()));
+        }
+
+        Some(parts)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LuaDocStrTplPart {
+    Text(String),
+    Type(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -773,3 +818,135 @@ impl LuaDocAttributeType {
         self.children()
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LuaDocMappedType {
+    syntax: LuaSyntaxNode,
+}
+
+impl LuaAstNode for LuaDocMappedType {
+    fn syntax(&self) -> &LuaSyntaxNode {
+        &self.syntax
+    }
+
+    fn can_cast(kind: LuaSyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == LuaSyntaxKind::TypeMapped
+    }
+
+    fn cast(syntax: LuaSyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind().into()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+}
+
+impl LuaDocMappedType {
+    pub fn get_mapped_keys(&self) -> Option<LuaDocMappedKeys> {
+        self.child()
+    }
+
+    pub fn get_value_type(&self) -> Option<LuaDocType> {
+        self.child()
+    }
+
+    /// `?` or `+?` after the keys
+    pub fn is_optional(&self) -> bool {
+        self.token_by_kind(LuaTokenKind::TkDocQuestion).is_some() && !self.is_remove_optional()
+    }
+
+    /// `-?` after the keys
+    pub fn is_remove_optional(&self) -> bool {
+        self.token_by_kind(LuaTokenKind::TkMinus).is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LuaDocMappedKeys {
+    syntax: LuaSyntaxNode,
+}
+
+impl LuaAstNode for LuaDocMappedKeys {
+    fn syntax(&self) -> &LuaSyntaxNode {
+        &self.syntax
+    }
+
+    fn can_cast(kind: LuaSyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == LuaSyntaxKind::DocMappedKeys
+    }
+
+    fn cast(syntax: LuaSyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind().into()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+}
+
+impl LuaDocMappedKeys {
+    pub fn get_name_token(&self) -> Option<LuaNameToken> {
+        self.token()
+    }
+
+    /// The type after `in`
+    pub fn get_key_type(&self) -> Option<LuaDocType> {
+        self.child()
+    }
+
+    /// The type after `as`
+    pub fn get_remap_type(&self) -> Option<LuaDocType> {
+        self.children().nth(1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LuaDocIndexAccessType {
+    syntax: LuaSyntaxNode,
+}
+
+impl LuaAstNode for LuaDocIndexAccessType {
+    fn syntax(&self) -> &LuaSyntaxNode {
+        &self.syntax
+    }
+
+    fn can_cast(kind: LuaSyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == LuaSyntaxKind::TypeIndexAccess
+    }
+
+    fn cast(syntax: LuaSyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind().into()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+}
+
+impl LuaDocIndexAccessType {
+    pub fn get_types(&self) -> Option<(LuaDocType, LuaDocType)> {
+        let mut children = self.children();
+        let base = children.next()?;
+        let key = children.next()?;
+        Some((base, key))
+    }
+}
@@ -109,6 +109,8 @@ pub enum LuaAst {
     LuaDocGenericType(LuaDocGenericType),
     LuaDocStrTplType(LuaDocStrTplType),
     LuaDocMultiLineUnionType(LuaDocMultiLineUnionType),
+    LuaDocMappedType(LuaDocMappedType),
+    LuaDocIndexAccessType(LuaDocIndexAccessType),
     // other structure do not need enum here
 }
 
@@ -196,6 +198,8 @@ impl LuaAstNode for LuaAst {
             LuaAst::LuaDocGenericType(node) => node.syntax(),
             LuaAst::LuaDocStrTplType(node) => node.syntax(),
             LuaAst::LuaDocMultiLineUnionType(node) => node.syntax(),
+            LuaAst::LuaDocMappedType(node) => node.syntax(),
+            LuaAst::LuaDocIndexAccessType(node) => node.syntax(),
         }
     }
 
@@ -291,6 +295,8 @@ impl LuaAstNode for LuaAst {
                 | LuaSyntaxKind::TypeGeneric
                 | LuaSyntaxKind::TypeStringTemplate
                 | LuaSyntaxKind::TypeMultiLineUnion
+                | LuaSyntaxKind::TypeMapped
+                | LuaSyntaxKind::TypeIndexAccess
                 | LuaSyntaxKind::DocAttributeUse
         )
     }
@@ -457,6 +463,12 @@ impl LuaAstNode for LuaAst {
             LuaSyntaxKind::TypeMultiLineUnion => {
                 LuaDocMultiLineUnionType::cast(syntax).map(LuaAst::LuaDocMultiLineUnionType)
             }
+            LuaSyntaxKind::TypeMapped => {
+                LuaDocMappedType::cast(syntax).map(LuaAst::LuaDocMappedType)
+            }
+            LuaSyntaxKind::TypeIndexAccess => {
+                LuaDocIndexAccessType::cast(syntax).map(LuaAst::LuaDocIndexAccessType)
+            }
             LuaSyntaxKind::DocTagAttributeUse => {
                 LuaDocTagAttributeUse::cast(syntax).map(LuaAst::LuaDocTagAttributeUse)
             }